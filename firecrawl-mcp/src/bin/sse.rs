@@ -1,15 +1,29 @@
 use anyhow::Result;
 use firecrawl_mcp::FirecrawlMCP;
+use firecrawl_mcp::limits::{limit_sessions, SessionLimit};
 use rmcp::transport::streamable_http_server::{
     session::local::LocalSessionManager, StreamableHttpServerConfig, StreamableHttpService,
 };
 use std::env;
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio_util::sync::CancellationToken;
-use tracing::info;
+use tracing::{info, warn};
 use tracing_subscriber::{self, EnvFilter};
 
+/// Reads a positive number from the environment variable `name`, or returns `default`.
+fn env_limit(name: &str, default: u64) -> u64 {
+    match env::var(name) {
+        Ok(value) => value
+            .parse()
+            .ok()
+            .filter(|limit| *limit > 0)
+            .unwrap_or_else(|| panic!("{name} must be a positive number")),
+        Err(_) => default,
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     tracing_subscriber::fmt()
@@ -33,23 +47,59 @@ async fn main() -> Result<()> {
         .expect("invalid bind address");
 
     let client = reqwest::Client::new();
+    // Tool calls would fail the same way, so only warn
+    if let Err(e) = FirecrawlMCP::new(&api_key, client.clone())
+        .client
+        .verify_connection()
+        .await
+    {
+        warn!("Firecrawl API check failed: {e}");
+    }
     let api_key_for_factory = api_key.clone();
     let client_for_factory = client.clone();
+    #[cfg(feature = "sanitize")]
+    let sanitize = env::var("FIRECRAWL_SANITIZE").is_ok_and(|v| v == "1" || v == "true");
+
+    let max_sessions = env_limit("MCP_MAX_SESSIONS", 100) as usize;
+    let max_tool_calls = env_limit("MCP_MAX_TOOL_CALLS_PER_SESSION", 8) as usize;
+    let idle_timeout = Duration::from_secs(env_limit("MCP_SESSION_IDLE_TIMEOUT_SECS", 300));
+    info!(
+        "Allowing {max_sessions} sessions, {max_tool_calls} concurrent tool calls per session, closing sessions idle for {}s",
+        idle_timeout.as_secs()
+    );
 
     let cancel = CancellationToken::new();
     let config = StreamableHttpServerConfig::default()
         .disable_allowed_hosts()
         .with_cancellation_token(cancel.clone());
 
-    let session_manager = Arc::new(LocalSessionManager::default());
+    let mut session_manager = LocalSessionManager::default();
+    session_manager.session_config.keep_alive = Some(idle_timeout);
+    let session_manager = Arc::new(session_manager);
+    let session_limit = SessionLimit::new(session_manager.clone(), max_sessions);
     let service: StreamableHttpService<FirecrawlMCP, LocalSessionManager> =
         StreamableHttpService::new(
-            move || Ok(FirecrawlMCP::new(&api_key_for_factory, client_for_factory.clone())),
+            move || {
+                let controller = FirecrawlMCP::new(&api_key_for_factory, client_for_factory.clone())
+                    .with_max_concurrent_tool_calls(max_tool_calls);
+                #[cfg(feature = "sanitize")]
+                let controller = if sanitize {
+                    controller.with_sanitize(Default::default())
+                } else {
+                    controller
+                };
+                Ok(controller)
+            },
             session_manager,
             config,
         );
 
-    let router = axum::Router::new().nest_service("/mcp", service);
+    let router = axum::Router::new()
+        .nest_service("/mcp", service)
+        .layer(axum::middleware::from_fn_with_state(
+            session_limit,
+            limit_sessions,
+        ));
 
     let tcp_listener = tokio::net::TcpListener::bind(bind_addr).await?;
     info!(
@@ -2,7 +2,7 @@ use anyhow::Result;
 use firecrawl_mcp::FirecrawlMCP;
 use rmcp::{ServiceExt, transport::stdio};
 use std::env;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 use tracing_subscriber::{self, EnvFilter};
 
 /// npx @modelcontextprotocol/inspector cargo run -p mcp-server-examples --example std_io
@@ -23,6 +23,17 @@ async fn main() -> Result<()> {
 
     // Create a Controller instance
     let controller = FirecrawlMCP::new(api_key, reqwest::Client::new());
+    #[cfg(feature = "sanitize")]
+    let controller = if env::var("FIRECRAWL_SANITIZE").is_ok_and(|v| v == "1" || v == "true") {
+        controller.with_sanitize(Default::default())
+    } else {
+        controller
+    };
+
+    // Tool calls would fail the same way, so only warn
+    if let Err(e) = controller.client.verify_connection().await {
+        warn!("Firecrawl API check failed: {}", e);
+    }
 
     // Create the service with our controller using stdio transport
     let service = controller.serve(stdio()).await.inspect_err(|e| {
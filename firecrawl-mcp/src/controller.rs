@@ -12,7 +12,13 @@ pub mod map;
 pub use map::{MAP_TOOL_NAME, get_firecrawl_map};
 #[cfg(feature = "scrape")]
 pub mod scrape;
-#[cfg(feature = "batch-scrape")]
+#[cfg(any(
+    feature = "batch-scrape",
+    feature = "crawl",
+    feature = "map",
+    feature = "scrape",
+    feature = "search"
+))]
 use rmcp::model::ContentBlock;
 #[cfg(feature = "scrape")]
 pub use scrape::{SCRAPE_TOOL_NAME, get_firecrawl_scrape};
@@ -21,16 +27,23 @@ pub mod search;
 #[cfg(feature = "search")]
 pub use search::{SEARCH_TOOL_NAME, get_firecrawl_search};
 
+use crate::messages::Messages;
 use firecrawl_sdk::FirecrawlApp;
+#[cfg(feature = "sanitize")]
+use firecrawl_sdk::sanitize::{SanitizeOptions, sanitize_markdown};
+#[cfg(any(feature = "batch-scrape", feature = "crawl", feature = "scrape"))]
+use firecrawl_sdk::scrape::{ScrapeFormats, ScrapeOptions};
 use rmcp::{
     ErrorData as McpError, RoleServer, ServerHandler,
     model::{
-        CallToolRequestParams, CallToolResult, ListToolsResult, PaginatedRequestParams,
-        ProtocolVersion, ServerCapabilities, ServerInfo, Tool,
+        CallToolRequestParams, CallToolResult, Implementation, JsonObject, ListToolsResult,
+        PaginatedRequestParams, ProtocolVersion, ServerCapabilities, ServerInfo, Tool,
     },
     service::RequestContext,
 };
 use std::sync::{Arc, LazyLock};
+use tokio::sync::Semaphore;
+#[cfg(feature = "batch-scrape")]
 use tracing::error;
 
 /// Extension trait to convert FirecrawlApp into FirecrawlMCP
@@ -126,20 +139,42 @@ pub static TOOLS: LazyLock<Arc<[Tool]>> = LazyLock::new(|| {
     ])
 });
 
+const DEFAULT_INSTRUCTIONS: &str =
+    "This server provides tools to crawl, scrape, and search the web using Firecrawl.";
+
 #[derive(Clone)]
 pub struct FirecrawlMCP {
     pub client: FirecrawlApp,
+    messages: Arc<Messages>,
+    server_info: Option<Implementation>,
+    instructions: Option<String>,
+    tool_calls: Option<Arc<Semaphore>>,
+    #[cfg(any(feature = "batch-scrape", feature = "crawl", feature = "scrape"))]
+    default_scrape_options: Arc<ScrapeOptions>,
+    #[cfg(feature = "sanitize")]
+    sanitize: Option<SanitizeOptions>,
 }
 
 impl FirecrawlMCP {
     pub fn new(api_key: impl AsRef<str>, client: reqwest::Client) -> Self {
-        Self {
-            client: FirecrawlApp::new_with_client(api_key, client).unwrap(),
-        }
+        Self::new_with_app(FirecrawlApp::new_with_client(api_key, client).unwrap())
     }
 
     pub fn new_with_app(app: FirecrawlApp) -> Self {
-        Self { client: app }
+        Self {
+            client: app,
+            messages: Arc::new(Messages::default()),
+            server_info: None,
+            instructions: None,
+            tool_calls: None,
+            #[cfg(any(feature = "batch-scrape", feature = "crawl", feature = "scrape"))]
+            default_scrape_options: Arc::new(ScrapeOptions {
+                formats: Some(vec![ScrapeFormats::Markdown]),
+                ..Default::default()
+            }),
+            #[cfg(feature = "sanitize")]
+            sanitize: None,
+        }
     }
 
     pub fn new_selfhosted(
@@ -147,33 +182,96 @@ impl FirecrawlMCP {
         api_key: Option<impl AsRef<str>>,
         client: reqwest::Client,
     ) -> Self {
-        Self {
-            client: FirecrawlApp::new_selfhosted_with_client(api_url, api_key, client).unwrap(),
-        }
+        Self::new_with_app(
+            FirecrawlApp::new_selfhosted_with_client(api_url, api_key, client).unwrap(),
+        )
     }
-}
 
-impl ServerHandler for FirecrawlMCP {
-    fn get_info(&self) -> ServerInfo {
-        ServerInfo::new(ServerCapabilities::builder().enable_tools().build())
-            .with_protocol_version(ProtocolVersion::V_2024_11_05)
-            .with_instructions(
-                "This server provides tools to crawl, scrape, and search the web using Firecrawl.",
-            )
+    /// Reports `name`, `version` and `instructions` to clients instead of the defaults, e.g. to
+    /// brand the server.
+    pub fn with_server_info(
+        mut self,
+        name: impl Into<String>,
+        version: impl Into<String>,
+        instructions: impl Into<String>,
+    ) -> Self {
+        self.server_info = Some(Implementation::new(name, version));
+        self.instructions = Some(instructions.into());
+        self
     }
 
-    async fn call_tool(
+    /// Replaces the text of tool output and errors, e.g. with a translation.
+    pub fn with_messages(mut self, messages: Messages) -> Self {
+        self.messages = Arc::new(messages);
+        self
+    }
+
+    /// Fails tool calls with an MCP error while `max` calls are already running.
+    ///
+    /// Clones share the limit, so create one instance per session to limit each session.
+    pub fn with_max_concurrent_tool_calls(mut self, max: usize) -> Self {
+        self.tool_calls = Some(Arc::new(Semaphore::new(max)));
+        self
+    }
+
+    /// Fills the scrape options left unset by clients of the scrape, crawl and batch scrape tools
+    /// with `options`, see `ScrapeOptions::merge`. (default: markdown format only)
+    ///
+    /// The tools return the markdown of the pages, so `options` should keep
+    /// `ScrapeFormats::Markdown` in its formats.
+    #[cfg(any(feature = "batch-scrape", feature = "crawl", feature = "scrape"))]
+    pub fn with_default_scrape_options(mut self, options: ScrapeOptions) -> Self {
+        self.default_scrape_options = Arc::new(options);
+        self
+    }
+
+    /// Fills the scrape options of a tool call with the server's defaults.
+    #[cfg(any(feature = "batch-scrape", feature = "crawl", feature = "scrape"))]
+    fn scrape_options(&self, options: Option<ScrapeOptions>) -> ScrapeOptions {
+        options
+            .unwrap_or_default()
+            .merge(&self.default_scrape_options)
+    }
+
+    /// Sanitizes the output of every tool with `options` before returning it to the model.
+    #[cfg(feature = "sanitize")]
+    pub fn with_sanitize(mut self, options: SanitizeOptions) -> Self {
+        self.sanitize = Some(options);
+        self
+    }
+
+    #[cfg(any(
+        feature = "batch-scrape",
+        feature = "crawl",
+        feature = "map",
+        feature = "scrape",
+        feature = "search"
+    ))]
+    fn tool_output(&self, output: String) -> ContentBlock {
+        #[cfg(feature = "sanitize")]
+        let output = match self.sanitize.clone() {
+            Some(options) => sanitize_markdown(&output, options),
+            None => output,
+        };
+        ContentBlock::text(output)
+    }
+
+    async fn call_tool_by_name(
         &self,
-        request: CallToolRequestParams,
-        _context: RequestContext<RoleServer>,
+        tool_name: &str,
+        params: JsonObject,
     ) -> Result<CallToolResult, McpError> {
-        let tool_name = request.name;
-        let params = request.arguments.unwrap();
+        let _permit = match &self.tool_calls {
+            Some(tool_calls) => Some(tool_calls.try_acquire().map_err(|_| {
+                McpError::invalid_request(self.messages.too_many_tool_calls.clone(), None)
+            })?),
+            None => None,
+        };
 
-        match tool_name.as_ref() {
+        match tool_name {
             #[cfg(feature = "batch-scrape")]
             BATCH_SCRAPE_TOOL_NAME => match self.batch_scrape(params).await {
-                Ok(result) => Ok(CallToolResult::success(vec![ContentBlock::text(result)])),
+                Ok(result) => Ok(CallToolResult::success(vec![self.tool_output(result)])),
                 Err(err) => {
                     error!("Batch scraping URLs failed: {}", err);
                     Err(err)
@@ -181,33 +279,54 @@ impl ServerHandler for FirecrawlMCP {
             },
             #[cfg(feature = "crawl")]
             CRAWL_TOOL_NAME => match self.crawl(params).await {
-                Ok(result) => Ok(CallToolResult::success(vec![ContentBlock::text(result)])),
+                Ok(result) => Ok(CallToolResult::success(vec![self.tool_output(result)])),
                 Err(err) => Err(err),
             },
             #[cfg(feature = "map")]
             MAP_TOOL_NAME => match self.map(params).await {
-                Ok(result) => Ok(CallToolResult::success(vec![ContentBlock::text(result)])),
+                Ok(result) => Ok(CallToolResult::success(vec![self.tool_output(result)])),
                 Err(err) => Err(err),
             },
             #[cfg(feature = "scrape")]
             SCRAPE_TOOL_NAME => match self.scrape(params).await {
-                Ok(result) => Ok(CallToolResult::success(vec![ContentBlock::text(result)])),
+                Ok(result) => Ok(CallToolResult::success(vec![self.tool_output(result)])),
                 Err(err) => Err(err),
             },
             #[cfg(feature = "search")]
             SEARCH_TOOL_NAME => match self.search(params).await {
-                Ok(result) => Ok(CallToolResult::success(vec![ContentBlock::text(result)])),
+                Ok(result) => Ok(CallToolResult::success(vec![self.tool_output(result)])),
                 Err(err) => Err(McpError::internal_error(
-                    format!("Search error: {}", err),
+                    format!("{}: {}", self.messages.search_error, err),
                     None,
                 )),
             },
             _ => Err(McpError::invalid_request(
-                format!("Tool not found: {}", tool_name),
+                format!("{}: {}", self.messages.tool_not_found, tool_name),
                 None,
             )),
         }
     }
+}
+
+impl ServerHandler for FirecrawlMCP {
+    fn get_info(&self) -> ServerInfo {
+        let info = ServerInfo::new(ServerCapabilities::builder().enable_tools().build())
+            .with_protocol_version(ProtocolVersion::V_2024_11_05)
+            .with_instructions(self.instructions.as_deref().unwrap_or(DEFAULT_INSTRUCTIONS));
+        match &self.server_info {
+            Some(server_info) => info.with_server_info(server_info.clone()),
+            None => info,
+        }
+    }
+
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let params = request.arguments.unwrap();
+        self.call_tool_by_name(request.name.as_ref(), params).await
+    }
 
     async fn list_tools(
         &self,
@@ -217,3 +336,246 @@ impl ServerHandler for FirecrawlMCP {
         Ok(ListToolsResult::with_all_items(Vec::from(TOOLS.as_ref())))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(any(feature = "scrape", feature = "search"))]
+    use serde_json::json;
+    #[cfg(any(feature = "scrape", feature = "search"))]
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path},
+    };
+
+    #[cfg(feature = "search")]
+    fn french() -> Messages {
+        Messages {
+            no_search_results: "Aucun résultat trouvé.".to_string(),
+            search_error: "Erreur de recherche".to_string(),
+            tool_not_found: "Outil introuvable".to_string(),
+            url_label: "Adresse".to_string(),
+            title_label: "Titre".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[cfg(any(feature = "scrape", feature = "search"))]
+    async fn mcp(server: &MockServer, messages: Messages) -> FirecrawlMCP {
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        FirecrawlMCP::new_with_app(app).with_messages(messages)
+    }
+
+    #[cfg(any(feature = "scrape", feature = "search"))]
+    fn text(result: &CallToolResult) -> &str {
+        &result.content[0].as_text().unwrap().text
+    }
+
+    #[cfg(feature = "search")]
+    fn search_params() -> JsonObject {
+        json!({ "query": "firecrawl" }).as_object().unwrap().clone()
+    }
+
+    #[cfg(feature = "search")]
+    #[tokio::test]
+    async fn test_custom_messages_in_tool_output() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/search"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "data": []
+            })))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/search"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "data": [{
+                    "url": "https://firecrawl.dev",
+                    "title": "Firecrawl",
+                    "description": "Web data for AI"
+                }]
+            })))
+            .mount(&server)
+            .await;
+
+        let mcp = mcp(&server, french()).await;
+        let result = mcp
+            .call_tool_by_name(SEARCH_TOOL_NAME, search_params())
+            .await
+            .unwrap();
+        assert_eq!(text(&result), "Aucun résultat trouvé.");
+
+        let result = mcp
+            .call_tool_by_name(SEARCH_TOOL_NAME, search_params())
+            .await
+            .unwrap();
+        assert_eq!(
+            text(&result),
+            "Titre: Firecrawl\nAdresse: https://firecrawl.dev\nDescription: Web data for AI\n"
+        );
+    }
+
+    #[cfg(feature = "search")]
+    #[tokio::test]
+    async fn test_custom_messages_in_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/search"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        let mcp = mcp(&server, french()).await;
+        let error = mcp
+            .call_tool_by_name(SEARCH_TOOL_NAME, search_params())
+            .await
+            .unwrap_err();
+        assert!(
+            error.message.starts_with("Erreur de recherche: "),
+            "{}",
+            error.message
+        );
+
+        let error = mcp
+            .call_tool_by_name("firecrawl_unknown", JsonObject::new())
+            .await
+            .unwrap_err();
+        assert_eq!(error.message, "Outil introuvable: firecrawl_unknown");
+    }
+
+    #[cfg(feature = "search")]
+    #[tokio::test]
+    async fn test_default_messages() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/search"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "data": []
+            })))
+            .mount(&server)
+            .await;
+
+        let mcp = mcp(&server, Messages::default()).await;
+        let result = mcp
+            .call_tool_by_name(SEARCH_TOOL_NAME, search_params())
+            .await
+            .unwrap();
+        assert_eq!(text(&result), "No search results found.");
+
+        let error = mcp
+            .call_tool_by_name("firecrawl_unknown", JsonObject::new())
+            .await
+            .unwrap_err();
+        assert_eq!(error.message, "Tool not found: firecrawl_unknown");
+    }
+
+    #[cfg(feature = "search")]
+    #[tokio::test]
+    async fn test_max_concurrent_tool_calls() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/search"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({ "success": true, "data": [] }))
+                    .set_delay(std::time::Duration::from_millis(300)),
+            )
+            .mount(&server)
+            .await;
+
+        let mcp = mcp(&server, Messages::default())
+            .await
+            .with_max_concurrent_tool_calls(1);
+        let (first, second) = tokio::join!(
+            mcp.call_tool_by_name(SEARCH_TOOL_NAME, search_params()),
+            async {
+                tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+                mcp.call_tool_by_name(SEARCH_TOOL_NAME, search_params())
+                    .await
+            }
+        );
+        assert!(first.is_ok());
+        assert_eq!(
+            second.unwrap_err().message,
+            "Too many concurrent tool calls, retry once one has finished"
+        );
+
+        // The limit is released once the first call finished
+        assert!(
+            mcp.call_tool_by_name(SEARCH_TOOL_NAME, search_params())
+                .await
+                .is_ok()
+        );
+    }
+
+    #[cfg(feature = "scrape")]
+    #[tokio::test]
+    async fn test_caller_scrape_options_win_over_defaults() {
+        use wiremock::matchers::body_json;
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/scrape"))
+            .and(body_json(json!({
+                "url": "https://example.com",
+                "formats": ["markdown", "links"],
+                "onlyMainContent": false,
+                "timeout": 30000
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "data": {
+                    "markdown": "# Example",
+                    "metadata": { "sourceURL": "https://example.com", "statusCode": 200 }
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mcp = mcp(&server, Messages::default())
+            .await
+            .with_default_scrape_options(
+                ScrapeOptions::default()
+                    .with_formats([ScrapeFormats::Markdown])
+                    .with_only_main_content(true)
+                    .with_timeout(30_000),
+            );
+        let params = json!({
+            "url": "https://example.com",
+            "formats": ["markdown", "links"],
+            "onlyMainContent": false
+        });
+        let result = mcp
+            .call_tool_by_name(SCRAPE_TOOL_NAME, params.as_object().unwrap().clone())
+            .await
+            .unwrap();
+        assert_eq!(text(&result), "# Example");
+    }
+
+    #[test]
+    fn test_server_info() {
+        let app = FirecrawlApp::new_selfhosted("http://localhost:3002", None::<String>).unwrap();
+        let mcp = FirecrawlMCP::new_with_app(app);
+
+        let info = mcp.get_info();
+        assert_eq!(info.instructions.as_deref(), Some(DEFAULT_INSTRUCTIONS));
+        assert_eq!(info.server_info, Implementation::from_build_env());
+
+        let info = mcp
+            .with_server_info("acme-web", "2.1.0", "Acme's web research tools.")
+            .get_info();
+        assert_eq!(info.server_info.name, "acme-web");
+        assert_eq!(info.server_info.version, "2.1.0");
+        assert_eq!(
+            info.instructions.as_deref(),
+            Some("Acme's web research tools.")
+        );
+        assert!(info.capabilities.tools.is_some());
+    }
+}
@@ -1,8 +1,8 @@
 use anyhow::Result;
 use async_claude::define_tool;
 use firecrawl_sdk::{
-    batch_scrape::{BatchScrapeUrlsInput, Webhook},
-    scrape::{ScrapeFormats, ScrapeOptions},
+    PollPolicy,
+    batch_scrape::{BatchScrapeParams, BatchScrapeUrlsInput, Webhook},
 };
 use rmcp::{handler::server::tool::parse_json_object, model::JsonObject};
 
@@ -18,7 +18,7 @@ define_tool!(
 );
 
 impl FirecrawlMCP {
-    pub async fn batch_scrape(&self, input: JsonObject) -> Result<String, rmcp::Error> {
+    pub async fn batch_scrape(&self, input: JsonObject) -> Result<String, rmcp::ErrorData> {
         //deserialize the json object into a ScrapeOptions struct
         let mut options = parse_json_object::<BatchScrapeUrlsInput>(input)?;
 
@@ -27,39 +27,33 @@ impl FirecrawlMCP {
             options.webhook = Some(Webhook::dummy());
         }
 
-        match &mut options.options {
-            Some(scrape_options) => {
-                scrape_options.formats = Some(vec![ScrapeFormats::Markdown]);
-            }
-            None => {
-                options.options = Some(ScrapeOptions {
-                    formats: Some(vec![ScrapeFormats::Markdown]),
-                    ..Default::default()
-                })
-            }
-        }
+        options.options = Some(self.scrape_options(options.options.take()));
 
         let result = self
             .client
-            .batch_scrape_urls(
-                options.urls,
-                options.options,
-                options.poll_interval,
-                None,
-                options.webhook.unwrap(),
-                Some(true),
-            )
+            .batch_scrape(BatchScrapeParams {
+                urls: options.urls,
+                options: options.options,
+                webhook: options.webhook.unwrap(),
+                poll_policy: options.poll_interval.map(PollPolicy::from_millis),
+                idempotency_key: None,
+                ignore_invalid_urls: Some(true),
+                cost_center: None,
+            })
             .await
-            .map_err(|e| rmcp::Error::internal_error(e.to_string(), None))?;
+            .map_err(|e| rmcp::ErrorData::internal_error(e.to_string(), None))?;
 
         let formatted = result
             .data
             .iter()
             .map(|d| {
                 format!(
-                    "URL: {}\nTitle: {}\nContent: {}\n\n",
+                    "{}: {}\n{}: {}\n{}: {}\n\n",
+                    self.messages.url_label,
                     d.metadata.source_url,
+                    self.messages.title_label,
                     d.metadata.title.as_ref().unwrap_or(&"".to_string()),
+                    self.messages.content_label,
                     d.markdown.as_ref().unwrap_or(&"".to_string())
                 )
             })
@@ -1,11 +1,11 @@
 use anyhow::Result;
 use async_claude::define_tool;
 use firecrawl_sdk::{
-    batch_scrape::Webhook,
-    crawl::CrawlUrlInput,
-    scrape::{ScrapeFormats, ScrapeOptions},
+    PollPolicy,
+    crawl::{CrawlParams, CrawlUrlInput},
+    webhook::Webhook,
 };
-use rmcp::{Error, handler::server::tool::parse_json_object, model::JsonObject};
+use rmcp::{ErrorData, handler::server::tool::parse_json_object, model::JsonObject};
 
 use super::FirecrawlMCP;
 
@@ -19,46 +19,40 @@ define_tool!(
 );
 
 impl FirecrawlMCP {
-    pub async fn crawl(&self, input: JsonObject) -> Result<String, Error> {
+    pub async fn crawl(&self, input: JsonObject) -> Result<String, ErrorData> {
         let mut options = parse_json_object::<CrawlUrlInput>(input)?;
 
         if options.webhook.is_none() {
             options.webhook = Some(Webhook::dummy());
         }
 
-        // Set the formats to Markdown regardless of whether scrape_options exists
-        match &mut options.options.scrape_options {
-            Some(scrape_options) => {
-                scrape_options.formats = Some(vec![ScrapeFormats::Markdown]);
-            }
-            None => {
-                options.options.scrape_options = Some(ScrapeOptions {
-                    formats: Some(vec![ScrapeFormats::Markdown]),
-                    ..Default::default()
-                });
-            }
-        }
+        options.options.scrape_options =
+            Some(self.scrape_options(options.options.scrape_options.take()));
 
         let results = self
             .client
-            .crawl_url(
-                options.url,
-                Some(options.options),
-                options.webhook.unwrap(),
-                options.poll_interval,
-                None,
-            )
+            .crawl(CrawlParams {
+                url: options.url,
+                options: Some(options.options),
+                webhook: options.webhook.unwrap(),
+                poll_policy: options.poll_interval.map(PollPolicy::from_millis),
+                idempotency_key: None,
+                cost_center: None,
+            })
             .await
-            .map_err(|e| Error::internal_error(e.to_string(), None))?;
+            .map_err(|e| ErrorData::internal_error(e.to_string(), None))?;
 
         let formatted = results
             .data
             .iter()
             .map(|d| {
                 format!(
-                    "URL: {}\nTitle: {}\nContent: {}",
+                    "{}: {}\n{}: {}\n{}: {}",
+                    self.messages.url_label,
                     d.metadata.source_url,
+                    self.messages.title_label,
                     d.metadata.title.as_ref().unwrap_or(&"".to_string()),
+                    self.messages.content_label,
                     d.markdown.as_ref().unwrap_or(&"".to_string())
                 )
             })
@@ -17,7 +17,7 @@ define_tool!(
 );
 
 impl FirecrawlMCP {
-    pub async fn map(&self, input: JsonObject) -> Result<String, rmcp::Error> {
+    pub async fn map(&self, input: JsonObject) -> Result<String, rmcp::ErrorData> {
         // Deserialize the json object into a MapUrlInput struct
         let options = parse_json_object::<MapUrlInput>(input)?;
 
@@ -26,11 +26,11 @@ impl FirecrawlMCP {
             .client
             .map_url(options.url, Some(options.options))
             .await
-            .map_err(|e| rmcp::Error::internal_error(e.to_string(), None))?;
+            .map_err(|e| rmcp::ErrorData::internal_error(e.to_string(), None))?;
 
         // Format the result as a JSON array of URLs
         let json_result = json!(result);
         serde_json::to_string_pretty(&json_result)
-            .map_err(|e| rmcp::Error::internal_error(e.to_string(), None))
+            .map_err(|e| rmcp::ErrorData::internal_error(e.to_string(), None))
     }
 }
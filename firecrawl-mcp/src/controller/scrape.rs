@@ -1,6 +1,6 @@
 use anyhow::Result;
 use async_claude::define_tool;
-use firecrawl_sdk::scrape::{ScrapeFormats, ScrapeUrlInput};
+use firecrawl_sdk::scrape::ScrapeUrlInput;
 use rmcp::{handler::server::tool::parse_json_object, model::JsonObject};
 
 use super::FirecrawlMCP;
@@ -15,16 +15,16 @@ define_tool!(
 );
 
 impl FirecrawlMCP {
-    pub async fn scrape(&self, input: JsonObject) -> Result<String, rmcp::Error> {
+    pub async fn scrape(&self, input: JsonObject) -> Result<String, rmcp::ErrorData> {
         //deserialize the json object into a ScrapeOptions struct
-        let mut options = parse_json_object::<ScrapeUrlInput>(input)?;
-        options.options.formats = Some(vec![ScrapeFormats::Markdown]);
+        let options = parse_json_object::<ScrapeUrlInput>(input)?;
+        let scrape_options = self.scrape_options(Some(options.options));
 
         let result = self
             .client
-            .scrape_url(options.url, Some(options.options))
+            .scrape_url(options.url, scrape_options)
             .await
-            .map_err(|e| rmcp::Error::internal_error(e.to_string(), None))?;
+            .map_err(|e| rmcp::ErrorData::internal_error(e.to_string(), None))?;
         Ok(result.markdown.unwrap_or_default())
     }
 }
@@ -15,7 +15,7 @@ define_tool!(
 );
 
 impl FirecrawlMCP {
-    pub async fn search(&self, input: JsonObject) -> Result<String, rmcp::Error> {
+    pub async fn search(&self, input: JsonObject) -> Result<String, rmcp::ErrorData> {
         // Deserialize the json object into a SearchInput struct
         let options = parse_json_object::<SearchInput>(input)?;
 
@@ -24,19 +24,24 @@ impl FirecrawlMCP {
             .client
             .search(options.query, Some(options.options))
             .await
-            .map_err(|e| rmcp::Error::internal_error(e.to_string(), None))?;
+            .map_err(|e| rmcp::ErrorData::internal_error(e.to_string(), None))?;
 
         // Format the results as a readable string
         if results.is_empty() {
-            return Ok("No search results found.".to_string());
+            return Ok(self.messages.no_search_results.clone());
         }
 
         let formatted = results
             .iter()
             .map(|r| {
                 format!(
-                    "Title: {}\nURL: {}\nDescription: {}\n",
-                    r.title, r.url, r.description
+                    "{}: {}\n{}: {}\n{}: {}\n",
+                    self.messages.title_label,
+                    r.title,
+                    self.messages.url_label,
+                    r.url,
+                    self.messages.description_label,
+                    r.description
                 )
             })
             .collect::<Vec<_>>()
@@ -1,4 +1,7 @@
 mod controller;
+pub mod limits;
+pub mod messages;
 pub use controller::*;
 pub use controller::{FirecrawlMCP, IntoFirecrawlMCP};
 pub use firecrawl_sdk::FirecrawlApp;
+pub use messages::Messages;
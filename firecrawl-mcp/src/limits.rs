@@ -0,0 +1,164 @@
+//! Limits keeping a single client of the Streamable HTTP server from exhausting its resources.
+//!
+//! Tool calls per session are limited by [`FirecrawlMCP::with_max_concurrent_tool_calls`] and idle
+//! sessions are closed by `SessionConfig.keep_alive` of the session manager. [`limit_sessions`]
+//! caps the number of open sessions.
+//!
+//! [`FirecrawlMCP::with_max_concurrent_tool_calls`]: crate::FirecrawlMCP::with_max_concurrent_tool_calls
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Request, State},
+    http::{Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use rmcp::transport::{
+    common::http_header::HEADER_SESSION_ID,
+    streamable_http_server::session::local::LocalSessionManager,
+};
+
+/// State of the [`limit_sessions`] middleware.
+#[derive(Debug, Clone)]
+pub struct SessionLimit {
+    sessions: Arc<LocalSessionManager>,
+    max_sessions: usize,
+}
+
+impl SessionLimit {
+    /// Allows at most `max_sessions` sessions of `sessions` to be open at the same time.
+    pub fn new(sessions: Arc<LocalSessionManager>, max_sessions: usize) -> Self {
+        Self {
+            sessions,
+            max_sessions,
+        }
+    }
+}
+
+/// Axum middleware refusing new sessions with `429 Too Many Requests` while the limit is reached.
+///
+/// Requests of already open sessions are always let through. The limit is checked before the
+/// session is created, so a burst of concurrent `initialize` requests can briefly exceed it.
+///
+/// ```no_run
+/// # use std::sync::Arc;
+/// # use rmcp::transport::streamable_http_server::session::local::LocalSessionManager;
+/// use firecrawl_mcp::limits::{SessionLimit, limit_sessions};
+///
+/// let sessions = Arc::new(LocalSessionManager::default());
+/// let router = axum::Router::new().layer(axum::middleware::from_fn_with_state(
+///     SessionLimit::new(sessions, 100),
+///     limit_sessions,
+/// ));
+/// # let _: axum::Router = router;
+/// ```
+pub async fn limit_sessions(
+    State(limit): State<SessionLimit>,
+    request: Request,
+    next: Next,
+) -> Response {
+    // Sessions are created by a POST without a session id
+    let creates_session =
+        request.method() == Method::POST && !request.headers().contains_key(HEADER_SESSION_ID);
+    if creates_session && limit.sessions.sessions.read().await.len() >= limit.max_sessions {
+        tracing::warn!(
+            "Refusing a new session, {} sessions are open",
+            limit.max_sessions
+        );
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            "Too many open sessions, retry later",
+        )
+            .into_response();
+    }
+
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FirecrawlMCP;
+    use firecrawl_sdk::FirecrawlApp;
+    use rmcp::transport::streamable_http_server::{
+        StreamableHttpServerConfig, StreamableHttpService,
+    };
+    use serde_json::json;
+
+    async fn serve(max_sessions: usize) -> String {
+        let sessions = Arc::new(LocalSessionManager::default());
+        let service: StreamableHttpService<FirecrawlMCP, LocalSessionManager> =
+            StreamableHttpService::new(
+                || {
+                    let app = FirecrawlApp::new_selfhosted("http://localhost:3002", None::<String>)
+                        .unwrap();
+                    Ok(FirecrawlMCP::new_with_app(app))
+                },
+                sessions.clone(),
+                StreamableHttpServerConfig::default().disable_allowed_hosts(),
+            );
+        let router = axum::Router::new().nest_service("/mcp", service).layer(
+            axum::middleware::from_fn_with_state(
+                SessionLimit::new(sessions, max_sessions),
+                limit_sessions,
+            ),
+        );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        format!("http://{addr}/mcp")
+    }
+
+    fn post(
+        client: &reqwest::Client,
+        url: &str,
+        message: serde_json::Value,
+    ) -> reqwest::RequestBuilder {
+        client
+            .post(url)
+            .header("Accept", "application/json, text/event-stream")
+            .header("Content-Type", "application/json")
+            .body(message.to_string())
+    }
+
+    async fn initialize(client: &reqwest::Client, url: &str) -> reqwest::Response {
+        let message = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2024-11-05",
+                "capabilities": {},
+                "clientInfo": { "name": "test", "version": "1.0.0" }
+            }
+        });
+        post(client, url, message).send().await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_sessions_over_the_limit_are_refused() {
+        let url = serve(2).await;
+        let client = reqwest::Client::new();
+
+        let first = initialize(&client, &url).await;
+        assert_eq!(first.status(), StatusCode::OK);
+        assert!(first.headers().contains_key(HEADER_SESSION_ID));
+        assert_eq!(initialize(&client, &url).await.status(), StatusCode::OK);
+
+        let refused = initialize(&client, &url).await;
+        assert_eq!(refused.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(!refused.headers().contains_key(HEADER_SESSION_ID));
+
+        // Open sessions keep working
+        let session_id = first.headers()[HEADER_SESSION_ID].clone();
+        let initialized = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+        let response = post(&client, &url, initialized)
+            .header(HEADER_SESSION_ID, session_id)
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success(), "{}", response.status());
+    }
+}
@@ -0,0 +1,60 @@
+//! User-facing text of the MCP tools, replaceable to localize tool output and errors.
+//!
+//! Messages coming from the Firecrawl API itself, such as the details of a failed scrape, are
+//! passed through unchanged.
+
+/// Text used in tool output and errors. The defaults are in English.
+///
+/// ```
+/// use firecrawl_mcp::messages::Messages;
+///
+/// let messages = Messages {
+///     no_search_results: "Aucun résultat trouvé.".to_string(),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Messages {
+    /// Returned by the search tool when nothing matched. (default: `"No search results found."`)
+    pub no_search_results: String,
+
+    /// Prefix of search errors, followed by `: ` and the error. (default: `"Search error"`)
+    pub search_error: String,
+
+    /// Prefix of the error for an unknown tool, followed by `: ` and the tool name.
+    /// (default: `"Tool not found"`)
+    pub tool_not_found: String,
+
+    /// Error returned when a session already runs as many tool calls as
+    /// `FirecrawlMCP::with_max_concurrent_tool_calls` allows.
+    /// (default: `"Too many concurrent tool calls, retry once one has finished"`)
+    pub too_many_tool_calls: String,
+
+    /// Label of the page URL in crawl, batch scrape and search results. (default: `"URL"`)
+    pub url_label: String,
+
+    /// Label of the page title in crawl, batch scrape and search results. (default: `"Title"`)
+    pub title_label: String,
+
+    /// Label of the page content in crawl and batch scrape results. (default: `"Content"`)
+    pub content_label: String,
+
+    /// Label of the page description in search results. (default: `"Description"`)
+    pub description_label: String,
+}
+
+impl Default for Messages {
+    fn default() -> Self {
+        Self {
+            no_search_results: "No search results found.".to_string(),
+            search_error: "Search error".to_string(),
+            tool_not_found: "Tool not found".to_string(),
+            too_many_tool_calls: "Too many concurrent tool calls, retry once one has finished"
+                .to_string(),
+            url_label: "URL".to_string(),
+            title_label: "Title".to_string(),
+            content_label: "Content".to_string(),
+            description_label: "Description".to_string(),
+        }
+    }
+}
@@ -0,0 +1,299 @@
+//! Drives `FirecrawlMCP` end to end, as an MCP client would: requests are serialized and sent
+//! over an in-memory transport, with a mock Firecrawl API behind the server.
+//!
+//! The server only depends on an `AsyncRead + AsyncWrite` transport, so a `tokio::io::duplex`
+//! pipe stands in for stdio here.
+
+use firecrawl_mcp::{FirecrawlApp, FirecrawlMCP};
+use rmcp::{
+    RoleClient, ServiceExt,
+    model::{CallToolRequestParams, CallToolResult, ErrorCode},
+    service::{RunningService, ServiceError},
+};
+use serde_json::{Value, json};
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+/// Serves a `FirecrawlMCP` backed by `server` and connects a client to it.
+async fn connect(server: &MockServer) -> RunningService<RoleClient, ()> {
+    let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+    let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+    tokio::spawn(async move {
+        let service = FirecrawlMCP::new_with_app(app).serve(server_io).await?;
+        service.waiting().await?;
+        anyhow::Ok(())
+    });
+    ().serve(client_io).await.unwrap()
+}
+
+async fn call_tool(
+    client: &RunningService<RoleClient, ()>,
+    name: &'static str,
+    arguments: Value,
+) -> Result<CallToolResult, ServiceError> {
+    let arguments = arguments.as_object().unwrap().clone();
+    client
+        .call_tool(CallToolRequestParams::new(name).with_arguments(arguments))
+        .await
+}
+
+#[cfg(any(
+    feature = "batch-scrape",
+    feature = "crawl",
+    feature = "map",
+    feature = "scrape",
+    feature = "search"
+))]
+fn text(result: &CallToolResult) -> &str {
+    &result.content[0].as_text().unwrap().text
+}
+
+fn mcp_error(error: ServiceError) -> rmcp::ErrorData {
+    match error {
+        ServiceError::McpError(error) => error,
+        error => panic!("expected an MCP error, got {error}"),
+    }
+}
+
+#[cfg(any(feature = "batch-scrape", feature = "crawl"))]
+async fn mock_job(server: &MockServer, route: &str, documents: Value) {
+    Mock::given(method("POST"))
+        .and(path(route))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "success": true,
+            "id": "job-1",
+            "url": format!("https://api.firecrawl.dev{route}/job-1")
+        })))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("{route}/job-1")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "success": true,
+            "status": "completed",
+            "total": 1,
+            "completed": 1,
+            "creditsUsed": 1,
+            "expiresAt": "2025-01-02T03:04:05.000Z",
+            "data": documents
+        })))
+        .mount(server)
+        .await;
+}
+
+#[cfg(any(feature = "batch-scrape", feature = "crawl", feature = "scrape"))]
+fn document() -> Value {
+    json!({
+        "markdown": "# Example",
+        "metadata": {
+            "sourceURL": "https://example.com",
+            "title": "Example Domain",
+            "statusCode": 200
+        }
+    })
+}
+
+#[tokio::test]
+async fn test_list_tools() {
+    let server = MockServer::start().await;
+    let client = connect(&server).await;
+
+    let tools = client.list_all_tools().await.unwrap();
+    let mut names: Vec<_> = tools.iter().map(|tool| tool.name.as_ref()).collect();
+    names.sort();
+
+    let expected = [
+        #[cfg(feature = "batch-scrape")]
+        "firecrawl_batch_scrape",
+        #[cfg(feature = "crawl")]
+        "firecrawl_crawl",
+        #[cfg(feature = "map")]
+        "firecrawl_map",
+        #[cfg(feature = "scrape")]
+        "firecrawl_scrape",
+        #[cfg(feature = "search")]
+        "firecrawl_search",
+    ];
+    assert_eq!(names, expected);
+    assert!(
+        tools
+            .iter()
+            .all(|tool| tool.input_schema.contains_key("properties"))
+    );
+
+    client.cancel().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_unknown_tool() {
+    let server = MockServer::start().await;
+    let client = connect(&server).await;
+
+    let error = mcp_error(
+        call_tool(&client, "firecrawl_unknown", json!({}))
+            .await
+            .unwrap_err(),
+    );
+    assert_eq!(error.code, ErrorCode::INVALID_REQUEST);
+    assert_eq!(error.message, "Tool not found: firecrawl_unknown");
+
+    client.cancel().await.unwrap();
+}
+
+#[cfg(feature = "scrape")]
+#[tokio::test]
+async fn test_scrape_tool() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/scrape"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "success": true,
+            "data": document()
+        })))
+        .mount(&server)
+        .await;
+    let client = connect(&server).await;
+
+    let result = call_tool(
+        &client,
+        "firecrawl_scrape",
+        json!({ "url": "https://example.com" }),
+    )
+    .await
+    .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    assert_eq!(text(&result), "# Example");
+
+    // Arguments that don't match the tool's schema are rejected
+    let error = mcp_error(
+        call_tool(&client, "firecrawl_scrape", json!({ "url": 42 }))
+            .await
+            .unwrap_err(),
+    );
+    assert_eq!(error.code, ErrorCode::INVALID_PARAMS);
+
+    client.cancel().await.unwrap();
+}
+
+#[cfg(feature = "map")]
+#[tokio::test]
+async fn test_map_tool() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/map"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "success": true,
+            "links": ["https://example.com", "https://example.com/about"]
+        })))
+        .mount(&server)
+        .await;
+    let client = connect(&server).await;
+
+    let result = call_tool(
+        &client,
+        "firecrawl_map",
+        json!({ "url": "https://example.com" }),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        text(&result),
+        "[\n  \"https://example.com\",\n  \"https://example.com/about\"\n]"
+    );
+
+    client.cancel().await.unwrap();
+}
+
+#[cfg(feature = "search")]
+#[tokio::test]
+async fn test_search_tool() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/search"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "success": true,
+            "data": [{
+                "url": "https://firecrawl.dev",
+                "title": "Firecrawl",
+                "description": "Web data for AI"
+            }]
+        })))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/search"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+    let client = connect(&server).await;
+
+    let result = call_tool(&client, "firecrawl_search", json!({ "query": "firecrawl" }))
+        .await
+        .unwrap();
+    assert_eq!(
+        text(&result),
+        "Title: Firecrawl\nURL: https://firecrawl.dev\nDescription: Web data for AI\n"
+    );
+
+    // API errors surface as internal errors
+    let error = mcp_error(
+        call_tool(&client, "firecrawl_search", json!({ "query": "firecrawl" }))
+            .await
+            .unwrap_err(),
+    );
+    assert_eq!(error.code, ErrorCode::INTERNAL_ERROR);
+    assert!(
+        error.message.starts_with("Search error: "),
+        "{}",
+        error.message
+    );
+
+    client.cancel().await.unwrap();
+}
+
+#[cfg(feature = "crawl")]
+#[tokio::test]
+async fn test_crawl_tool() {
+    let server = MockServer::start().await;
+    mock_job(&server, "/v1/crawl", json!([document()])).await;
+    let client = connect(&server).await;
+
+    let result = call_tool(
+        &client,
+        "firecrawl_crawl",
+        json!({ "url": "https://example.com" }),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        text(&result),
+        "URL: https://example.com\nTitle: Example Domain\nContent: # Example"
+    );
+
+    client.cancel().await.unwrap();
+}
+
+#[cfg(feature = "batch-scrape")]
+#[tokio::test]
+async fn test_batch_scrape_tool() {
+    let server = MockServer::start().await;
+    mock_job(&server, "/v1/batch/scrape", json!([document()])).await;
+    let client = connect(&server).await;
+
+    let result = call_tool(
+        &client,
+        "firecrawl_batch_scrape",
+        json!({ "urls": ["https://example.com"] }),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        text(&result),
+        "URL: https://example.com\nTitle: Example Domain\nContent: # Example\n\n"
+    );
+
+    client.cancel().await.unwrap();
+}
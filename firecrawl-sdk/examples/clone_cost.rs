@@ -0,0 +1,29 @@
+//! Measures the cost of cloning a `FirecrawlApp`, e.g. for one clone per spawned task.
+//!
+//! ```bash
+//! CLONES=1000000 cargo run --release --package firecrawl-sdk --example clone_cost
+//! ```
+use std::{env, hint::black_box, time::Instant};
+
+use firecrawl_sdk::FirecrawlApp;
+
+fn main() {
+    let clones: u32 = env::var("CLONES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(1_000_000);
+
+    let app = FirecrawlApp::new_selfhosted("http://localhost:3002", Some("fc-benchmark"))
+        .expect("Failed to initialize FirecrawlApp");
+
+    let start = Instant::now();
+    for _ in 0..clones {
+        drop(black_box(app.clone()));
+    }
+    let elapsed = start.elapsed();
+
+    println!(
+        "{clones} clones in {elapsed:?}: {:.1} ns/clone",
+        elapsed.as_nanos() as f64 / f64::from(clones)
+    );
+}
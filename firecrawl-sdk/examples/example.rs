@@ -1,8 +1,8 @@
 use firecrawl_sdk::{
     FirecrawlApp,
-    batch_scrape::Webhook,
-    crawl::CrawlOptions,
+    crawl::{CrawlOptions, CrawlParams},
     scrape::{JsonOptions, ScrapeFormats, ScrapeOptions},
+    webhook::Webhook,
 };
 use serde_json::json;
 
@@ -29,12 +29,8 @@ async fn main() {
     };
 
     let crawl_result = app
-        .crawl_url(
-            "https://mendable.ai",
-            crawl_options,
-            Webhook::dummy(),
-            None,
-            None,
+        .crawl(
+            CrawlParams::new("https://mendable.ai", Webhook::dummy()).with_options(crawl_options),
         )
         .await;
 
@@ -0,0 +1,51 @@
+//! Measures scrape throughput with a tuned connection pool.
+//!
+//! ```bash
+//! API_URL=http://localhost:3002 REQUESTS=200 CONCURRENCY=32 \
+//!     cargo run --package firecrawl-sdk --example throughput
+//! ```
+use std::{env, time::Duration, time::Instant};
+
+use firecrawl_sdk::FirecrawlApp;
+use futures::{StreamExt, stream};
+
+#[tokio::main]
+async fn main() {
+    let api_url = env::var("API_URL").unwrap_or("http://localhost:3002".to_string());
+    let url = env::var("TARGET_URL").unwrap_or("https://example.com".to_string());
+    let requests: usize = env::var("REQUESTS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(100);
+    let concurrency: usize = env::var("CONCURRENCY")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(16);
+
+    let mut builder = FirecrawlApp::builder()
+        .api_url(api_url)
+        .pool_max_idle_per_host(concurrency)
+        .pool_idle_timeout(Duration::from_secs(300))
+        .tcp_keepalive(Duration::from_secs(60));
+    if let Ok(api_key) = env::var("FIRECRAWL_API_KEY") {
+        builder = builder.api_key(api_key);
+    }
+    if env::var("HTTP2_PRIOR_KNOWLEDGE").is_ok() {
+        builder = builder.http2_prior_knowledge();
+    }
+    let app = builder.build().expect("Failed to initialize FirecrawlApp");
+
+    let start = Instant::now();
+    let failures = stream::iter(0..requests)
+        .map(|_| app.scrape_url(&url, None))
+        .buffer_unordered(concurrency)
+        .filter(|result| std::future::ready(result.is_err()))
+        .count()
+        .await;
+    let elapsed = start.elapsed();
+
+    println!(
+        "{requests} scrapes ({failures} failed) in {elapsed:?}: {:.1} req/s",
+        requests as f64 / elapsed.as_secs_f64()
+    );
+}
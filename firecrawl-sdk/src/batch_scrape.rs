@@ -1,41 +1,22 @@
-use std::collections::HashMap;
+use std::time::Duration;
 
+use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 #[cfg(feature = "mcp-tool")]
 use schemars::JsonSchema;
 
-use crate::{API_VERSION, FirecrawlApp, FirecrawlError, document::Document, scrape::ScrapeOptions};
+use crate::{
+    CancellationToken, CostOperation, FirecrawlApp, FirecrawlError, JobKind, PartialStatus,
+    PollPolicy, apply_timeout,
+    document::{Document, append_status_page, deserialize_status_page},
+    routes,
+    scrape::ScrapeOptions,
+    time::Instant,
+};
 
-#[serde_with::skip_serializing_none]
-#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
-#[cfg_attr(feature = "mcp-tool", derive(JsonSchema))]
-#[serde(rename_all = "camelCase")]
-pub struct Webhook {
-    /// Webhook URL to notify when scraping is complete
-    pub url: String,
-
-    /// Custom headers to send with webhook
-    pub headers: Option<HashMap<String, String>>,
-
-    /// Custom metadata to include in webhook payload
-    pub metadata: Option<HashMap<String, Value>>,
-
-    /// Events that trigger the webhook
-    pub events: Option<Vec<String>>,
-}
-
-impl Webhook {
-    pub fn dummy() -> Self {
-        Webhook {
-            url: "https://webhook.example.com".to_string(),
-            headers: None,
-            metadata: None,
-            events: None,
-        }
-    }
-}
+pub use crate::webhook::Webhook;
 
 #[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
@@ -55,23 +36,25 @@ pub struct BatchScrapeRequestBody {
     pub options: ScrapeOptions,
 }
 
-#[derive(Deserialize, Serialize, Debug, Default)]
+/// A started batch scrape job, returned by [`FirecrawlApp::batch_scrape_urls_async`].
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "mcp-tool", derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
-struct BatchScrapeResponse {
+pub struct BatchScrapeInitResponse {
     /// This will always be `true` due to `FirecrawlApp::handle_response`.
-    success: bool,
+    pub success: bool,
 
     /// The ID of the batch scrape job
-    id: String,
+    pub id: String,
 
     /// The URL to check the status of the batch scrape job
-    url: String,
+    pub url: String,
 
     /// If ignoreInvalidURLs is true, this is an array containing the invalid URLs
     /// that were specified in the request. If there were no invalid URLs, this will
     /// be an empty array. If ignoreInvalidURLs is false, this field will be undefined.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    invalid_urls: Option<Vec<String>>,
+    #[serde(rename = "invalidURLs", skip_serializing_if = "Option::is_none")]
+    pub invalid_urls: Option<Vec<String>>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
@@ -99,18 +82,23 @@ pub struct BatchScrapeUrlsInput {
     pub options: Option<ScrapeOptions>,
 }
 
-#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Deserialize, Serialize, Debug, Default, PartialEq, Eq, Clone)]
 #[serde(rename_all = "camelCase")]
 pub enum BatchScrapeStatusTypes {
+    /// The batch scrape job is in progress.
+    #[default]
     Scraping,
+
+    /// The batch scrape job has been completed successfully.
     Completed,
+
+    /// The batch scrape job has failed.
     Failed,
-}
 
-impl Default for BatchScrapeStatusTypes {
-    fn default() -> Self {
-        Self::Scraping
-    }
+    /// A status this version of the SDK doesn't know, as sent by the API, which is monitored
+    /// like `Scraping`.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 #[derive(Deserialize, Serialize, Debug, Default)]
@@ -119,6 +107,15 @@ pub struct BatchScrapeStatus {
     /// This will always be `true` due to `FirecrawlApp::handle_response`.
     pub success: bool,
 
+    /// The ID of the batch scrape job. Filled in by the SDK, not part of the API response.
+    #[serde(default)]
+    pub id: String,
+
+    /// The URL to check the status of the batch scrape job. Filled in by the SDK, not part of the
+    /// API response.
+    #[serde(default)]
+    pub status_url: String,
+
     /// The status of the batch scrape job
     pub status: BatchScrapeStatusTypes,
 
@@ -135,6 +132,8 @@ pub struct BatchScrapeStatus {
     pub credits_used: usize,
 
     /// When the batch scrape results expire
+    ///
+    /// Enable the `chrono` feature to have it parsed by `expires_at_timestamp`.
     pub expires_at: Option<String>,
 
     /// Cursor for the next page of results, if any
@@ -143,44 +142,276 @@ pub struct BatchScrapeStatus {
     /// The resulting documents if the status is Completed
     #[serde(default)]
     pub data: Vec<Document>,
+
+    /// Documents of `data` the SDK couldn't deserialize, e.g. with a metadata field of an
+    /// unexpected type, as their index among the documents sent by the API and the error.
+    #[serde(skip)]
+    pub skipped_documents: Vec<(usize, String)>,
+
+    /// Id of the last status request, to share with Firecrawl support. Filled in by the SDK from
+    /// the `x-request-id` response header.
+    #[serde(skip)]
+    pub request_id: Option<String>,
 }
 
+impl BatchScrapeStatus {
+    /// `expires_at` parsed into a timestamp, or `None` if it's missing or not an ISO-8601
+    /// timestamp.
+    #[cfg(feature = "chrono")]
+    pub fn expires_at_timestamp(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.expires_at.as_deref().and_then(crate::timestamp::parse)
+    }
+}
+
+/// Splits `urls` into the ones `remaining_credits` can pay for and the ones to defer, keeping
+/// their order.
+///
+/// Uses `ScrapeOptions::estimated_credits_per_url`, so leave some margin if the pages may cost
+/// more than estimated.
+pub fn plan_batches(
+    urls: Vec<String>,
+    options: &ScrapeOptions,
+    remaining_credits: u64,
+) -> (Vec<String>, Vec<String>) {
+    let affordable = remaining_credits / options.estimated_credits_per_url();
+    let affordable = usize::try_from(affordable).unwrap_or(usize::MAX);
+
+    let mut urls = urls;
+    let deferred = urls.split_off(affordable.min(urls.len()));
+    (urls, deferred)
+}
+
+/// Parameters of [`FirecrawlApp::batch_scrape`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchScrapeParams {
+    /// URLs to scrape
+    pub urls: Vec<String>,
+
+    /// Options applied to every URL. (default: the API's defaults)
+    pub options: Option<ScrapeOptions>,
+
+    /// Webhook notified about the progress of the batch scrape
+    pub webhook: Webhook,
+
+    /// Intervals between two checks of the batch scrape's status. (default: every 2 seconds)
+    pub poll_policy: Option<PollPolicy>,
+
+    /// Sent as `x-idempotency-key`, so a retried request doesn't start a second batch scrape
+    pub idempotency_key: Option<String>,
+
+    /// Skip invalid URLs instead of failing the whole batch
+    pub ignore_invalid_urls: Option<bool>,
+
+    /// Label of the batch scrape's `CostEvent`, instead of the one set with
+    /// `FirecrawlApp::with_cost_center`
+    pub cost_center: Option<String>,
+}
+
+impl BatchScrapeParams {
+    /// Scrapes `urls` with the default options, notifying `webhook`.
+    pub fn new(urls: impl IntoIterator<Item = impl Into<String>>, webhook: Webhook) -> Self {
+        Self {
+            urls: urls.into_iter().map(Into::into).collect(),
+            options: None,
+            webhook,
+            poll_policy: None,
+            idempotency_key: None,
+            ignore_invalid_urls: None,
+            cost_center: None,
+        }
+    }
+
+    /// Checks the batch scrape's status every `poll_interval` milliseconds.
+    pub fn with_poll_interval(self, poll_interval: u64) -> Self {
+        self.with_poll_policy(PollPolicy::from_millis(poll_interval))
+    }
+}
+
+option_setters!(BatchScrapeParams,
+    /// Setters for the optional parameters.
+    ///
+    /// ```
+    /// use firecrawl_sdk::batch_scrape::{BatchScrapeParams, Webhook};
+    /// use firecrawl_sdk::scrape::ScrapeOptions;
+    ///
+    /// let params = BatchScrapeParams::new(["https://example.com"], Webhook::dummy())
+    ///     .with_options(ScrapeOptions::default().with_only_main_content(true))
+    ///     .with_ignore_invalid_urls(true);
+    /// assert_eq!(params.ignore_invalid_urls, Some(true));
+    /// ```
+    {
+        with_options => options: ScrapeOptions,
+        with_poll_policy => poll_policy: PollPolicy,
+        with_idempotency_key => idempotency_key: impl Into<String>,
+        with_ignore_invalid_urls => ignore_invalid_urls: bool,
+        with_cost_center => cost_center: impl Into<String>,
+    }
+);
+
 impl FirecrawlApp {
-    /// Scrapes multiple URLs in a single request using the Firecrawl API.
-    pub async fn batch_scrape_urls(
+    /// Starts a batch scrape job, returning its ID without waiting for the end result, e.g. when
+    /// the results are delivered to `webhook`.
+    ///
+    /// Use [`FirecrawlApp::monitor_batch_scrape_status`] to wait for the result later.
+    pub async fn batch_scrape_urls_async(
         &self,
         urls: Vec<String>,
         options: impl Into<Option<ScrapeOptions>>,
-        poll_interval: Option<u64>,
         idempotency_key: Option<String>,
         webhook: Webhook,
         ignore_invalid_urls: Option<bool>,
-    ) -> Result<BatchScrapeStatus, FirecrawlError> {
-        let request_body = BatchScrapeRequestBody {
+    ) -> Result<BatchScrapeInitResponse, FirecrawlError> {
+        let body = BatchScrapeRequestBody {
             urls,
             webhook,
             ignore_invalid_urls,
             options: options.into().unwrap_or_default(),
         };
+        self.start_batch_scrape(&body, idempotency_key).await
+    }
 
-        let headers = self.prepare_headers(idempotency_key.as_ref());
+    async fn start_batch_scrape(
+        &self,
+        body: &BatchScrapeRequestBody,
+        idempotency_key: Option<String>,
+    ) -> Result<BatchScrapeInitResponse, FirecrawlError> {
+        body.options.validate()?;
+        let idempotency_key = self.idempotency_key(idempotency_key);
+        let headers = self.prepare_headers(idempotency_key.as_ref())?;
 
-        let response = self
+        let request = self
+            .inner
             .client
-            .post(format!("{}/{}/batch/scrape", self.api_url, API_VERSION))
+            .post(self.endpoint(routes::batch_scrape()))
             .headers(headers)
-            .json(&request_body)
-            .send()
+            .json(body);
+        let request = apply_timeout(request, crate::scrape_request_timeout(body.options.timeout));
+        let response = self
+            .send_request(request)
             .await
             .map_err(|e| FirecrawlError::HttpError("Batch scraping URLs".to_string(), e))?;
 
+        self.handle_response::<BatchScrapeInitResponse>(response, "batch scrape URLs")
+            .await
+    }
+
+    /// Scrapes multiple URLs in a single request using the Firecrawl API.
+    pub async fn batch_scrape(
+        &self,
+        params: BatchScrapeParams,
+    ) -> Result<BatchScrapeStatus, FirecrawlError> {
+        self.batch_scrape_with_cancellation(params, CancellationToken::new())
+            .await
+    }
+
+    /// Scrapes multiple URLs in a single request, like [`FirecrawlApp::batch_scrape`].
+    #[deprecated(
+        since = "0.6.0",
+        note = "use `FirecrawlApp::batch_scrape` with `BatchScrapeParams` instead: `app.batch_scrape(BatchScrapeParams::new(urls, webhook).with_options(options))`, with `BatchScrapeParams::with_poll_interval`, `BatchScrapeParams::with_idempotency_key` and `BatchScrapeParams::with_ignore_invalid_urls` for the other arguments"
+    )]
+    pub async fn batch_scrape_urls(
+        &self,
+        urls: Vec<String>,
+        options: impl Into<Option<ScrapeOptions>>,
+        poll_interval: Option<u64>,
+        idempotency_key: Option<String>,
+        webhook: Webhook,
+        ignore_invalid_urls: Option<bool>,
+    ) -> Result<BatchScrapeStatus, FirecrawlError> {
+        self.batch_scrape(BatchScrapeParams {
+            urls,
+            options: options.into(),
+            webhook,
+            poll_policy: poll_interval.map(PollPolicy::from_millis),
+            idempotency_key,
+            ignore_invalid_urls,
+            cost_center: None,
+        })
+        .await
+    }
+
+    /// Like [`FirecrawlApp::batch_scrape`], but stops polling once `cancel` is cancelled.
+    ///
+    /// Cancelling fails with `FirecrawlError::OperationCancelled`, carrying the last status with
+    /// the documents collected so far. The batch scrape itself keeps running on the API.
+    pub async fn batch_scrape_with_cancellation(
+        &self,
+        params: BatchScrapeParams,
+        cancel: CancellationToken,
+    ) -> Result<BatchScrapeStatus, FirecrawlError> {
+        let options = params.options.unwrap_or_default();
+        let timeout = crate::scrape_request_timeout(options.timeout);
+        let request_body = BatchScrapeRequestBody {
+            urls: params.urls,
+            webhook: params.webhook,
+            ignore_invalid_urls: params.ignore_invalid_urls,
+            options,
+        };
         let response = self
-            .handle_response::<BatchScrapeResponse>(response, "batch scrape URLs")
+            .start_batch_scrape(&request_body, params.idempotency_key)
             .await?;
 
-        let poll_interval = poll_interval.unwrap_or(2000);
-        self.monitor_batch_scrape_status(&response.id, poll_interval)
-            .await
+        let poll_policy = params.poll_policy.unwrap_or_default();
+        let mut result = self
+            .monitor_batch_scrape_status_with_timeout(&response.id, &poll_policy, timeout, &cancel)
+            .await;
+        let succeeded = result.is_ok();
+        let status = match &mut result {
+            Ok(status) => Some(status),
+            Err(
+                FirecrawlError::OperationCancelled(PartialStatus::BatchScrape(status))
+                | FirecrawlError::PollTimeout {
+                    last_status: PartialStatus::BatchScrape(status),
+                    ..
+                },
+            ) => Some(&mut **status),
+            Err(_) => None,
+        };
+        if let Some(status) = status {
+            self.report_cost(
+                CostOperation::BatchScrape,
+                &request_body.urls,
+                status.credits_used as u64,
+                succeeded,
+                params.cost_center.as_deref(),
+            );
+        }
+        let mut status = result?;
+        status.status_url = response.url;
+        #[cfg(feature = "otel")]
+        self.inner
+            .metrics
+            .record_credits_used("batch/scrape", status.credits_used as u64);
+
+        Ok(status)
+    }
+
+    /// Like [`FirecrawlApp::batch_scrape_with_cancellation`], with positional arguments.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn batch_scrape_urls_with_cancellation(
+        &self,
+        urls: Vec<String>,
+        options: impl Into<Option<ScrapeOptions>>,
+        poll_interval: Option<u64>,
+        idempotency_key: Option<String>,
+        webhook: Webhook,
+        ignore_invalid_urls: Option<bool>,
+        cancel: CancellationToken,
+    ) -> Result<BatchScrapeStatus, FirecrawlError> {
+        let params = BatchScrapeParams {
+            urls,
+            options: options.into(),
+            webhook,
+            poll_policy: poll_interval.map(PollPolicy::from_millis),
+            idempotency_key,
+            ignore_invalid_urls,
+            cost_center: None,
+        };
+        self.batch_scrape_with_cancellation(params, cancel).await
+    }
+
+    fn batch_scrape_status_url(&self, id: &str) -> String {
+        self.endpoint(routes::batch_scrape_status(id)).to_string()
     }
 
     /// Checks the status of a batch scrape job.
@@ -188,25 +419,9 @@ impl FirecrawlApp {
         &self,
         id: &str,
     ) -> Result<BatchScrapeStatus, FirecrawlError> {
-        let headers = self.prepare_headers(None);
-
         println!("Checking batch scrape status for job: {}", id);
 
-        let response = self
-            .client
-            .get(format!(
-                "{}/{}/batch/scrape/{}",
-                self.api_url, API_VERSION, id
-            ))
-            .headers(headers)
-            .send()
-            .await
-            .map_err(|e| {
-                FirecrawlError::HttpError("Checking batch scrape status".to_string(), e)
-            })?;
-
-        self.handle_response::<BatchScrapeStatus>(response, "check batch scrape status")
-            .await
+        self.fetch_batch_scrape_status(id, None, None).await
     }
 
     /// Monitors a batch scrape job until it completes, fails, or is cancelled.
@@ -215,23 +430,67 @@ impl FirecrawlApp {
         id: &str,
         poll_interval: u64,
     ) -> Result<BatchScrapeStatus, FirecrawlError> {
+        self.monitor_batch_scrape_status_with_policy(id, &PollPolicy::from_millis(poll_interval))
+            .await
+    }
+
+    /// Monitors a batch scrape job like [`FirecrawlApp::monitor_batch_scrape_status`], checking
+    /// its status following `poll_policy`.
+    pub async fn monitor_batch_scrape_status_with_policy(
+        &self,
+        id: &str,
+        poll_policy: &PollPolicy,
+    ) -> Result<BatchScrapeStatus, FirecrawlError> {
+        self.monitor_batch_scrape_status_with_timeout(
+            id,
+            poll_policy,
+            None,
+            &CancellationToken::new(),
+        )
+        .await
+    }
+
+    async fn monitor_batch_scrape_status_with_timeout(
+        &self,
+        id: &str,
+        poll_policy: &PollPolicy,
+        timeout: Option<Duration>,
+        cancel: &CancellationToken,
+    ) -> Result<BatchScrapeStatus, FirecrawlError> {
+        let started = Instant::now();
+        let mut schedule = poll_policy.schedule();
+        let partial = |mut status: BatchScrapeStatus, data, skipped| {
+            status.data = data;
+            status.skipped_documents = skipped;
+            PartialStatus::BatchScrape(Box::new(status))
+        };
         let mut all_data = Vec::new();
+        let mut all_skipped = Vec::new();
         let mut current_cursor: Option<String> = None;
+        // Warn about an unknown status once, not on every poll
+        let mut warned = false;
 
         loop {
-            let mut status_data = if let Some(ref cursor) = current_cursor {
-                self.check_batch_scrape_status_with_cursor(id, cursor)
-                    .await?
-            } else {
-                self.check_batch_scrape_status(id).await?
-            };
+            let mut status_data = self
+                .fetch_batch_scrape_status(id, current_cursor.as_deref(), timeout)
+                .await?;
 
             // Collect data from this page
-            all_data.append(&mut status_data.data);
+            append_status_page(
+                (&mut all_data, &mut all_skipped),
+                (
+                    std::mem::take(&mut status_data.data),
+                    std::mem::take(&mut status_data.skipped_documents),
+                ),
+            );
 
             // Check if we need to paginate
-            if let Some(next) = status_data.next {
-                current_cursor = Some(next);
+            if let Some(next) = &status_data.next {
+                if cancel.is_cancelled() {
+                    let status = partial(status_data, all_data, all_skipped);
+                    break Err(FirecrawlError::OperationCancelled(status));
+                }
+                current_cursor = Some(next.clone());
                 continue;
             }
 
@@ -240,15 +499,29 @@ impl FirecrawlApp {
                 BatchScrapeStatusTypes::Completed => {
                     // Put all collected data back into the status
                     status_data.data = all_data;
+                    status_data.skipped_documents = all_skipped;
                     break Ok(status_data);
                 }
-                BatchScrapeStatusTypes::Scraping => {
-                    tokio::time::sleep(tokio::time::Duration::from_millis(poll_interval)).await;
+                BatchScrapeStatusTypes::Scraping | BatchScrapeStatusTypes::Unknown(_) => {
+                    if let BatchScrapeStatusTypes::Unknown(status) = &status_data.status
+                        && !warned
+                    {
+                        self.warn(&format!(
+                            "batch scrape job {id} has an unknown status {status:?}, polling again"
+                        ));
+                        warned = true;
+                    }
+                    let interval = schedule.next_interval(status_data.completed as u64);
+                    if let Some(stop) = self.wait_for_next_poll(interval, cancel, started).await {
+                        let status = partial(status_data, all_data, all_skipped);
+                        break Err(stop.into_error(id, started, status));
+                    }
                     // Keep the cursor as is, to continue from where we left off
                 }
                 BatchScrapeStatusTypes::Failed => {
                     break Err(FirecrawlError::BatchScrapeJobFailed(
                         "Batch scrape job failed.".to_string(),
+                        id.to_string(),
                     ));
                 }
             }
@@ -261,23 +534,50 @@ impl FirecrawlApp {
         id: &str,
         cursor: &str,
     ) -> Result<BatchScrapeStatus, FirecrawlError> {
-        let headers = self.prepare_headers(None);
+        self.fetch_batch_scrape_status(id, Some(cursor), None).await
+    }
 
-        let response = self
-            .client
-            .get(format!(
-                "{}/{}/batch/scrape/{}?cursor={}",
-                self.api_url, API_VERSION, id, cursor
-            ))
-            .headers(headers)
-            .send()
-            .await
-            .map_err(|e| {
-                FirecrawlError::HttpError("Checking batch scrape status".to_string(), e)
-            })?;
+    async fn fetch_batch_scrape_status(
+        &self,
+        id: &str,
+        cursor: Option<&str>,
+        timeout: Option<Duration>,
+    ) -> Result<BatchScrapeStatus, FirecrawlError> {
+        let headers = self.prepare_headers(None)?;
 
-        self.handle_response::<BatchScrapeStatus>(response, "check batch scrape status")
-            .await
+        let route = match cursor {
+            Some(cursor) => routes::batch_cursor(id, cursor),
+            None => routes::batch_scrape_status(id),
+        };
+        let url = self.endpoint(route);
+        let request = apply_timeout(self.inner.client.get(url).headers(headers), timeout);
+        let response = self.send_request(request).await.map_err(|e| {
+            FirecrawlError::HttpError("Checking batch scrape status".to_string(), e)
+        })?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(FirecrawlError::JobNotFound {
+                kind: JobKind::BatchScrape,
+                id: id.to_string(),
+            });
+        }
+
+        let (page, request_id) = self
+            .handle_response_with_request_id::<Value>(response, "check batch scrape status")
+            .await?;
+        #[cfg(feature = "strict")]
+        self.warn_unknown_fields(crate::strict::status_page_warnings::<BatchScrapeStatus>(
+            &page,
+        ));
+        let (mut status, data, skipped_documents): (BatchScrapeStatus, _, _) =
+            deserialize_status_page(page).map_err(FirecrawlError::ResponseParseError)?;
+        status.data = data;
+        status.skipped_documents = skipped_documents;
+        status.id = id.to_string();
+        status.request_id = request_id;
+        self.cap_html(&mut status.data);
+        status.status_url = self.batch_scrape_status_url(id);
+
+        Ok(status)
     }
 }
 
@@ -286,6 +586,7 @@ mod tests {
     use super::*;
     use crate::scrape::{Action, ActionType, JsonOptions, ScrapeFormats};
     use serde_json::json;
+    use std::collections::HashMap;
 
     #[test]
     fn test_batch_scrape_request_serialization() {
@@ -374,6 +675,9 @@ mod tests {
                 remove_base64_images: Some(true),
                 block_ads: Some(true),
                 proxy: Some("basic".to_string()),
+                max_age: None,
+                store_in_cache: None,
+                screenshot_options: None,
             },
         };
 
@@ -381,6 +685,49 @@ mod tests {
         assert_eq!(req_body, expected_req_body);
     }
 
+    #[test]
+    fn test_batch_scrape_response_invalid_urls() {
+        let body = r#"{"success":true,"id":"batch-123","url":"https://api.firecrawl.dev/v1/batch/scrape/batch-123","invalidURLs":["not a url"]}"#;
+
+        let response: BatchScrapeInitResponse =
+            serde_json::from_str(body).expect("Failed to deserialize JSON");
+        assert_eq!(response.invalid_urls, Some(vec!["not a url".to_string()]));
+        assert_eq!(serde_json::to_string(&response).unwrap(), body);
+    }
+
+    fn batch_scrape_status_json() -> serde_json::Value {
+        json!({
+            "success": true,
+            "status": "completed",
+            "total": 1,
+            "completed": 1,
+            "creditsUsed": 1,
+            "expiresAt": "2025-01-02T03:04:05.000Z",
+            "data": []
+        })
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_batch_scrape_status_expires_at_timestamp() {
+        use chrono::TimeZone;
+
+        let status: BatchScrapeStatus = serde_json::from_value(batch_scrape_status_json()).unwrap();
+        assert_eq!(
+            status.expires_at_timestamp(),
+            Some(chrono::Utc.with_ymd_and_hms(2025, 1, 2, 3, 4, 5).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_batch_scrape_status_expires_at_string() {
+        let status: BatchScrapeStatus = serde_json::from_value(batch_scrape_status_json()).unwrap();
+        assert_eq!(
+            status.expires_at.as_deref(),
+            Some("2025-01-02T03:04:05.000Z")
+        );
+    }
+
     #[test]
     fn test_batch_scrape_options_to_scrape_options() {
         let scrape_options = ScrapeOptions {
@@ -399,6 +746,9 @@ mod tests {
             remove_base64_images: Some(true),
             block_ads: Some(true),
             proxy: Some("basic".to_string()),
+            max_age: None,
+            store_in_cache: None,
+            screenshot_options: None,
         };
 
         assert_eq!(scrape_options.formats.as_ref().unwrap().len(), 1);
@@ -421,4 +771,466 @@ mod tests {
         assert!(scrape_options.block_ads.unwrap());
         assert_eq!(scrape_options.proxy.as_ref().unwrap(), "basic");
     }
+
+    async fn mock_batch_scrape(server: &wiremock::MockServer, status: serde_json::Value) {
+        use wiremock::{
+            Mock, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        Mock::given(method("POST"))
+            .and(path("/v1/batch/scrape"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "id": "batch-123",
+                "url": "https://api.firecrawl.dev/v1/batch/scrape/batch-123"
+            })))
+            .mount(server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/v1/batch/scrape/batch-123"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(status))
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_batch_scrape_status_warns_about_unknown_fields_in_strict_mode() {
+        let server = wiremock::MockServer::start().await;
+        let mut page = batch_scrape_status_json();
+        page["priority"] = json!(1);
+        page["data"] = json!([{
+            "summary": "An example",
+            "metadata": { "sourceURL": "https://example.com", "statusCode": 200 }
+        }]);
+        mock_batch_scrape(&server, page).await;
+
+        let warnings = std::sync::Arc::new(crate::observer::WarningRecorder::default());
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>)
+            .unwrap()
+            .with_hook(warnings.clone());
+        for _ in 0..2 {
+            let status = app.check_batch_scrape_status("batch-123").await.unwrap();
+            assert_eq!(status.data.len(), 1);
+        }
+
+        if cfg!(feature = "strict") {
+            assert_eq!(
+                warnings.take(),
+                vec![
+                    "unknown fields in BatchScrapeStatus: priority, the SDK may need updating",
+                    "unknown fields in Document: summary, the SDK may need updating",
+                ]
+            );
+        } else {
+            assert!(warnings.take().is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batch_scrape_urls_exposes_job_id() {
+        let server = wiremock::MockServer::start().await;
+        mock_batch_scrape(&server, batch_scrape_status_json()).await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let status = app
+            .batch_scrape(
+                BatchScrapeParams::new(["https://example.com"], Webhook::dummy())
+                    .with_poll_interval(1),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(status.id, "batch-123");
+        assert_eq!(
+            status.status_url,
+            "https://api.firecrawl.dev/v1/batch/scrape/batch-123"
+        );
+
+        let status = app.check_batch_scrape_status("batch-123").await.unwrap();
+        assert_eq!(status.id, "batch-123");
+        assert_eq!(
+            status.status_url,
+            format!("{}/v1/batch/scrape/batch-123", server.uri())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_batch_scrape_urls_async() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{body_partial_json, method, path},
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/batch/scrape"))
+            .and(body_partial_json(json!({
+                "urls": ["https://example.com", "not a url"],
+                "webhook": { "url": "https://example.com/webhook" },
+                "ignoreInvalidURLs": true,
+                "onlyMainContent": true
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "id": "batch-123",
+                "url": "https://api.firecrawl.dev/v1/batch/scrape/batch-123",
+                "invalidURLs": ["not a url"]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        // Returns without polling
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(batch_scrape_status_json()))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let response = app
+            .batch_scrape_urls_async(
+                vec!["https://example.com".to_string(), "not a url".to_string()],
+                ScrapeOptions::default().with_only_main_content(true),
+                None,
+                Webhook {
+                    url: "https://example.com/webhook".to_string(),
+                    ..Default::default()
+                },
+                Some(true),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.id, "batch-123");
+        assert_eq!(
+            response.url,
+            "https://api.firecrawl.dev/v1/batch/scrape/batch-123"
+        );
+        assert_eq!(response.invalid_urls, Some(vec!["not a url".to_string()]));
+    }
+
+    #[tokio::test]
+    async fn test_batch_scrape_urls_failure_carries_job_id() {
+        let server = wiremock::MockServer::start().await;
+        let mut failed = batch_scrape_status_json();
+        failed["status"] = json!("failed");
+        mock_batch_scrape(&server, failed).await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let result = app
+            .batch_scrape(
+                BatchScrapeParams::new(["https://example.com"], Webhook::dummy())
+                    .with_poll_interval(1),
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(FirecrawlError::BatchScrapeJobFailed(_, id)) if id == "batch-123"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_expired_batch_scrape_id() {
+        use wiremock::{
+            Mock, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let server = wiremock::MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/batch/scrape/batch-expired"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+                "success": false,
+                "error": "Job not found"
+            })))
+            // Monitoring gives up instead of polling again
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let error = app
+            .check_batch_scrape_status("batch-expired")
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(
+                &error,
+                FirecrawlError::JobNotFound { kind: JobKind::BatchScrape, id } if id == "batch-expired"
+            ),
+            "{error:?}"
+        );
+        assert_eq!(
+            error.to_string(),
+            "The batch scrape job batch-expired was not found or has expired"
+        );
+
+        let error = app
+            .monitor_batch_scrape_status("batch-expired", 1)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, FirecrawlError::JobNotFound { .. }));
+        assert!(!error.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_malformed_batch_scrape_id() {
+        use wiremock::{
+            Mock, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let server = wiremock::MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/batch/scrape/not-a-uuid"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+                "success": false,
+                "error": "Invalid job ID format"
+            })))
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let error = app
+            .check_batch_scrape_status("not-a-uuid")
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&error, FirecrawlError::APIError(_, e) if e.status_code == Some(400) && e.error == "Invalid job ID format"),
+            "{error:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_batch_scrape_status_uses_scrape_timeout() {
+        use wiremock::{
+            Mock, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let server = wiremock::MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/batch/scrape"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "id": "batch-123",
+                "url": "https://api.firecrawl.dev/v1/batch/scrape/batch-123"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/batch/scrape/batch-123"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(batch_scrape_status_json())
+                    .set_delay(Duration::from_millis(500)),
+            )
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::builder()
+            .api_url(server.uri())
+            .timeout(Duration::from_millis(200))
+            .build()
+            .unwrap();
+
+        let result = app.check_batch_scrape_status("batch-123").await;
+        assert!(matches!(result, Err(FirecrawlError::HttpError(_, e)) if e.is_timeout()));
+
+        let options = ScrapeOptions {
+            timeout: Some(1000),
+            ..Default::default()
+        };
+        let status = app
+            .batch_scrape(
+                BatchScrapeParams::new(["https://example.com"], Webhook::dummy())
+                    .with_options(options)
+                    .with_poll_interval(1),
+            )
+            .await
+            .unwrap();
+        assert_eq!(status.id, "batch-123");
+    }
+
+    #[test]
+    fn test_plan_batches() {
+        let urls: Vec<String> = (1..=10)
+            .map(|i| format!("https://example.com/{i}"))
+            .collect();
+
+        let markdown = ScrapeOptions::default();
+        let (affordable, deferred) = plan_batches(urls.clone(), &markdown, 7);
+        assert_eq!(affordable, urls[..7]);
+        assert_eq!(deferred, urls[7..]);
+
+        // 5 credits per URL
+        let json = ScrapeOptions {
+            formats: Some(vec![ScrapeFormats::Markdown, ScrapeFormats::JSON]),
+            ..Default::default()
+        };
+        let (affordable, deferred) = plan_batches(urls.clone(), &json, 14);
+        assert_eq!(affordable, urls[..2]);
+        assert_eq!(deferred, urls[2..]);
+
+        let (affordable, deferred) = plan_batches(urls.clone(), &json, 1000);
+        assert_eq!(affordable, urls);
+        assert!(deferred.is_empty());
+
+        let (affordable, deferred) = plan_batches(urls.clone(), &json, 0);
+        assert!(affordable.is_empty());
+        assert_eq!(deferred, urls);
+    }
+
+    #[tokio::test]
+    async fn test_batch_scrape_urls_with_cancellation_returns_partial_status() {
+        let server = wiremock::MockServer::start().await;
+        mock_batch_scrape(
+            &server,
+            json!({
+                "success": true,
+                "status": "scraping",
+                "total": 2,
+                "completed": 1,
+                "creditsUsed": 1,
+                "data": [{
+                    "markdown": "# One",
+                    "metadata": { "sourceURL": "https://example.com/1", "statusCode": 200 }
+                }]
+            }),
+        )
+        .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let cancel = CancellationToken::new();
+        let canceller = cancel.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            canceller.cancel();
+        });
+
+        let started = std::time::Instant::now();
+        let error = app
+            .batch_scrape_urls_with_cancellation(
+                vec![
+                    "https://example.com/1".to_string(),
+                    "https://example.com/2".to_string(),
+                ],
+                None,
+                Some(60_000),
+                None,
+                Webhook::dummy(),
+                None,
+                cancel,
+            )
+            .await
+            .unwrap_err();
+
+        assert!(started.elapsed() < Duration::from_secs(5));
+        let FirecrawlError::OperationCancelled(PartialStatus::BatchScrape(status)) = error else {
+            panic!("{error:?}");
+        };
+        assert_eq!(status.status, BatchScrapeStatusTypes::Scraping);
+        assert_eq!(status.completed, 1);
+        assert_eq!(status.data.len(), 1);
+        assert_eq!(status.data[0].metadata.source_url, "https://example.com/1");
+    }
+
+    #[tokio::test]
+    async fn test_batch_scrape_keeps_polling_unknown_status() {
+        use wiremock::{
+            Mock, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let server = wiremock::MockServer::start().await;
+        // A status introduced by a newer version of the API, mounted first to answer first
+        let mut paused = batch_scrape_status_json();
+        paused["status"] = json!("paused");
+        Mock::given(method("GET"))
+            .and(path("/v1/batch/scrape/batch-123"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(paused.clone()))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        mock_batch_scrape(&server, batch_scrape_status_json()).await;
+
+        let status: BatchScrapeStatus = serde_json::from_value(paused).unwrap();
+        assert_eq!(
+            status.status,
+            BatchScrapeStatusTypes::Unknown("paused".to_string())
+        );
+
+        let warnings = std::sync::Arc::new(crate::observer::WarningRecorder::default());
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>)
+            .unwrap()
+            .with_hook(warnings.clone());
+        let status = app
+            .batch_scrape(
+                BatchScrapeParams::new(["https://example.com"], Webhook::dummy())
+                    .with_poll_interval(10),
+            )
+            .await
+            .unwrap();
+        assert_eq!(status.status, BatchScrapeStatusTypes::Completed);
+        // Once for the job, not once per poll
+        assert_eq!(
+            warnings.take(),
+            vec![r#"batch scrape job batch-123 has an unknown status "paused", polling again"#]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_batch_scrape_urls_gives_up_after_max_poll_wait() {
+        let server = wiremock::MockServer::start().await;
+        mock_batch_scrape(
+            &server,
+            json!({
+                "success": true,
+                "status": "scraping",
+                "total": 2,
+                "completed": 1,
+                "creditsUsed": 1,
+                "data": [{
+                    "metadata": { "sourceURL": "https://example.com/1", "statusCode": 200 }
+                }]
+            }),
+        )
+        .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>)
+            .unwrap()
+            .with_max_poll_wait(Duration::from_millis(300));
+        let error = app
+            .batch_scrape(
+                BatchScrapeParams::new(
+                    ["https://example.com/1", "https://example.com/2"],
+                    Webhook::dummy(),
+                )
+                .with_poll_interval(100),
+            )
+            .await
+            .unwrap_err();
+
+        assert!(
+            error
+                .to_string()
+                .starts_with("Gave up waiting for the batch scrape job batch-123 after"),
+            "{error}"
+        );
+        let FirecrawlError::PollTimeout {
+            job_id,
+            elapsed,
+            last_status: PartialStatus::BatchScrape(status),
+        } = error
+        else {
+            panic!("{error:?}");
+        };
+        assert_eq!(job_id, "batch-123");
+        assert!(elapsed >= Duration::from_millis(300), "{elapsed:?}");
+        assert_eq!(status.status, BatchScrapeStatusTypes::Scraping);
+        assert!(!status.data.is_empty());
+    }
 }
@@ -0,0 +1,226 @@
+//! Synchronous client, for programs without an async runtime.
+//!
+//! ```no_run
+//! use firecrawl_sdk::blocking::FirecrawlApp;
+//!
+//! let app = FirecrawlApp::new("fc-YOUR-API-KEY").expect("Failed to initialize FirecrawlApp");
+//! let document = app.scrape_url("https://example.com", None).expect("Failed to scrape");
+//! println!("{:?}", document.markdown);
+//! ```
+
+use std::sync::Arc;
+
+use tokio::runtime::Runtime;
+
+use crate::FirecrawlError;
+#[cfg(feature = "batch")]
+use crate::batch_scrape::{BatchScrapeParams, BatchScrapeStatus};
+#[cfg(feature = "crawl")]
+use crate::crawl::{CrawlParams, CrawlStatus};
+#[cfg(feature = "scrape")]
+use crate::document::Document;
+#[cfg(feature = "map")]
+use crate::map::MapOptions;
+#[cfg(feature = "scrape")]
+use crate::scrape::ScrapeOptions;
+#[cfg(feature = "search")]
+use crate::search::{SearchOptions, SearchResult};
+
+/// Client of the Firecrawl API blocking the calling thread until each call returns.
+///
+/// Wraps an async [`crate::FirecrawlApp`] and runs it on a current-thread runtime owned by the
+/// client. Calls panic when made from within an async runtime, where the async client should be
+/// used instead. Clones share the runtime.
+#[derive(Clone, Debug)]
+pub struct FirecrawlApp {
+    app: crate::FirecrawlApp,
+    runtime: Arc<Runtime>,
+}
+
+impl FirecrawlApp {
+    /// Connects to the cloud service with `api_key`, see [`crate::FirecrawlApp::new`].
+    pub fn new(api_key: impl AsRef<str>) -> Result<Self, FirecrawlError> {
+        Self::from_async(crate::FirecrawlApp::new(api_key)?)
+    }
+
+    /// Connects to a self-hosted instance, see [`crate::FirecrawlApp::new_selfhosted`].
+    pub fn new_selfhosted(
+        api_url: impl AsRef<str>,
+        api_key: Option<impl AsRef<str>>,
+    ) -> Result<Self, FirecrawlError> {
+        Self::from_async(crate::FirecrawlApp::new_selfhosted(api_url, api_key)?)
+    }
+
+    /// Runs `app`, e.g. one built with [`crate::FirecrawlApp::builder`], synchronously.
+    pub fn from_async(app: crate::FirecrawlApp) -> Result<Self, FirecrawlError> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| {
+                FirecrawlError::configuration(format!("Failed to start the blocking runtime: {e}"))
+            })?;
+
+        Ok(Self {
+            app,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// The async client making the requests.
+    pub fn as_async(&self) -> &crate::FirecrawlApp {
+        &self.app
+    }
+
+    /// Scrapes a URL, see [`crate::FirecrawlApp::scrape_url`].
+    #[cfg(feature = "scrape")]
+    pub fn scrape_url(
+        &self,
+        url: impl AsRef<str>,
+        options: impl Into<Option<ScrapeOptions>>,
+    ) -> Result<Document, FirecrawlError> {
+        self.runtime.block_on(self.app.scrape_url(url, options))
+    }
+
+    /// Maps a URL, see [`crate::FirecrawlApp::map_url`].
+    #[cfg(feature = "map")]
+    pub fn map_url(
+        &self,
+        url: impl AsRef<str>,
+        options: impl Into<Option<MapOptions>>,
+    ) -> Result<Vec<String>, FirecrawlError> {
+        self.runtime.block_on(self.app.map_url(url, options))
+    }
+
+    /// Searches the web, see [`crate::FirecrawlApp::search`].
+    #[cfg(feature = "search")]
+    pub fn search(
+        &self,
+        query: impl AsRef<str>,
+        options: impl Into<Option<SearchOptions>>,
+    ) -> Result<Vec<SearchResult>, FirecrawlError> {
+        self.runtime.block_on(self.app.search(query, options))
+    }
+
+    /// Crawls a URL and waits for the end result, see [`crate::FirecrawlApp::crawl`].
+    #[cfg(feature = "crawl")]
+    pub fn crawl(&self, params: CrawlParams) -> Result<CrawlStatus, FirecrawlError> {
+        self.runtime.block_on(self.app.crawl(params))
+    }
+
+    /// Scrapes multiple URLs and waits for the end result, see
+    /// [`crate::FirecrawlApp::batch_scrape`].
+    #[cfg(feature = "batch")]
+    pub fn batch_scrape(
+        &self,
+        params: BatchScrapeParams,
+    ) -> Result<BatchScrapeStatus, FirecrawlError> {
+        self.runtime.block_on(self.app.batch_scrape(params))
+    }
+}
+
+#[cfg(all(
+    test,
+    feature = "batch",
+    feature = "crawl",
+    feature = "map",
+    feature = "search"
+))]
+mod tests {
+    use serde_json::json;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path},
+    };
+
+    use super::*;
+    use crate::webhook::Webhook;
+
+    #[test]
+    fn test_blocking_calls_without_runtime() {
+        // The mock server needs a runtime of its own, kept running for the whole test
+        let server_runtime = Runtime::new().unwrap();
+        let server = server_runtime.block_on(async {
+            let server = MockServer::start().await;
+            let document = json!({
+                "markdown": "# Example",
+                "metadata": { "sourceURL": "https://example.com", "statusCode": 200 }
+            });
+            let status = json!({
+                "success": true,
+                "status": "completed",
+                "total": 1,
+                "completed": 1,
+                "creditsUsed": 1,
+                "expiresAt": "2025-01-02T03:04:05.000Z",
+                "data": [document]
+            });
+            for (verb, route, body) in [
+                (
+                    "POST",
+                    "/v1/scrape",
+                    json!({ "success": true, "data": document }),
+                ),
+                (
+                    "POST",
+                    "/v1/map",
+                    json!({ "success": true, "links": ["https://example.com/about"] }),
+                ),
+                (
+                    "POST",
+                    "/v1/search",
+                    json!({
+                        "success": true,
+                        "data": [{
+                            "url": "https://example.com",
+                            "title": "Example",
+                            "description": "An example page"
+                        }]
+                    }),
+                ),
+                (
+                    "POST",
+                    "/v1/crawl",
+                    json!({ "success": true, "id": "crawl-1", "url": "" }),
+                ),
+                ("GET", "/v1/crawl/crawl-1", status.clone()),
+                (
+                    "POST",
+                    "/v1/batch/scrape",
+                    json!({ "success": true, "id": "batch-1", "url": "" }),
+                ),
+                ("GET", "/v1/batch/scrape/batch-1", status),
+            ] {
+                Mock::given(method(verb))
+                    .and(path(route))
+                    .respond_with(ResponseTemplate::new(200).set_body_json(body))
+                    .expect(1)
+                    .mount(&server)
+                    .await;
+            }
+            server
+        });
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let document = app.scrape_url("https://example.com", None).unwrap();
+        assert_eq!(document.markdown.as_deref(), Some("# Example"));
+        assert_eq!(
+            app.map_url("https://example.com", None).unwrap(),
+            vec!["https://example.com/about"]
+        );
+        assert_eq!(app.search("example", None).unwrap()[0].title, "Example");
+        let crawl = app
+            .crawl(CrawlParams::new("https://example.com", Webhook::dummy()))
+            .unwrap();
+        assert_eq!(crawl.data.len(), 1);
+        let batch = app
+            .clone()
+            .batch_scrape(BatchScrapeParams::new(
+                ["https://example.com"],
+                Webhook::dummy(),
+            ))
+            .unwrap();
+        assert_eq!(batch.data.len(), 1);
+
+        server_runtime.block_on(server.verify());
+    }
+}
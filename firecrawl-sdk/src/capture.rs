@@ -0,0 +1,281 @@
+use std::{
+    collections::VecDeque,
+    sync::{Mutex, PoisonError},
+    time::SystemTime,
+};
+
+use reqwest::{Method, Url};
+use serde_json::Value;
+
+use crate::redact_api_key;
+
+/// Number of exchanges kept by [`FirecrawlApp::enable_capture`](crate::FirecrawlApp::enable_capture).
+pub(crate) const CAPTURE_CAPACITY: usize = 20;
+
+/// Maximum length in bytes of a captured response body.
+pub(crate) const MAX_CAPTURED_RESPONSE_BYTES: usize = 8 * 1024;
+
+/// Names of the JSON fields whose values are always redacted, compared case-insensitively.
+const SECRET_FIELDS: [&str; 4] = ["authorization", "apikey", "api_key", "x-api-key"];
+
+/// A request and its response, captured with
+/// [`FirecrawlApp::enable_capture`](crate::FirecrawlApp::enable_capture), e.g. to send to
+/// Firecrawl support the exact JSON of a scrape with unexpected output.
+///
+/// The API key of the client is redacted from every field, as are the values of JSON fields
+/// named like `authorization` or `apiKey`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExchangeRecord {
+    /// HTTP method of the request
+    pub method: Method,
+
+    /// Full URL of the request
+    pub endpoint: String,
+
+    /// Time the request was sent
+    pub timestamp: SystemTime,
+
+    /// JSON body of the request, if any
+    pub request: Option<Value>,
+
+    /// HTTP status code of the response. `None` if the request failed without a response.
+    pub status: Option<u16>,
+
+    /// Body of the response, truncated to 8 KiB at a character boundary and suffixed with `…`
+    /// if longer. `None` until the SDK has read it, and for requests that failed without a
+    /// response.
+    pub response: Option<String>,
+}
+
+/// The last exchanges of a client, oldest first.
+#[derive(Debug)]
+pub(crate) struct Capture {
+    api_key: Option<String>,
+    exchanges: Mutex<VecDeque<ExchangeRecord>>,
+}
+
+impl Capture {
+    pub(crate) fn new(api_key: Option<&str>) -> Self {
+        Capture {
+            api_key: api_key.map(str::to_string),
+            exchanges: Mutex::new(VecDeque::with_capacity(CAPTURE_CAPACITY)),
+        }
+    }
+
+    /// Records a request sent to `url` at `timestamp` and the status of its response, dropping
+    /// the oldest exchange once [`CAPTURE_CAPACITY`] are kept.
+    pub(crate) fn record_request(
+        &self,
+        method: &Method,
+        url: &Url,
+        body: Option<&[u8]>,
+        timestamp: SystemTime,
+        status: Option<u16>,
+    ) {
+        let request = body.map(|body| match serde_json::from_slice::<Value>(body) {
+            Ok(json) => self.redact_json(json),
+            Err(_) => Value::String(self.redact(&String::from_utf8_lossy(body))),
+        });
+        let record = ExchangeRecord {
+            method: method.clone(),
+            endpoint: self.redact(url.as_str()),
+            timestamp,
+            request,
+            status,
+            response: None,
+        };
+
+        let mut exchanges = self.lock();
+        if exchanges.len() == CAPTURE_CAPACITY {
+            exchanges.pop_front();
+        }
+        exchanges.push_back(record);
+    }
+
+    /// Attaches `body` to the oldest exchange with `url` whose response wasn't read yet.
+    pub(crate) fn record_response(&self, url: &Url, body: &[u8]) {
+        let body = match serde_json::from_slice::<Value>(body) {
+            Ok(json) => self.redact_json(json).to_string(),
+            Err(_) => self.redact(&String::from_utf8_lossy(body)),
+        };
+
+        let endpoint = self.redact(url.as_str());
+        let mut exchanges = self.lock();
+        if let Some(record) = exchanges.iter_mut().find(|record| {
+            record.endpoint == endpoint && record.status.is_some() && record.response.is_none()
+        }) {
+            record.response = Some(truncate(body, MAX_CAPTURED_RESPONSE_BYTES));
+        }
+    }
+
+    pub(crate) fn exchanges(&self) -> Vec<ExchangeRecord> {
+        self.lock().iter().cloned().collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<ExchangeRecord>> {
+        self.exchanges
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn redact(&self, text: &str) -> String {
+        match self.api_key.as_deref() {
+            Some(api_key) if !api_key.is_empty() => text.replace(api_key, &redact_api_key(api_key)),
+            _ => text.to_string(),
+        }
+    }
+
+    fn redact_json(&self, json: Value) -> Value {
+        match json {
+            Value::String(text) => Value::String(self.redact(&text)),
+            Value::Array(items) => Value::Array(
+                items
+                    .into_iter()
+                    .map(|item| self.redact_json(item))
+                    .collect(),
+            ),
+            Value::Object(fields) => Value::Object(
+                fields
+                    .into_iter()
+                    .map(|(name, value)| {
+                        let value = if SECRET_FIELDS
+                            .iter()
+                            .any(|secret| name.eq_ignore_ascii_case(secret))
+                        {
+                            Value::String("****".to_string())
+                        } else {
+                            self.redact_json(value)
+                        };
+                        (name, value)
+                    })
+                    .collect(),
+            ),
+            json => json,
+        }
+    }
+}
+
+/// Truncates `text` to at most `max_bytes`, at a character boundary, marking it with `…`.
+fn truncate(mut text: String, max_bytes: usize) -> String {
+    if text.len() <= max_bytes {
+        return text;
+    }
+    let end = (0..=max_bytes)
+        .rev()
+        .find(|&i| text.is_char_boundary(i))
+        .unwrap_or(0);
+    text.truncate(end);
+    text.push('…');
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[cfg(feature = "scrape")]
+    #[tokio::test]
+    async fn test_capture_on_off() {
+        use crate::{FirecrawlApp, scrape::ScrapeOptions};
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/scrape"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "data": {
+                    "markdown": "# Example",
+                    "metadata": { "sourceURL": "https://example.com", "statusCode": 200 }
+                }
+            })))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/scrape"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+                "success": false,
+                "error": "Invalid key fc-secret-key-1234"
+            })))
+            .mount(&server)
+            .await;
+        let options = ScrapeOptions::default().with_headers([(
+            "Authorization".to_string(),
+            "Bearer fc-secret-key-1234".to_string(),
+        )]);
+
+        // Off by default
+        let app = FirecrawlApp::new_selfhosted(server.uri(), Some("fc-secret-key-1234")).unwrap();
+        app.scrape_url("https://example.com", options.clone())
+            .await
+            .unwrap();
+        assert!(app.last_exchanges().is_empty());
+
+        let app = app.enable_capture(true);
+        let clone = app.clone();
+        let error = app
+            .scrape_url("https://example.com/fc-secret-key-1234", options)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, crate::FirecrawlError::APIError(..)));
+
+        let exchanges = clone.last_exchanges();
+        assert_eq!(exchanges.len(), 1);
+        let exchange = &exchanges[0];
+        assert_eq!(exchange.method, Method::POST);
+        assert_eq!(exchange.endpoint, format!("{}/v1/scrape", server.uri()));
+        assert_eq!(
+            exchange.request,
+            Some(json!({
+                "url": "https://example.com/fc-****1234",
+                "headers": { "Authorization": "****" }
+            }))
+        );
+        assert_eq!(exchange.status, Some(400));
+        assert_eq!(
+            exchange.response.as_deref(),
+            Some(r#"{"error":"Invalid key fc-****1234","success":false}"#)
+        );
+        assert!(
+            !format!("{exchanges:?}").contains("fc-secret-key-1234"),
+            "{exchanges:?}"
+        );
+
+        assert!(app.enable_capture(false).last_exchanges().is_empty());
+    }
+
+    #[test]
+    fn test_capture_keeps_last_exchanges() {
+        let capture = Capture::new(None);
+        let url = Url::parse("https://api.firecrawl.dev/v1/crawl/crawl-1").unwrap();
+        for i in 0..CAPTURE_CAPACITY + 2 {
+            let body = json!({ "poll": i }).to_string();
+            capture.record_request(
+                &Method::GET,
+                &url,
+                Some(body.as_bytes()),
+                SystemTime::UNIX_EPOCH,
+                Some(200),
+            );
+        }
+        // Attached to the oldest exchange still waiting for its response
+        capture.record_response(&url, "é".repeat(MAX_CAPTURED_RESPONSE_BYTES).as_bytes());
+
+        let exchanges = capture.exchanges();
+        assert_eq!(exchanges.len(), CAPTURE_CAPACITY);
+        assert_eq!(exchanges[0].request, Some(json!({ "poll": 2 })));
+        let response = exchanges[0].response.as_deref().unwrap();
+        assert!(response.len() <= MAX_CAPTURED_RESPONSE_BYTES + '…'.len_utf8());
+        assert!(response.ends_with("é…"));
+        assert!(
+            exchanges[1..]
+                .iter()
+                .all(|record| record.response.is_none())
+        );
+    }
+}
@@ -0,0 +1,408 @@
+//! Pins the exact JSON keys produced by every options and request struct.
+//!
+//! `rename_all = "camelCase"` does not produce every spelling the API expects, so a handful of
+//! fields carry explicit renames. Each struct below is serialized with all fields set and its keys
+//! are compared against a checked-in list, so an accidental rename fails loudly.
+//!
+//! Intentional exceptions to plain camelCase:
+//! - `CrawlOptions.deduplicate_similar_urls` -> `deduplicateSimilarURLs`
+//! - `BatchScrapeRequestBody.ignore_invalid_urls` -> `ignoreInvalidURLs`
+//! - `DocumentMetadata.source_url` -> `sourceURL`
+//! - `Action.action_type` -> `type`
+//! - `ScrapeFormats::ScreenshotFullPage` -> `screenshot@fullPage`
+
+use std::collections::{BTreeSet, HashMap};
+
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use crate::{
+    batch_scrape::{BatchScrapeRequestBody, Webhook},
+    crawl::{CrawlOptions, CrawlRequestBody},
+    document::DocumentMetadata,
+    map::{MapOptions, MapRequestBody},
+    scrape::{
+        Action, ActionType, ExtractOptions, JsonOptions, LocationOptions, ScrapeFormats,
+        ScrapeOptions, ScrapeRequestBody, ScreenshotOptions, Viewport,
+    },
+    search::{self, SearchOptions, SearchRequestBody},
+};
+
+fn keys(value: &impl Serialize) -> BTreeSet<String> {
+    match serde_json::to_value(value).unwrap() {
+        Value::Object(map) => map.keys().cloned().collect(),
+        other => panic!("expected an object, got {other}"),
+    }
+}
+
+fn expected(keys: &[&str]) -> BTreeSet<String> {
+    keys.iter().map(|k| k.to_string()).collect()
+}
+
+fn full_scrape_options() -> ScrapeOptions {
+    ScrapeOptions {
+        formats: Some(vec![ScrapeFormats::Markdown]),
+        screenshot_options: Some(ScreenshotOptions::default()),
+        only_main_content: Some(true),
+        include_tags: Some(vec!["div".to_string()]),
+        exclude_tags: Some(vec!["img".to_string()]),
+        headers: Some(HashMap::new()),
+        wait_for: Some(0),
+        timeout: Some(30000),
+        json_options: Some(JsonOptions::default()),
+        location: Some(LocationOptions::default()),
+        actions: Some(vec![]),
+        mobile: Some(false),
+        skip_tls_verification: Some(false),
+        remove_base64_images: Some(true),
+        block_ads: Some(true),
+        proxy: Some("basic".to_string()),
+        max_age: Some(3_600_000),
+        store_in_cache: Some(true),
+    }
+}
+
+const SCRAPE_OPTIONS_KEYS: &[&str] = &[
+    "formats",
+    "onlyMainContent",
+    "includeTags",
+    "excludeTags",
+    "headers",
+    "waitFor",
+    "timeout",
+    "jsonOptions",
+    "location",
+    "actions",
+    "mobile",
+    "skipTlsVerification",
+    "removeBase64Images",
+    "blockAds",
+    "proxy",
+    "maxAge",
+    "storeInCache",
+];
+
+#[test]
+fn test_scrape_options_keys() {
+    assert_eq!(keys(&full_scrape_options()), expected(SCRAPE_OPTIONS_KEYS));
+}
+
+#[test]
+fn test_scrape_request_body_keys() {
+    let body = ScrapeRequestBody {
+        url: "https://example.com".to_string(),
+        options: full_scrape_options(),
+    };
+
+    let mut expected_keys = expected(SCRAPE_OPTIONS_KEYS);
+    expected_keys.insert("url".to_string());
+    assert_eq!(keys(&body), expected_keys);
+}
+
+#[test]
+fn test_scrape_formats_spelling() {
+    let formats = serde_json::to_value([
+        ScrapeFormats::Markdown,
+        ScrapeFormats::HTML,
+        ScrapeFormats::RawHTML,
+        ScrapeFormats::Links,
+        ScrapeFormats::Screenshot,
+        ScrapeFormats::ScreenshotFullPage,
+        ScrapeFormats::JSON,
+    ])
+    .unwrap();
+
+    assert_eq!(
+        formats,
+        json!([
+            "markdown",
+            "html",
+            "rawHtml",
+            "links",
+            "screenshot",
+            "screenshot@fullPage",
+            "json"
+        ])
+    );
+}
+
+#[test]
+fn test_screenshot_format_keys() {
+    let options = ScrapeOptions {
+        formats: Some(vec![ScrapeFormats::Screenshot]),
+        screenshot_options: Some(ScreenshotOptions {
+            full_page: Some(true),
+            quality: Some(80),
+            viewport: Some(Viewport {
+                width: 1280,
+                height: 800,
+            }),
+        }),
+        ..Default::default()
+    };
+
+    let formats = &serde_json::to_value(&options).unwrap()["formats"];
+    assert_eq!(
+        keys(&formats[0]),
+        expected(&["type", "fullPage", "quality", "viewport"])
+    );
+    assert_eq!(
+        keys(&formats[0]["viewport"]),
+        expected(&["width", "height"])
+    );
+}
+
+#[test]
+fn test_json_and_extract_options_keys() {
+    let json_options = JsonOptions {
+        schema: Some(json!({"type": "object"})),
+        system_prompt: Some("system".to_string()),
+        prompt: Some("prompt".to_string()),
+    };
+    let extract_options = ExtractOptions {
+        schema: Some(json!({"type": "object"})),
+        system_prompt: Some("system".to_string()),
+        prompt: Some("prompt".to_string()),
+    };
+
+    let expected_keys = expected(&["schema", "systemPrompt", "prompt"]);
+    assert_eq!(keys(&json_options), expected_keys);
+    assert_eq!(keys(&extract_options), expected_keys);
+}
+
+#[test]
+fn test_action_keys() {
+    let action = Action {
+        action_type: ActionType::ExecuteJavascript,
+        selector: Some("#id".to_string()),
+        text: Some("text".to_string()),
+        milliseconds: Some(1000),
+        key: Some("Enter".to_string()),
+        direction: Some("down".to_string()),
+        script: Some("return 1".to_string()),
+        full_page: Some(true),
+    };
+
+    assert_eq!(
+        keys(&action),
+        expected(&[
+            "type",
+            "selector",
+            "text",
+            "milliseconds",
+            "key",
+            "direction",
+            "script",
+            "fullPage",
+        ])
+    );
+    assert_eq!(
+        serde_json::to_value(&action).unwrap()["type"],
+        "executeJavascript"
+    );
+}
+
+#[test]
+fn test_location_options_keys() {
+    let scrape_location = LocationOptions {
+        country: "US".to_string(),
+        languages: vec!["en-US".to_string()],
+    };
+    let search_location = search::LocationOptions {
+        country: Some("US".to_string()),
+        languages: Some(vec!["en-US".to_string()]),
+    };
+
+    let expected_keys = expected(&["country", "languages"]);
+    assert_eq!(keys(&scrape_location), expected_keys);
+    assert_eq!(keys(&search_location), expected_keys);
+}
+
+fn full_crawl_options() -> CrawlOptions {
+    CrawlOptions {
+        scrape_options: Some(ScrapeOptions::default()),
+        include_paths: Some(vec!["/blog/*".to_string()]),
+        exclude_paths: Some(vec!["/admin/*".to_string()]),
+        max_depth: Some(2),
+        ignore_sitemap: Some(true),
+        limit: Some(10),
+        allow_backward_links: Some(false),
+        allow_external_links: Some(false),
+        deduplicate_similar_urls: Some(true),
+        ignore_query_parameters: Some(true),
+        max_concurrency: Some(2),
+    }
+}
+
+const CRAWL_OPTIONS_KEYS: &[&str] = &[
+    "scrapeOptions",
+    "includePaths",
+    "excludePaths",
+    "maxDepth",
+    "ignoreSitemap",
+    "limit",
+    "allowBackwardLinks",
+    "allowExternalLinks",
+    "deduplicateSimilarURLs",
+    "ignoreQueryParameters",
+    "maxConcurrency",
+];
+
+#[test]
+fn test_crawl_options_keys() {
+    assert_eq!(keys(&full_crawl_options()), expected(CRAWL_OPTIONS_KEYS));
+}
+
+#[test]
+fn test_crawl_request_body_keys() {
+    let body = CrawlRequestBody {
+        url: "https://example.com".to_string(),
+        options: full_crawl_options(),
+        webhook: Webhook::dummy(),
+    };
+
+    let mut expected_keys = expected(CRAWL_OPTIONS_KEYS);
+    expected_keys.extend(["url".to_string(), "webhook".to_string()]);
+    assert_eq!(keys(&body), expected_keys);
+}
+
+#[test]
+fn test_webhook_keys() {
+    let webhook = Webhook {
+        url: "https://webhook.example.com".to_string(),
+        headers: Some(HashMap::new()),
+        metadata: Some(HashMap::new()),
+        events: Some(vec!["completed".to_string()]),
+    };
+
+    assert_eq!(
+        keys(&webhook),
+        expected(&["url", "headers", "metadata", "events"])
+    );
+}
+
+#[test]
+fn test_batch_scrape_request_body_keys() {
+    let body = BatchScrapeRequestBody {
+        urls: vec!["https://example.com".to_string()],
+        webhook: Webhook::dummy(),
+        ignore_invalid_urls: Some(true),
+        options: full_scrape_options(),
+    };
+
+    let mut expected_keys = expected(SCRAPE_OPTIONS_KEYS);
+    expected_keys.extend([
+        "urls".to_string(),
+        "webhook".to_string(),
+        "ignoreInvalidURLs".to_string(),
+    ]);
+    assert_eq!(keys(&body), expected_keys);
+}
+
+fn full_map_options() -> MapOptions {
+    MapOptions {
+        search: Some("docs".to_string()),
+        ignore_sitemap: Some(true),
+        sitemap_only: Some(false),
+        include_subdomains: Some(true),
+        limit: Some(100),
+        timeout: Some(5000),
+        location: Some(LocationOptions {
+            country: "US".to_string(),
+            languages: vec!["en-US".to_string()],
+        }),
+        // Applied by the SDK, never sent
+        normalize_results: Some(true),
+    }
+}
+
+const MAP_OPTIONS_KEYS: &[&str] = &[
+    "search",
+    "ignoreSitemap",
+    "sitemapOnly",
+    "includeSubdomains",
+    "limit",
+    "timeout",
+    "location",
+];
+
+#[test]
+fn test_map_options_keys() {
+    assert_eq!(keys(&full_map_options()), expected(MAP_OPTIONS_KEYS));
+
+    let body = MapRequestBody {
+        url: "https://example.com".to_string(),
+        options: full_map_options(),
+    };
+    let mut expected_keys = expected(MAP_OPTIONS_KEYS);
+    expected_keys.insert("url".to_string());
+    assert_eq!(keys(&body), expected_keys);
+}
+
+fn full_search_options() -> SearchOptions {
+    SearchOptions {
+        limit: Some(5),
+        lang: Some("en".to_string()),
+        country: Some("us".to_string()),
+        tbs: Some("qdr:d".to_string()),
+        filter: Some("filter".to_string()),
+        location: Some(search::LocationOptions::default()),
+        scrape_options: Some(ScrapeOptions::default()),
+        max_results: Some(5),
+    }
+}
+
+const SEARCH_OPTIONS_KEYS: &[&str] = &[
+    "limit",
+    "lang",
+    "country",
+    "tbs",
+    "filter",
+    "location",
+    "scrapeOptions",
+    "maxResults",
+];
+
+#[test]
+fn test_search_options_keys() {
+    assert_eq!(keys(&full_search_options()), expected(SEARCH_OPTIONS_KEYS));
+
+    let body = SearchRequestBody {
+        query: "firecrawl".to_string(),
+        options: full_search_options(),
+    };
+    let mut expected_keys = expected(SEARCH_OPTIONS_KEYS);
+    expected_keys.insert("query".to_string());
+    assert_eq!(keys(&body), expected_keys);
+}
+
+#[test]
+fn test_document_metadata_keys() {
+    let metadata = DocumentMetadata {
+        source_url: "https://example.com".to_string(),
+        status_code: 200,
+        error: Some("error".to_string()),
+        title: Some("title".to_string()),
+        description: Some("description".to_string()),
+        language: Some("en".to_string()),
+        content_type: Some("text/html".to_string()),
+        last_modified: Some("Wed, 21 Oct 2015 07:28:00 GMT".to_string()),
+        etag: Some("\"33a64df5\"".to_string()),
+        additional_fields: HashMap::new(),
+    };
+
+    assert_eq!(
+        keys(&metadata),
+        expected(&[
+            "sourceURL",
+            "statusCode",
+            "error",
+            "title",
+            "description",
+            "language",
+            "contentType",
+            "lastModified",
+            "etag",
+        ])
+    );
+}
@@ -0,0 +1,197 @@
+//! Keeps the deprecated positional methods equivalent to their params-struct replacements until
+//! they are removed.
+//!
+//! Each deprecated method and its replacement are called with the same arguments against a mock
+//! server, and the requests they send must be byte-identical. The deprecation notes must name the
+//! replacement, since they are the migration guide downstream users see.
+#![allow(deprecated)]
+
+use std::collections::HashMap;
+
+use serde_json::json;
+use wiremock::{
+    Mock, MockServer, Request, ResponseTemplate,
+    matchers::{method, path},
+};
+
+use crate::{
+    FirecrawlApp,
+    batch_scrape::{BatchScrapeParams, Webhook},
+    crawl::{CrawlOptions, CrawlParams},
+    scrape::{ScrapeFormats, ScrapeOptions},
+};
+
+fn webhook() -> Webhook {
+    Webhook {
+        url: "https://hooks.example.org/firecrawl".to_string(),
+        headers: Some(HashMap::from([(
+            "Authorization".to_string(),
+            "Bearer token".to_string(),
+        )])),
+        metadata: None,
+        events: Some(vec!["completed".to_string()]),
+    }
+}
+
+/// Method, path, idempotency key and body of every request but the status checks.
+async fn sent_jobs(server: &MockServer) -> Vec<(String, String, Option<String>, Vec<u8>)> {
+    let requests: Vec<Request> = server.received_requests().await.unwrap();
+    requests
+        .into_iter()
+        .filter(|request| request.method.as_str() == "POST")
+        .map(|request| {
+            let idempotency_key = request
+                .headers
+                .get("x-idempotency-key")
+                .map(|value| value.to_str().unwrap().to_string());
+            (
+                request.method.to_string(),
+                request.url.path().to_string(),
+                idempotency_key,
+                request.body,
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_crawl_url_sends_the_same_request_as_crawl() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/crawl"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "success": true,
+            "id": "crawl-123",
+            "url": "https://api.firecrawl.dev/v1/crawl/crawl-123"
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/crawl/crawl-123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "status": "completed",
+            "total": 0,
+            "completed": 0,
+            "creditsUsed": 0,
+            "expiresAt": "2025-01-02T03:04:05.000Z",
+            "data": []
+        })))
+        .mount(&server)
+        .await;
+
+    let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+    let options = CrawlOptions::default()
+        .with_limit(10)
+        .with_exclude_paths(["/blog/*".to_string()])
+        .with_scrape_options(ScrapeOptions::default().with_formats([ScrapeFormats::Markdown]));
+
+    app.crawl_url(
+        "https://example.com",
+        options.clone(),
+        webhook(),
+        Some(1),
+        Some("crawl-1".to_string()),
+    )
+    .await
+    .unwrap();
+    app.crawl(
+        CrawlParams::new("https://example.com", webhook())
+            .with_options(options)
+            .with_poll_interval(1)
+            .with_idempotency_key("crawl-1"),
+    )
+    .await
+    .unwrap();
+
+    let jobs = sent_jobs(&server).await;
+    assert_eq!(jobs.len(), 2);
+    assert_eq!(jobs[0], jobs[1]);
+}
+
+#[tokio::test]
+async fn test_batch_scrape_urls_sends_the_same_request_as_batch_scrape() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/batch/scrape"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "success": true,
+            "id": "batch-123",
+            "url": "https://api.firecrawl.dev/v1/batch/scrape/batch-123"
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/batch/scrape/batch-123"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "success": true,
+            "status": "completed",
+            "total": 0,
+            "completed": 0,
+            "creditsUsed": 0,
+            "data": []
+        })))
+        .mount(&server)
+        .await;
+
+    let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+    let urls = vec![
+        "https://example.com/1".to_string(),
+        "https://example.com/2".to_string(),
+    ];
+    let options = ScrapeOptions::default()
+        .with_formats([ScrapeFormats::Markdown, ScrapeFormats::Links])
+        .with_only_main_content(true);
+
+    app.batch_scrape_urls(
+        urls.clone(),
+        options.clone(),
+        Some(1),
+        Some("batch-1".to_string()),
+        webhook(),
+        Some(true),
+    )
+    .await
+    .unwrap();
+    app.batch_scrape(
+        BatchScrapeParams::new(urls, webhook())
+            .with_options(options)
+            .with_poll_interval(1)
+            .with_idempotency_key("batch-1")
+            .with_ignore_invalid_urls(true),
+    )
+    .await
+    .unwrap();
+
+    let jobs = sent_jobs(&server).await;
+    assert_eq!(jobs.len(), 2);
+    assert_eq!(jobs[0], jobs[1]);
+}
+
+/// The `#[deprecated]` attribute right before `pub async fn {function}(` in `source`.
+fn deprecation_note<'a>(source: &'a str, function: &str) -> &'a str {
+    let end = source
+        .find(&format!("pub async fn {function}("))
+        .unwrap_or_else(|| panic!("{function} not found"));
+    let start = source[..end]
+        .rfind("#[deprecated(")
+        .unwrap_or_else(|| panic!("{function} is not deprecated"));
+    let note = &source[start..end];
+    assert!(
+        !note.contains("pub "),
+        "the deprecation before {function} belongs to another item"
+    );
+    note
+}
+
+#[test]
+fn test_deprecation_notes_name_their_replacement() {
+    let note = deprecation_note(include_str!("crawl.rs"), "crawl_url");
+    for name in ["FirecrawlApp::crawl`", "CrawlParams"] {
+        assert!(note.contains(name), "{name} missing from {note}");
+    }
+
+    let note = deprecation_note(include_str!("batch_scrape.rs"), "batch_scrape_urls");
+    for name in ["FirecrawlApp::batch_scrape`", "BatchScrapeParams"] {
+        assert!(note.contains(name), "{name} missing from {note}");
+    }
+}
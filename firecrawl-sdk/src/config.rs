@@ -0,0 +1,111 @@
+use std::time::Duration;
+
+use reqwest::Client;
+
+use crate::{FirecrawlError, client_builder};
+
+/// Request timeout of the clients built by the SDK, so a hung instance can't block forever.
+pub(crate) const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Connect timeout of the clients built by the SDK.
+pub(crate) const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Settings of the HTTP client built by
+/// [`FirecrawlApp::new_selfhosted_with_config`](crate::FirecrawlApp::new_selfhosted_with_config).
+///
+/// `None` leaves the setting to reqwest, which has no timeouts and no idle connection limit.
+/// Use [`FirecrawlApp::builder`](crate::FirecrawlApp::builder) for the less common settings.
+///
+/// None of the settings apply on `wasm32`, where requests go through the `fetch` of the host.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientConfig {
+    /// Timeout for establishing a connection. (default: 30 seconds)
+    pub connect_timeout: Option<Duration>,
+
+    /// Timeout of a whole request, from connecting until the response body is read.
+    /// (default: 120 seconds)
+    ///
+    /// Requests that scrape pages with `ScrapeOptions.timeout` set use that value plus a grace
+    /// period instead, so long scrapes with a high `wait_for` still have an upper bound.
+    pub request_timeout: Option<Duration>,
+
+    /// Maximum number of idle connections kept per host. (default: no limit)
+    pub pool_max_idle_per_host: Option<usize>,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Some(DEFAULT_CONNECT_TIMEOUT),
+            request_timeout: Some(DEFAULT_REQUEST_TIMEOUT),
+            pool_max_idle_per_host: None,
+        }
+    }
+}
+
+impl ClientConfig {
+    pub(crate) fn build_client(&self) -> Result<Client, FirecrawlError> {
+        let client = client_builder();
+        #[cfg(not(target_arch = "wasm32"))]
+        let client = self.configure_connections(client);
+
+        client
+            .build()
+            .map_err(|e| FirecrawlError::HttpError("Building HTTP client".to_string(), e))
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn configure_connections(&self, mut client: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        if let Some(timeout) = self.connect_timeout {
+            client = client.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.request_timeout {
+            client = client.timeout(timeout);
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            client = client.pool_max_idle_per_host(max);
+        }
+        client
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FirecrawlApp;
+    use wiremock::{Mock, MockServer, ResponseTemplate, matchers::method};
+
+    #[tokio::test]
+    async fn test_request_timeout() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({
+                        "success": true,
+                        "data": { "remaining_credits": 1 }
+                    }))
+                    .set_delay(Duration::from_millis(500)),
+            )
+            .mount(&server)
+            .await;
+
+        let config = ClientConfig {
+            request_timeout: Some(Duration::from_millis(200)),
+            ..Default::default()
+        };
+        let app = FirecrawlApp::new_selfhosted_with_config(server.uri(), Some("fc-test"), config)
+            .unwrap();
+        let result = app.get_credit_usage().await;
+        assert!(matches!(result, Err(FirecrawlError::HttpError(_, e)) if e.is_timeout()));
+
+        let config = ClientConfig {
+            request_timeout: None,
+            connect_timeout: None,
+            pool_max_idle_per_host: Some(4),
+        };
+        let app = FirecrawlApp::new_selfhosted_with_config(server.uri(), Some("fc-test"), config)
+            .unwrap();
+        assert!(app.get_credit_usage().await.is_ok());
+    }
+}
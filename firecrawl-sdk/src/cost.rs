@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+/// Callback registered with [`FirecrawlApp::with_cost_hook`](crate::FirecrawlApp::with_cost_hook).
+pub(crate) type CostHook = Arc<dyn Fn(&CostEvent<'_>) + Send + Sync>;
+
+/// Operation that consumed credits, see [`CostEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CostOperation {
+    /// A single scrape, whose credits are estimated by the SDK
+    Scrape,
+
+    /// A crawl, with the credits reported by the API
+    Crawl,
+
+    /// A batch scrape, with the credits reported by the API
+    BatchScrape,
+}
+
+/// Credits consumed by an operation, passed to the hook registered with
+/// [`FirecrawlApp::with_cost_hook`](crate::FirecrawlApp::with_cost_hook), e.g. to report the
+/// spending of each service.
+#[derive(Debug, Clone)]
+pub struct CostEvent<'a> {
+    /// Operation that consumed the credits
+    pub operation: CostOperation,
+
+    /// URL scraped, seed of the crawl or URLs of the batch scrape
+    pub targets: &'a [String],
+
+    /// Credits consumed
+    pub credits: u64,
+
+    /// Whether `credits` is the SDK's estimate, see `ScrapeOptions::estimated_credits_per_url`,
+    /// rather than what the API reported.
+    pub estimated: bool,
+
+    /// Whether the operation succeeded. Failed, cancelled or timed out crawls and batch scrapes
+    /// report the credits they consumed until then.
+    pub succeeded: bool,
+
+    /// Label set with `FirecrawlApp::with_cost_center`, or on the parameters of the operation,
+    /// which take precedence.
+    pub cost_center: Option<&'a str>,
+}
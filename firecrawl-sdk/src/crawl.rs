@@ -1,15 +1,28 @@
+use std::{
+    collections::{HashMap, hash_map::Entry},
+    time::Duration,
+};
+
+use futures::{Stream, StreamExt, stream};
+use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use url::Url;
 
 #[cfg(feature = "mcp-tool")]
 use schemars::JsonSchema;
 
 use crate::{
-    API_VERSION, FirecrawlApp, FirecrawlError,
-    batch_scrape::Webhook,
-    document::Document,
+    CancellationToken, CostOperation, FirecrawlApp, FirecrawlError, JobKind, PartialStatus,
+    PollPolicy, apply_timeout,
+    document::{Document, append_status_page, deserialize_status_page},
+    routes,
     scrape::{ScrapeFormats, ScrapeOptions},
+    time::Instant,
 };
 
+pub use crate::webhook::Webhook;
+
 #[derive(Deserialize, Serialize, Clone, Copy, Debug)]
 #[cfg_attr(feature = "mcp-tool", derive(JsonSchema))]
 pub enum CrawlScrapeFormats {
@@ -40,8 +53,18 @@ pub enum CrawlScrapeFormats {
     /// Can not be used in conjunction with `CrawlScrapeFormats::Screenshot`.
     #[serde(rename = "screenshot@fullPage")]
     ScreenshotFullPage,
+
+    /// Will result in structured JSON data extracted from every page, based on the schema
+    /// provided in `jsonOptions`.
+    ///
+    /// See `ScrapeOptions.json_options` for more options.
+    #[serde(rename = "json")]
+    JSON,
 }
 
+/// Every format of a crawl is a format of `ScrapeOptions.formats`, which is what
+/// `CrawlOptions.scrape_options` takes. This includes `JSON`, extracting structured data from
+/// every crawled page: there is no separate `extract` format.
 impl From<CrawlScrapeFormats> for ScrapeFormats {
     fn from(value: CrawlScrapeFormats) -> Self {
         match value {
@@ -51,6 +74,7 @@ impl From<CrawlScrapeFormats> for ScrapeFormats {
             CrawlScrapeFormats::Links => Self::Links,
             CrawlScrapeFormats::Screenshot => Self::Screenshot,
             CrawlScrapeFormats::ScreenshotFullPage => Self::ScreenshotFullPage,
+            CrawlScrapeFormats::JSON => Self::JSON,
         }
     }
 }
@@ -90,8 +114,103 @@ pub struct CrawlOptions {
 
     /// Ignore query parameters when comparing URLs
     pub ignore_query_parameters: Option<bool>,
+
+    /// Maximum number of pages scraped at the same time, to spare fragile sites.
+    /// (default: the team's concurrency limit)
+    pub max_concurrency: Option<u32>,
 }
 
+option_setters!(CrawlOptions,
+    /// Setters to build options without struct update syntax.
+    ///
+    /// ```
+    /// use firecrawl_sdk::crawl::CrawlOptions;
+    /// use firecrawl_sdk::scrape::ScrapeOptions;
+    ///
+    /// let options = CrawlOptions::default()
+    ///     .with_limit(10)
+    ///     .with_include_paths(["/blog/*".to_string()])
+    ///     .with_max_concurrency(2)
+    ///     .with_scrape_options(ScrapeOptions::default().with_only_main_content(true));
+    /// assert_eq!(options.limit, Some(10));
+    /// ```
+    {
+        with_scrape_options => scrape_options: ScrapeOptions,
+        with_include_paths => include_paths: impl Into<Vec<String>>,
+        with_exclude_paths => exclude_paths: impl Into<Vec<String>>,
+        with_max_depth => max_depth: u32,
+        with_ignore_sitemap => ignore_sitemap: bool,
+        with_limit => limit: u32,
+        with_allow_backward_links => allow_backward_links: bool,
+        with_allow_external_links => allow_external_links: bool,
+        with_deduplicate_similar_urls => deduplicate_similar_urls: bool,
+        with_ignore_query_parameters => ignore_query_parameters: bool,
+        with_max_concurrency => max_concurrency: u32,
+    }
+);
+
+/// Parameters of [`FirecrawlApp::crawl`].
+#[derive(Debug, Clone)]
+pub struct CrawlParams {
+    /// URL to start crawling from
+    pub url: String,
+
+    /// Options of the crawl. (default: the API's defaults)
+    pub options: Option<CrawlOptions>,
+
+    /// Webhook notified about the progress of the crawl
+    pub webhook: Webhook,
+
+    /// Intervals between two checks of the crawl's status. (default: every 2 seconds)
+    pub poll_policy: Option<PollPolicy>,
+
+    /// Sent as `x-idempotency-key`, so a retried request doesn't start a second crawl
+    pub idempotency_key: Option<String>,
+
+    /// Label of the crawl's `CostEvent`, instead of the one set with
+    /// `FirecrawlApp::with_cost_center`
+    pub cost_center: Option<String>,
+}
+
+impl CrawlParams {
+    /// Crawls `url` with the default options, notifying `webhook`.
+    pub fn new(url: impl Into<String>, webhook: Webhook) -> Self {
+        Self {
+            url: url.into(),
+            options: None,
+            webhook,
+            poll_policy: None,
+            idempotency_key: None,
+            cost_center: None,
+        }
+    }
+
+    /// Checks the crawl's status every `poll_interval` milliseconds.
+    pub fn with_poll_interval(self, poll_interval: u64) -> Self {
+        self.with_poll_policy(PollPolicy::from_millis(poll_interval))
+    }
+}
+
+option_setters!(CrawlParams,
+    /// Setters for the optional parameters.
+    ///
+    /// ```
+    /// use firecrawl_sdk::PollPolicy;
+    /// use firecrawl_sdk::crawl::{CrawlOptions, CrawlParams, Webhook};
+    ///
+    /// let params = CrawlParams::new("https://example.com", Webhook::dummy())
+    ///     .with_options(CrawlOptions::default().with_limit(10))
+    ///     .with_poll_interval(5000);
+    /// assert_eq!(params.poll_policy, Some(PollPolicy::from_millis(5000)));
+    /// ```
+    {
+        with_options => options: CrawlOptions,
+        with_poll_policy => poll_policy: PollPolicy,
+        with_idempotency_key => idempotency_key: impl Into<String>,
+        with_cost_center => cost_center: impl Into<String>,
+    }
+);
+
 #[derive(Deserialize, Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct CrawlRequestBody {
@@ -116,11 +235,12 @@ pub struct CrawlResponse {
     pub data: Document,
 }
 
-#[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Deserialize, Serialize, Debug, Default, PartialEq, Eq, Clone)]
 #[cfg_attr(feature = "mcp-tool", derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub enum CrawlStatusTypes {
     /// The crawl job is in progress.
+    #[default]
     Scraping,
 
     /// The crawl job has been completed successfully.
@@ -131,6 +251,21 @@ pub enum CrawlStatusTypes {
 
     /// The crawl job has been cancelled.
     Cancelled,
+
+    /// A status this version of the SDK doesn't know, as sent by the API, which is monitored
+    /// like `Scraping`.
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+impl CrawlStatusTypes {
+    /// Whether the crawl job has stopped, i.e. it completed, failed or was cancelled.
+    pub fn is_terminal(&self) -> bool {
+        !matches!(
+            self,
+            CrawlStatusTypes::Scraping | CrawlStatusTypes::Unknown(_)
+        )
+    }
 }
 
 #[serde_with::skip_serializing_none]
@@ -150,7 +285,9 @@ pub struct CrawlStatus {
     pub credits_used: u32,
 
     /// Expiry time of crawl data. After this date, the crawl data will be unavailable from the API.
-    pub expires_at: String, // TODO: parse into date
+    ///
+    /// Enable the `chrono` feature to have it parsed by `expires_at_timestamp`.
+    pub expires_at: String,
 
     /// URL to call to get the next batch of documents.
     /// Unless you are sidestepping the SDK, you do not need to deal with this.
@@ -158,6 +295,179 @@ pub struct CrawlStatus {
 
     /// List of documents returned by the crawl
     pub data: Vec<Document>,
+
+    /// Documents of `data` the SDK couldn't deserialize, e.g. with a metadata field of an
+    /// unexpected type, as their index among the documents sent by the API and the error.
+    #[serde(skip)]
+    pub skipped_documents: Vec<(usize, String)>,
+
+    /// Id of the last status request, to share with Firecrawl support. Filled in by the SDK from
+    /// the `x-request-id` response header.
+    #[serde(skip)]
+    pub request_id: Option<String>,
+
+    /// Options the crawl was started with, when started with [`FirecrawlApp::crawl`], so
+    /// [`FirecrawlApp::retry_crawl`] can start it again.
+    #[serde(skip)]
+    pub options: Option<CrawlOptions>,
+
+    /// Webhook the crawl was started with, when started with [`FirecrawlApp::crawl`].
+    #[serde(skip)]
+    pub webhook: Option<Webhook>,
+}
+
+impl CrawlStatus {
+    /// `expires_at` parsed into a timestamp, or `None` if it's empty or not an ISO-8601
+    /// timestamp.
+    #[cfg(feature = "chrono")]
+    pub fn expires_at_timestamp(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        crate::timestamp::parse(&self.expires_at)
+    }
+
+    /// Share of the pages scraped so far, from `0.0` to `1.0`, or `None` while no page has been
+    /// discovered yet. As `total` may grow during the crawl, the fraction may go down too.
+    pub fn progress_fraction(&self) -> Option<f32> {
+        (self.total != 0).then(|| self.completed as f32 / self.total as f32)
+    }
+
+    /// Pairs the source URL of each document with the links found on its page, in crawl order,
+    /// e.g. to analyze the link graph of the site.
+    ///
+    /// Links are only returned with `CrawlScrapeFormats::Links`, so without it every page has
+    /// an empty list.
+    pub fn link_graph(&self) -> Vec<(String, Vec<String>)> {
+        self.data
+            .iter()
+            .map(|document| {
+                (
+                    document.metadata.source_url.clone(),
+                    document.links.clone().unwrap_or_default(),
+                )
+            })
+            .collect()
+    }
+
+    /// The documents whose page loaded successfully, see [`Document::was_successful`].
+    pub fn successful_documents(&self) -> impl Iterator<Item = &Document> {
+        self.data
+            .iter()
+            .filter(|document| document.was_successful())
+    }
+
+    /// Groups the documents by the first path segment of their source URL, e.g. `/blog` for
+    /// `https://example.com/blog/post`, in crawl order within each group.
+    ///
+    /// Pages at the root of the site are keyed by `/`, and documents whose source URL can't be
+    /// parsed by an empty string.
+    pub fn group_by_path_prefix(&self) -> HashMap<String, Vec<&Document>> {
+        let mut groups: HashMap<String, Vec<&Document>> = HashMap::new();
+        for document in &self.data {
+            groups
+                .entry(path_prefix(&document.metadata.source_url))
+                .or_default()
+                .push(document);
+        }
+        groups
+    }
+}
+
+/// Combined result of crawling several seeds with [`FirecrawlApp::crawl_urls`].
+#[derive(Debug)]
+pub struct CrawlOutcome {
+    /// Documents of all the crawls in seed order, keeping one document per normalized source URL.
+    pub data: Vec<Document>,
+
+    /// Credits used by all the crawls, including failed and cancelled ones.
+    pub credits_used: u32,
+
+    /// Result of the crawl of each seed, in seed order. Their documents are moved to `data`.
+    pub crawls: Vec<(String, Result<CrawlStatus, FirecrawlError>)>,
+}
+
+impl CrawlOutcome {
+    /// Merges the results of crawling `seeds`, deduplicating the documents.
+    fn merge(seeds: Vec<String>, results: Vec<Result<CrawlStatus, FirecrawlError>>) -> Self {
+        let mut data: Vec<Document> = Vec::new();
+        let mut positions: HashMap<String, usize> = HashMap::new();
+        let mut credits_used = 0;
+        let mut crawls = Vec::with_capacity(seeds.len());
+
+        for (seed, mut result) in seeds.into_iter().zip(results) {
+            if let Some(status) = crawl_status_mut(&mut result) {
+                credits_used += status.credits_used;
+                for document in std::mem::take(&mut status.data) {
+                    match positions.entry(normalize_url(&document.metadata.source_url)) {
+                        Entry::Occupied(entry) => {
+                            // Keep the document that came back with the most formats
+                            let kept = &mut data[*entry.get()];
+                            if document.present_formats().len() > kept.present_formats().len() {
+                                *kept = document;
+                            }
+                        }
+                        Entry::Vacant(entry) => {
+                            entry.insert(data.len());
+                            data.push(document);
+                        }
+                    }
+                }
+            }
+            crawls.push((seed, result));
+        }
+
+        Self {
+            data,
+            credits_used,
+            crawls,
+        }
+    }
+
+    /// Whether the crawls of all the seeds completed.
+    pub fn is_complete(&self) -> bool {
+        self.crawls.iter().all(|(_, result)| result.is_ok())
+    }
+}
+
+/// `url` without its fragment and the trailing slash of its path, so links to the same page
+/// compare equal. URLs that can't be parsed are kept as is.
+fn normalize_url(url: &str) -> String {
+    let Ok(mut url) = Url::parse(url) else {
+        return url.to_string();
+    };
+    url.set_fragment(None);
+    if url.path().len() > 1 && url.path().ends_with('/') {
+        let path = url.path().trim_end_matches('/').to_string();
+        url.set_path(&path);
+    }
+    url.to_string()
+}
+
+/// The crawl status carried by the result of a crawl, if any.
+fn crawl_status_mut(result: &mut Result<CrawlStatus, FirecrawlError>) -> Option<&mut CrawlStatus> {
+    match result {
+        Ok(status) => Some(status),
+        Err(
+            FirecrawlError::CrawlJobFailed(_, status)
+            | FirecrawlError::CrawlJobCancelled(status)
+            | FirecrawlError::OperationCancelled(PartialStatus::Crawl(status))
+            | FirecrawlError::PollTimeout {
+                last_status: PartialStatus::Crawl(status),
+                ..
+            },
+        ) => Some(status),
+        Err(_) => None,
+    }
+}
+
+/// First path segment of `url` with its leading slash, `/` for the root.
+fn path_prefix(url: &str) -> String {
+    let Ok(url) = Url::parse(url) else {
+        return String::new();
+    };
+    let segment = url
+        .path_segments()
+        .and_then(|mut segments| segments.next())
+        .unwrap_or_default();
+    format!("/{segment}")
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -193,6 +503,15 @@ pub struct CrawlUrlInput {
     pub webhook: Option<Webhook>,
 }
 
+/// Deserializes a status page of a crawl, skipping malformed documents.
+fn crawl_status_page(page: Value) -> Result<CrawlStatus, FirecrawlError> {
+    let (mut status, data, skipped_documents): (CrawlStatus, _, _) =
+        deserialize_status_page(page).map_err(FirecrawlError::ResponseParseError)?;
+    status.data = data;
+    status.skipped_documents = skipped_documents;
+    Ok(status)
+}
+
 impl FirecrawlApp {
     /// Initiates a crawl job for a URL using the Firecrawl API.
     pub async fn crawl_url_async(
@@ -208,14 +527,17 @@ impl FirecrawlApp {
             webhook,
         };
 
-        let headers = self.prepare_headers(idempotency_key.as_ref());
+        let idempotency_key = self.idempotency_key(idempotency_key);
+        let headers = self.prepare_headers(idempotency_key.as_ref())?;
 
-        let response = self
+        let request = self
+            .inner
             .client
-            .post(format!("{}/{}/crawl", self.api_url, API_VERSION))
+            .post(self.endpoint(routes::crawl()))
             .headers(headers.clone())
-            .json(&body)
-            .send()
+            .json(&body);
+        let response = self
+            .send_request(request)
             .await
             .map_err(|e| FirecrawlError::HttpError(format!("Crawling {:?}", url.as_ref()), e))?;
 
@@ -224,6 +546,16 @@ impl FirecrawlApp {
     }
 
     /// Performs a crawl job for a URL using the Firecrawl API, waiting for the end result. This may take a long time depending on the size of the target page and your options (namely `CrawlOptions.limit`).
+    pub async fn crawl(&self, params: CrawlParams) -> Result<CrawlStatus, FirecrawlError> {
+        self.crawl_with_cancellation(params, CancellationToken::new())
+            .await
+    }
+
+    /// Performs a crawl job for a URL, like [`FirecrawlApp::crawl`].
+    #[deprecated(
+        since = "0.6.0",
+        note = "use `FirecrawlApp::crawl` with `CrawlParams` instead: `app.crawl(CrawlParams::new(url, webhook).with_options(options))`, with `CrawlParams::with_poll_interval` and `CrawlParams::with_idempotency_key` for the other arguments"
+    )]
     pub async fn crawl_url(
         &self,
         url: impl AsRef<str>,
@@ -232,38 +564,178 @@ impl FirecrawlApp {
         poll_interval: Option<u64>,
         idempotency_key: Option<String>,
     ) -> Result<CrawlStatus, FirecrawlError> {
-        let options = options.into();
-        let poll_interval = poll_interval.unwrap_or(2000);
+        self.crawl(CrawlParams {
+            url: url.as_ref().to_string(),
+            options: options.into(),
+            webhook,
+            poll_policy: poll_interval.map(PollPolicy::from_millis),
+            idempotency_key,
+            cost_center: None,
+        })
+        .await
+    }
+
+    /// Like [`FirecrawlApp::crawl`], but stops polling once `cancel` is cancelled.
+    ///
+    /// Cancelling fails with `FirecrawlError::OperationCancelled`, carrying the last status with
+    /// the documents collected so far. The crawl itself keeps running on the API.
+    pub async fn crawl_with_cancellation(
+        &self,
+        params: CrawlParams,
+        cancel: CancellationToken,
+    ) -> Result<CrawlStatus, FirecrawlError> {
+        let poll_policy = params.poll_policy.unwrap_or_default();
+        let timeout = crate::scrape_request_timeout(
+            params
+                .options
+                .as_ref()
+                .and_then(|o| o.scrape_options.as_ref())
+                .and_then(|s| s.timeout),
+        );
 
+        let options = params.options.clone();
+        let webhook = params.webhook.clone();
         let res = self
-            .crawl_url_async(url, options, idempotency_key, webhook)
+            .crawl_url_async(
+                &params.url,
+                params.options,
+                params.idempotency_key,
+                params.webhook,
+            )
             .await?;
 
-        self.monitor_crawl_status(&res.id, poll_interval).await
+        let mut result = self
+            .monitor_crawl_status(&res.id, &poll_policy, timeout, &cancel)
+            .await;
+        let succeeded = result.is_ok();
+        if let Some(status) = crawl_status_mut(&mut result) {
+            status.options = options;
+            status.webhook = Some(webhook);
+            self.report_cost(
+                CostOperation::Crawl,
+                std::slice::from_ref(&params.url),
+                status.credits_used.into(),
+                succeeded,
+                params.cost_center.as_deref(),
+            );
+        }
+        let status = result?;
+        #[cfg(feature = "otel")]
+        self.inner
+            .metrics
+            .record_credits_used("crawl", status.credits_used.into());
+
+        Ok(status)
+    }
+
+    /// Like [`FirecrawlApp::crawl_with_cancellation`], with positional arguments.
+    pub async fn crawl_url_with_cancellation(
+        &self,
+        url: impl AsRef<str>,
+        options: impl Into<Option<CrawlOptions>>,
+        webhook: Webhook,
+        poll_interval: Option<u64>,
+        idempotency_key: Option<String>,
+        cancel: CancellationToken,
+    ) -> Result<CrawlStatus, FirecrawlError> {
+        let params = CrawlParams {
+            url: url.as_ref().to_string(),
+            options: options.into(),
+            webhook,
+            poll_policy: poll_interval.map(PollPolicy::from_millis),
+            idempotency_key,
+            cost_center: None,
+        };
+        self.crawl_with_cancellation(params, cancel).await
+    }
+
+    /// Starts a failed or cancelled crawl of `original_url` again, with the options of
+    /// `previous` changed by `adjust`, e.g. to lower the limit or switch the proxy.
+    ///
+    /// The options and webhook of `previous` are only known when it was started with
+    /// [`FirecrawlApp::crawl`]. Otherwise `adjust` starts from the default options, and no
+    /// webhook is notified. Fails with a configuration error if `previous` is still running or
+    /// completed.
+    pub async fn retry_crawl(
+        &self,
+        previous: &CrawlStatus,
+        original_url: &str,
+        adjust: impl FnOnce(&mut CrawlOptions),
+    ) -> Result<CrawlStatus, FirecrawlError> {
+        if !matches!(
+            previous.status,
+            CrawlStatusTypes::Failed | CrawlStatusTypes::Cancelled
+        ) {
+            return Err(FirecrawlError::configuration(format!(
+                "Only failed or cancelled crawls can be retried, this one is {:?}",
+                previous.status
+            )));
+        }
+
+        let mut options = previous.options.clone().unwrap_or_default();
+        adjust(&mut options);
+        let webhook = previous.webhook.clone().unwrap_or_else(Webhook::dummy);
+        self.crawl(CrawlParams::new(original_url, webhook).with_options(options))
+            .await
+    }
+
+    /// Crawls each of `seeds` with the same options, waiting for all the crawls, and merges
+    /// their results into one [`CrawlOutcome`].
+    ///
+    /// The API crawls from a single URL, so one job is started per seed and they run
+    /// concurrently. Pages reached from several seeds are kept once, preferring the document with
+    /// the most formats. A failing crawl doesn't stop the others; its error is reported in
+    /// `CrawlOutcome::crawls`. Fails with a configuration error if `seeds` is empty.
+    pub async fn crawl_urls(
+        &self,
+        seeds: Vec<String>,
+        options: impl Into<Option<CrawlOptions>>,
+        webhook: Webhook,
+    ) -> Result<CrawlOutcome, FirecrawlError> {
+        if seeds.is_empty() {
+            return Err(FirecrawlError::configuration(
+                "No URL to start crawling from",
+            ));
+        }
+
+        let options = options.into();
+        let results = futures::future::join_all(seeds.iter().map(|seed| {
+            let mut params = CrawlParams::new(seed, webhook.clone());
+            params.options = options.clone();
+            self.crawl(params)
+        }))
+        .await;
+
+        Ok(CrawlOutcome::merge(seeds, results))
     }
 
     async fn check_crawl_status_next(
         &self,
         next: impl AsRef<str>,
+        timeout: Option<Duration>,
     ) -> Result<CrawlStatus, FirecrawlError> {
-        let response = self
+        let request = self
+            .inner
             .client
             .get(next.as_ref())
-            .headers(self.prepare_headers(None))
-            .send()
-            .await
-            .map_err(|e| {
-                FirecrawlError::HttpError(
-                    format!("Paginating crawl using URL {:?}", next.as_ref()),
-                    e,
-                )
-            })?;
-
-        self.handle_response(
-            response,
-            format!("Paginating crawl using URL {:?}", next.as_ref()),
-        )
-        .await
+            .headers(self.prepare_headers(None)?);
+        let request = apply_timeout(request, timeout);
+        let response = self.send_request(request).await.map_err(|e| {
+            FirecrawlError::HttpError(format!("Paginating crawl using URL {:?}", next.as_ref()), e)
+        })?;
+
+        let (page, request_id) = self
+            .handle_response_with_request_id(
+                response,
+                format!("Paginating crawl using URL {:?}", next.as_ref()),
+            )
+            .await?;
+        #[cfg(feature = "strict")]
+        self.warn_unknown_fields(crate::strict::status_page_warnings::<CrawlStatus>(&page));
+        let mut status = crawl_status_page(page)?;
+        status.request_id = request_id;
+        self.cap_html(&mut status.data);
+        Ok(status)
     }
 
     /// Checks for the status of a crawl, based on the crawl's ID. To be used in conjunction with `FirecrawlApp::crawl_url_async`.
@@ -271,32 +743,49 @@ impl FirecrawlApp {
         &self,
         id: impl AsRef<str>,
     ) -> Result<CrawlStatus, FirecrawlError> {
-        let response = self
+        self.check_crawl_status_with_timeout(id, None).await
+    }
+
+    async fn check_crawl_status_with_timeout(
+        &self,
+        id: impl AsRef<str>,
+        timeout: Option<Duration>,
+    ) -> Result<CrawlStatus, FirecrawlError> {
+        let request = self
+            .inner
             .client
-            .get(format!(
-                "{}/{}/crawl/{}",
-                self.api_url,
-                API_VERSION,
-                id.as_ref()
-            ))
-            .headers(self.prepare_headers(None))
-            .send()
-            .await
-            .map_err(|e| {
-                FirecrawlError::HttpError(format!("Checking status of crawl {}", id.as_ref()), e)
-            })?;
+            .get(self.endpoint(routes::crawl_status(id.as_ref())))
+            .headers(self.prepare_headers(None)?);
+        let request = apply_timeout(request, timeout);
+        let response = self.send_request(request).await.map_err(|e| {
+            FirecrawlError::HttpError(format!("Checking status of crawl {}", id.as_ref()), e)
+        })?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(FirecrawlError::JobNotFound {
+                kind: JobKind::Crawl,
+                id: id.as_ref().to_string(),
+            });
+        }
 
-        let mut status: CrawlStatus = self
-            .handle_response(
+        let (page, request_id) = self
+            .handle_response_with_request_id(
                 response,
                 format!("Checking status of crawl {}", id.as_ref()),
             )
             .await?;
+        #[cfg(feature = "strict")]
+        self.warn_unknown_fields(crate::strict::status_page_warnings::<CrawlStatus>(&page));
+        let mut status = crawl_status_page(page)?;
+        status.request_id = request_id;
+        self.cap_html(&mut status.data);
 
         if status.status == CrawlStatusTypes::Completed {
             while let Some(next) = status.next {
-                let new_status = self.check_crawl_status_next(next).await?;
-                status.data.extend_from_slice(&new_status.data);
+                let new_status = self.check_crawl_status_next(next, timeout).await?;
+                append_status_page(
+                    (&mut status.data, &mut status.skipped_documents),
+                    (new_status.data, new_status.skipped_documents),
+                );
                 status.next = new_status.next;
             }
         }
@@ -304,28 +793,95 @@ impl FirecrawlApp {
         Ok(status)
     }
 
+    /// Streams the documents of a crawl one page at a time, following the `next` cursor, so they
+    /// can be processed without holding the whole result in memory.
+    ///
+    /// Only the documents scraped so far are returned while the crawl is still running, so this
+    /// is best used once it has finished. A failed or cancelled crawl ends the stream with
+    /// `FirecrawlError::CrawlJobFailed` or `FirecrawlError::CrawlJobCancelled`, carrying the
+    /// status without its documents.
+    pub fn crawl_status_stream(
+        &self,
+        id: &str,
+    ) -> impl Stream<Item = Result<Document, FirecrawlError>> {
+        let first_page = self.endpoint(routes::crawl_status(id)).to_string();
+
+        stream::unfold(Some(first_page), move |page| async move {
+            let page = page?;
+            let mut status = match self.check_crawl_status_next(page, None).await {
+                Ok(status) => status,
+                Err(e) => return Some((vec![Err(e)], None)),
+            };
+
+            let mut items: Vec<_> = std::mem::take(&mut status.data)
+                .into_iter()
+                .map(Ok)
+                .collect();
+            let next = status.next.take();
+            if next.is_none() {
+                match status.status {
+                    CrawlStatusTypes::Failed => items.push(Err(FirecrawlError::CrawlJobFailed(
+                        "Crawl job failed.".to_string(),
+                        Box::new(status),
+                    ))),
+                    CrawlStatusTypes::Cancelled => {
+                        items.push(Err(FirecrawlError::CrawlJobCancelled(Box::new(status))))
+                    }
+                    CrawlStatusTypes::Completed
+                    | CrawlStatusTypes::Scraping
+                    | CrawlStatusTypes::Unknown(_) => {}
+                }
+            }
+
+            Some((items, next))
+        })
+        .flat_map(stream::iter)
+    }
+
     async fn monitor_crawl_status(
         &self,
         id: &str,
-        poll_interval: u64,
+        poll_policy: &PollPolicy,
+        timeout: Option<Duration>,
+        cancel: &CancellationToken,
     ) -> Result<CrawlStatus, FirecrawlError> {
+        let started = Instant::now();
+        let mut schedule = poll_policy.schedule();
+        let partial = |mut status: CrawlStatus, data, skipped| {
+            status.data = data;
+            status.skipped_documents = skipped;
+            PartialStatus::Crawl(Box::new(status))
+        };
         let mut all_data = Vec::new();
+        let mut all_skipped = Vec::new();
         let mut current_cursor: Option<String> = None;
+        // Warn about an unknown status once, not on every poll
+        let mut warned = false;
 
         loop {
             // Get status data, either from the base endpoint or using the next cursor
             let mut status_data = if let Some(ref cursor) = current_cursor {
-                self.check_crawl_status_next(cursor).await?
+                self.check_crawl_status_next(cursor, timeout).await?
             } else {
-                self.check_crawl_status(id).await?
+                self.check_crawl_status_with_timeout(id, timeout).await?
             };
 
             // Collect data from this page
-            all_data.append(&mut status_data.data);
+            append_status_page(
+                (&mut all_data, &mut all_skipped),
+                (
+                    std::mem::take(&mut status_data.data),
+                    std::mem::take(&mut status_data.skipped_documents),
+                ),
+            );
 
             // Check if we need to paginate
-            if let Some(next) = status_data.next {
-                current_cursor = Some(next);
+            if let Some(next) = &status_data.next {
+                if cancel.is_cancelled() {
+                    let status = partial(status_data, all_data, all_skipped);
+                    break Err(FirecrawlError::OperationCancelled(status));
+                }
+                current_cursor = Some(next.clone());
                 continue;
             }
 
@@ -334,24 +890,39 @@ impl FirecrawlApp {
                 CrawlStatusTypes::Completed => {
                     // Put all collected data back into the status
                     status_data.data = all_data;
+                    status_data.skipped_documents = all_skipped;
                     break Ok(status_data);
                 }
-                CrawlStatusTypes::Scraping => {
-                    tokio::time::sleep(tokio::time::Duration::from_millis(poll_interval)).await;
+                CrawlStatusTypes::Scraping | CrawlStatusTypes::Unknown(_) => {
+                    if let CrawlStatusTypes::Unknown(status) = &status_data.status
+                        && !warned
+                    {
+                        self.warn(&format!(
+                            "crawl job {id} has an unknown status {status:?}, polling again"
+                        ));
+                        warned = true;
+                    }
+                    let interval = schedule.next_interval(status_data.completed.into());
+                    if let Some(stop) = self.wait_for_next_poll(interval, cancel, started).await {
+                        let status = partial(status_data, all_data, all_skipped);
+                        break Err(stop.into_error(id, started, status));
+                    }
                     // Keep the cursor as is, to continue from where we left off
                 }
                 CrawlStatusTypes::Failed => {
                     // Put all collected data back into the status for error context
                     status_data.data = all_data;
+                    status_data.skipped_documents = all_skipped;
                     break Err(FirecrawlError::CrawlJobFailed(
                         "Crawl job failed.".to_string(),
-                        status_data,
+                        Box::new(status_data),
                     ));
                 }
                 CrawlStatusTypes::Cancelled => {
                     // Put all collected data back into the status for error context
                     status_data.data = all_data;
-                    break Err(FirecrawlError::CrawlJobCancelled(status_data));
+                    status_data.skipped_documents = all_skipped;
+                    break Err(FirecrawlError::CrawlJobCancelled(Box::new(status_data)));
                 }
             }
         }
@@ -423,8 +994,10 @@ mod schema_tests {
             property_keys.contains(&"allowExternalLinks".to_string()),
             "allowExternalLinks not found"
         );
+        // The webhook lives on the tool input rather than on the options themselves
+        let input_schema = async_claude::tool::parse_input_schema::<CrawlUrlInput>().unwrap();
         assert!(
-            property_keys.contains(&"webhook".to_string()),
+            input_schema["properties"]["webhook"].is_object(),
             "webhook not found"
         );
 
@@ -441,6 +1014,10 @@ mod schema_tests {
             property_keys.contains(&"ignoreQueryParameters".to_string()),
             "ignoreQueryParameters not found"
         );
+        assert!(
+            property_keys.contains(&"maxConcurrency".to_string()),
+            "maxConcurrency not found"
+        );
 
         // Check expected property types and descriptions for properties that certainly exist
         assert_eq!(properties["scrapeOptions"]["type"], "object");
@@ -466,5 +1043,1079 @@ mod schema_tests {
             properties["limit"]["type"] == "integer" || properties["limit"]["type"] == "number",
             "Property limit should be numeric"
         );
+        assert!(
+            properties["maxConcurrency"]["type"] == "integer"
+                || properties["maxConcurrency"]["type"] == "number",
+            "Property maxConcurrency should be numeric"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn crawl_status_json() -> serde_json::Value {
+        json!({
+            "status": "completed",
+            "total": 2,
+            "completed": 2,
+            "creditsUsed": 2,
+            "expiresAt": "2025-01-02T03:04:05.000Z",
+            "data": []
+        })
+    }
+
+    #[test]
+    fn test_crawl_options_max_concurrency() {
+        let options: CrawlOptions =
+            serde_json::from_value(json!({ "limit": 50, "maxConcurrency": 2 })).unwrap();
+        assert_eq!(options.max_concurrency, Some(2));
+        assert_eq!(
+            serde_json::to_value(&options).unwrap(),
+            json!({ "limit": 50, "maxConcurrency": 2 })
+        );
+
+        let options: CrawlOptions = serde_json::from_value(json!({})).unwrap();
+        assert_eq!(options.max_concurrency, None);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_crawl_status_expires_at_timestamp() {
+        use chrono::TimeZone;
+
+        let status: CrawlStatus = serde_json::from_value(crawl_status_json()).unwrap();
+        assert_eq!(
+            status.expires_at_timestamp(),
+            Some(chrono::Utc.with_ymd_and_hms(2025, 1, 2, 3, 4, 5).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_crawl_status_expires_at_string() {
+        let status: CrawlStatus = serde_json::from_value(crawl_status_json()).unwrap();
+        assert_eq!(status.expires_at, "2025-01-02T03:04:05.000Z");
+    }
+
+    #[tokio::test]
+    async fn test_unknown_crawl_id() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/crawl/crawl-unknown"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+                "success": false,
+                "error": "Job not found"
+            })))
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let error = app.check_crawl_status("crawl-unknown").await.unwrap_err();
+        assert!(
+            matches!(
+                &error,
+                FirecrawlError::JobNotFound { kind: JobKind::Crawl, id } if id == "crawl-unknown"
+            ),
+            "{error:?}"
+        );
+        assert_eq!(
+            error.to_string(),
+            "The crawl job crawl-unknown was not found or has expired"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_crawl_status_uses_scrape_timeout() {
+        use crate::scrape::ScrapeOptions;
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/crawl"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "id": "crawl-123",
+                "url": "https://api.firecrawl.dev/v1/crawl/crawl-123"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/crawl/crawl-123"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(crawl_status_json())
+                    .set_delay(Duration::from_millis(500)),
+            )
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::builder()
+            .api_url(server.uri())
+            .timeout(Duration::from_millis(200))
+            .build()
+            .unwrap();
+
+        let result = app.check_crawl_status("crawl-123").await;
+        assert!(matches!(result, Err(FirecrawlError::HttpError(_, e)) if e.is_timeout()));
+
+        let options = CrawlOptions {
+            scrape_options: Some(ScrapeOptions {
+                timeout: Some(1000),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let status = app
+            .crawl(
+                CrawlParams::new("https://example.com", Webhook::dummy())
+                    .with_options(options)
+                    .with_poll_interval(1),
+            )
+            .await
+            .unwrap();
+        assert_eq!(status.total, 2);
+    }
+
+    #[tokio::test]
+    async fn test_crawl_status_stream_follows_next() {
+        use futures::TryStreamExt;
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path, query_param},
+        };
+
+        let document = |url: &str| json!({ "metadata": { "sourceURL": url, "statusCode": 200 } });
+
+        let server = MockServer::start().await;
+        let mut first_page = crawl_status_json();
+        first_page["total"] = json!(3);
+        first_page["data"] = json!([
+            document("https://example.com/1"),
+            document("https://example.com/2")
+        ]);
+        first_page["next"] = json!(format!("{}/v1/crawl/crawl-123?skip=2", server.uri()));
+        let mut last_page = crawl_status_json();
+        last_page["total"] = json!(3);
+        last_page["data"] = json!([document("https://example.com/3")]);
+
+        Mock::given(method("GET"))
+            .and(path("/v1/crawl/crawl-123"))
+            .and(query_param("skip", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(last_page))
+            .with_priority(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/crawl/crawl-123"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(first_page))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let urls: Vec<String> = app
+            .crawl_status_stream("crawl-123")
+            .map_ok(|document| document.metadata.source_url)
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            urls,
+            vec![
+                "https://example.com/1",
+                "https://example.com/2",
+                "https://example.com/3"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_crawl_status_stream_failed_crawl() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let mut failed = crawl_status_json();
+        failed["status"] = json!("failed");
+        failed["data"] =
+            json!([{ "metadata": { "sourceURL": "https://example.com", "statusCode": 200 } }]);
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/crawl/crawl-123"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(failed))
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let items: Vec<_> = app.crawl_status_stream("crawl-123").collect().await;
+
+        assert_eq!(items.len(), 2);
+        assert!(items[0].is_ok());
+        assert!(matches!(
+            &items[1],
+            Err(FirecrawlError::CrawlJobFailed(_, status)) if status.data.is_empty()
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_crawl_status_skips_malformed_documents() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path, query_param},
+        };
+
+        let document = |url: &str| json!({ "metadata": { "sourceURL": url, "statusCode": 200 } });
+
+        let server = MockServer::start().await;
+        let mut first_page = crawl_status_json();
+        first_page["total"] = json!(5);
+        first_page["data"] = json!([
+            document("https://example.com/1"),
+            // An outdated worker sent the status code as a string
+            { "metadata": { "sourceURL": "https://example.com/2", "statusCode": "200" } },
+            document("https://example.com/3")
+        ]);
+        first_page["next"] = json!(format!("{}/v1/crawl/crawl-123?skip=3", server.uri()));
+        let mut last_page = crawl_status_json();
+        last_page["total"] = json!(5);
+        last_page["data"] = json!([{ "markdown": 42 }, document("https://example.com/5")]);
+
+        Mock::given(method("GET"))
+            .and(path("/v1/crawl/crawl-123"))
+            .and(query_param("skip", "3"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(last_page))
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/crawl/crawl-123"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(first_page))
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let status = app.check_crawl_status("crawl-123").await.unwrap();
+
+        let urls: Vec<&str> = status
+            .data
+            .iter()
+            .map(|document| document.metadata.source_url.as_str())
+            .collect();
+        assert_eq!(
+            urls,
+            vec![
+                "https://example.com/1",
+                "https://example.com/3",
+                "https://example.com/5"
+            ]
+        );
+        let skipped: Vec<usize> = status.skipped_documents.iter().map(|(i, _)| *i).collect();
+        assert_eq!(skipped, vec![1, 3]);
+        assert!(
+            status.skipped_documents[0].1.contains("expected u16"),
+            "{}",
+            status.skipped_documents[0].1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_crawl_status_warns_about_unknown_fields_in_strict_mode() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let server = MockServer::start().await;
+        let mut page = crawl_status_json();
+        page["crawlMode"] = json!("fast");
+        page["data"] = json!([{
+            "summary": "An example",
+            "metadata": { "sourceURL": "https://example.com", "statusCode": 200 }
+        }]);
+        Mock::given(method("GET"))
+            .and(path("/v1/crawl/crawl-123"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(page))
+            .mount(&server)
+            .await;
+
+        let warnings = std::sync::Arc::new(crate::observer::WarningRecorder::default());
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>)
+            .unwrap()
+            .with_hook(warnings.clone());
+        for _ in 0..2 {
+            let status = app.check_crawl_status("crawl-123").await.unwrap();
+            assert_eq!(status.data.len(), 1);
+        }
+
+        if cfg!(feature = "strict") {
+            assert_eq!(
+                warnings.take(),
+                vec![
+                    "unknown fields in CrawlStatus: crawlMode, the SDK may need updating",
+                    "unknown fields in Document: summary, the SDK may need updating",
+                ]
+            );
+        } else {
+            assert!(warnings.take().is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_crawl_url_with_cancellation_returns_partial_status() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/crawl"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "id": "crawl-123",
+                "url": "https://api.firecrawl.dev/v1/crawl/crawl-123"
+            })))
+            .mount(&server)
+            .await;
+        let mut status = crawl_status_json();
+        status["status"] = json!("scraping");
+        status["completed"] = json!(1);
+        status["data"] = json!([{
+            "markdown": "# One",
+            "metadata": { "sourceURL": "https://example.com/1", "statusCode": 200 }
+        }]);
+        Mock::given(method("GET"))
+            .and(path("/v1/crawl/crawl-123"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(status))
+            // Cancelled while waiting for the second poll
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let cancel = CancellationToken::new();
+        let canceller = cancel.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            canceller.cancel();
+        });
+
+        let started = std::time::Instant::now();
+        let error = app
+            .crawl_url_with_cancellation(
+                "https://example.com",
+                None,
+                Webhook::dummy(),
+                Some(60_000),
+                None,
+                cancel,
+            )
+            .await
+            .unwrap_err();
+
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(
+            error.to_string(),
+            "Monitoring of the crawl job was cancelled"
+        );
+        let FirecrawlError::OperationCancelled(PartialStatus::Crawl(status)) = error else {
+            panic!("{error:?}");
+        };
+        assert_eq!(status.status, CrawlStatusTypes::Scraping);
+        assert_eq!(status.completed, 1);
+        assert_eq!(status.data.len(), 1);
+        assert_eq!(status.data[0].metadata.source_url, "https://example.com/1");
+    }
+
+    #[tokio::test]
+    async fn test_crawl_url_gives_up_after_max_poll_wait() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/crawl"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "id": "crawl-stuck",
+                "url": "https://api.firecrawl.dev/v1/crawl/crawl-stuck"
+            })))
+            .mount(&server)
+            .await;
+        // A crawl stuck after a worker crash
+        let mut status = crawl_status_json();
+        status["status"] = json!("scraping");
+        status["completed"] = json!(1);
+        status["data"] = json!([{
+            "metadata": { "sourceURL": "https://example.com/1", "statusCode": 200 }
+        }]);
+        Mock::given(method("GET"))
+            .and(path("/v1/crawl/crawl-stuck"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(status))
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>)
+            .unwrap()
+            .with_max_poll_wait(Duration::from_millis(300));
+        let error = app
+            .crawl(
+                CrawlParams::new("https://example.com", Webhook::dummy()).with_poll_interval(100),
+            )
+            .await
+            .unwrap_err();
+
+        let FirecrawlError::PollTimeout {
+            job_id,
+            elapsed,
+            last_status: PartialStatus::Crawl(status),
+        } = error
+        else {
+            panic!("{error:?}");
+        };
+        assert_eq!(job_id, "crawl-stuck");
+        assert!(elapsed >= Duration::from_millis(300), "{elapsed:?}");
+        assert!(elapsed < Duration::from_secs(5), "{elapsed:?}");
+        assert_eq!(status.status, CrawlStatusTypes::Scraping);
+        assert!(!status.data.is_empty());
+        assert_eq!(status.data[0].metadata.source_url, "https://example.com/1");
+    }
+
+    #[tokio::test]
+    async fn test_cost_events_of_crawls() {
+        use std::sync::{Arc, Mutex};
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{body_partial_json, method, path},
+        };
+
+        use crate::{CostEvent, CostOperation};
+
+        let server = MockServer::start().await;
+        for (seed, id, status, credits) in [
+            ("https://example.com", "crawl-ok", "completed", 2),
+            ("https://example.org", "crawl-failed", "failed", 7),
+        ] {
+            Mock::given(method("POST"))
+                .and(path("/v1/crawl"))
+                .and(body_partial_json(json!({ "url": seed })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "success": true,
+                    "id": id,
+                    "url": format!("https://api.firecrawl.dev/v1/crawl/{id}")
+                })))
+                .mount(&server)
+                .await;
+            let mut body = crawl_status_json();
+            body["status"] = json!(status);
+            body["creditsUsed"] = json!(credits);
+            Mock::given(method("GET"))
+                .and(path(format!("/v1/crawl/{id}")))
+                .respond_with(ResponseTemplate::new(200).set_body_json(body))
+                .mount(&server)
+                .await;
+        }
+
+        type Recorded = (CostOperation, Vec<String>, u64, bool, bool, Option<String>);
+        let events: Arc<Mutex<Vec<Recorded>>> = Arc::default();
+        let recorder = events.clone();
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>)
+            .unwrap()
+            .with_cost_center("search-indexer")
+            .with_cost_hook(move |event: &CostEvent<'_>| {
+                recorder.lock().unwrap().push((
+                    event.operation,
+                    event.targets.to_vec(),
+                    event.credits,
+                    event.estimated,
+                    event.succeeded,
+                    event.cost_center.map(String::from),
+                ));
+            });
+
+        app.crawl(CrawlParams::new("https://example.com", Webhook::dummy()))
+            .await
+            .unwrap();
+        let error = app
+            .crawl(
+                CrawlParams::new("https://example.org", Webhook::dummy())
+                    .with_cost_center("archiver"),
+            )
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(error, FirecrawlError::CrawlJobFailed(..)),
+            "{error:?}"
+        );
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                (
+                    CostOperation::Crawl,
+                    vec!["https://example.com".to_string()],
+                    2,
+                    false,
+                    true,
+                    Some("search-indexer".to_string())
+                ),
+                (
+                    CostOperation::Crawl,
+                    vec!["https://example.org".to_string()],
+                    7,
+                    false,
+                    false,
+                    Some("archiver".to_string())
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_crawl_with_reduced_limit() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{body_partial_json, method, path},
+        };
+
+        let server = MockServer::start().await;
+        for (limit, id) in [(100, "crawl-1"), (10, "crawl-2")] {
+            Mock::given(method("POST"))
+                .and(path("/v1/crawl"))
+                .and(body_partial_json(json!({
+                    "url": "https://example.com",
+                    "limit": limit,
+                    "excludePaths": ["/blog/*"]
+                })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "success": true,
+                    "id": id,
+                    "url": format!("https://api.firecrawl.dev/v1/crawl/{id}")
+                })))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+        let mut failed = crawl_status_json();
+        failed["status"] = json!("failed");
+        Mock::given(method("GET"))
+            .and(path("/v1/crawl/crawl-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(failed))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/crawl/crawl-2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(crawl_status_json()))
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let options = CrawlOptions::default()
+            .with_limit(100)
+            .with_exclude_paths(["/blog/*".to_string()]);
+        let error = app
+            .crawl(CrawlParams::new("https://example.com", Webhook::dummy()).with_options(options))
+            .await
+            .unwrap_err();
+        let FirecrawlError::CrawlJobFailed(_, previous) = error else {
+            panic!("{error:?}");
+        };
+
+        let status = app
+            .retry_crawl(&previous, "https://example.com", |options| {
+                options.limit = Some(10)
+            })
+            .await
+            .unwrap();
+        assert_eq!(status.status, CrawlStatusTypes::Completed);
+        assert_eq!(status.options.and_then(|o| o.limit), Some(10));
+
+        // Only jobs that didn't go through can be retried
+        let completed: CrawlStatus = serde_json::from_value(crawl_status_json()).unwrap();
+        let error = app
+            .retry_crawl(&completed, "https://example.com", |_| {})
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("Completed"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn test_crawl_urls_merges_overlapping_seeds() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{body_partial_json, method, path},
+        };
+
+        let page = |url: &str, markdown: Option<&str>| {
+            json!({
+                "markdown": markdown,
+                "html": "<p>page</p>",
+                "metadata": { "sourceURL": url, "statusCode": 200 }
+            })
+        };
+        let server = MockServer::start().await;
+        for (seed, id, data) in [
+            (
+                "https://docs.example.com",
+                "crawl-docs",
+                json!([
+                    page("https://docs.example.com/", Some("# Docs")),
+                    page("https://api.example.com/reference", None),
+                    page("https://docs.example.com/guide#install", None),
+                ]),
+            ),
+            (
+                "https://api.example.com",
+                "crawl-api",
+                json!([
+                    page("https://api.example.com", Some("# API")),
+                    page("https://api.example.com/reference/", Some("# Reference")),
+                    page("https://docs.example.com/guide", Some("# Guide")),
+                ]),
+            ),
+        ] {
+            Mock::given(method("POST"))
+                .and(path("/v1/crawl"))
+                .and(body_partial_json(json!({ "url": seed, "limit": 5 })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "success": true,
+                    "id": id,
+                    "url": format!("https://api.firecrawl.dev/v1/crawl/{id}")
+                })))
+                .expect(1)
+                .mount(&server)
+                .await;
+            let mut status = crawl_status_json();
+            status["creditsUsed"] = json!(3);
+            status["data"] = data;
+            Mock::given(method("GET"))
+                .and(path(format!("/v1/crawl/{id}")))
+                .respond_with(ResponseTemplate::new(200).set_body_json(status))
+                .mount(&server)
+                .await;
+        }
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let outcome = app
+            .crawl_urls(
+                vec![
+                    "https://docs.example.com".to_string(),
+                    "https://api.example.com".to_string(),
+                ],
+                CrawlOptions::default().with_limit(5),
+                Webhook::dummy(),
+            )
+            .await
+            .unwrap();
+
+        assert!(outcome.is_complete());
+        assert_eq!(outcome.credits_used, 6);
+        assert_eq!(
+            outcome
+                .crawls
+                .iter()
+                .map(|(seed, result)| (seed.as_str(), result.as_ref().unwrap().data.len()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("https://docs.example.com", 0),
+                ("https://api.example.com", 0)
+            ]
+        );
+
+        // Pages found from both seeds are kept once, at their first position, with the markdown
+        let pages: Vec<_> = outcome
+            .data
+            .iter()
+            .map(|document| {
+                (
+                    document.metadata.source_url.as_str(),
+                    document.markdown.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            pages,
+            vec![
+                ("https://docs.example.com/", Some("# Docs")),
+                ("https://api.example.com/reference/", Some("# Reference")),
+                ("https://docs.example.com/guide", Some("# Guide")),
+                ("https://api.example.com", Some("# API")),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_crawl_urls_keeps_going_after_a_failed_seed() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{body_partial_json, method, path},
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/crawl"))
+            .and(body_partial_json(
+                json!({ "url": "https://down.example.com" }),
+            ))
+            .respond_with(ResponseTemplate::new(500).set_body_json(json!({
+                "success": false,
+                "error": "Internal error"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/crawl"))
+            .and(body_partial_json(json!({ "url": "https://example.com" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "id": "crawl-1",
+                "url": "https://api.firecrawl.dev/v1/crawl/crawl-1"
+            })))
+            .mount(&server)
+            .await;
+        let mut status = crawl_status_json();
+        status["data"] = json!([{
+            "markdown": "# Example",
+            "metadata": { "sourceURL": "https://example.com", "statusCode": 200 }
+        }]);
+        Mock::given(method("GET"))
+            .and(path("/v1/crawl/crawl-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(status))
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let outcome = app
+            .crawl_urls(
+                vec![
+                    "https://down.example.com".to_string(),
+                    "https://example.com".to_string(),
+                ],
+                None,
+                Webhook::dummy(),
+            )
+            .await
+            .unwrap();
+
+        assert!(!outcome.is_complete());
+        assert!(outcome.crawls[0].1.is_err());
+        assert_eq!(outcome.credits_used, 2);
+        assert_eq!(outcome.data.len(), 1);
+
+        let error = app
+            .crawl_urls(Vec::new(), None, Webhook::dummy())
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("No URL"), "{error}");
+    }
+
+    #[test]
+    fn test_group_by_path_prefix() {
+        let mut status: CrawlStatus = serde_json::from_value(crawl_status_json()).unwrap();
+        status.data = [
+            "https://example.com/blog/first-post",
+            "https://example.com/docs/intro",
+            "https://example.com/",
+            "https://example.com/blog/second-post?page=2",
+            "https://example.com",
+            "https://example.com/docs",
+            "not a url",
+        ]
+        .into_iter()
+        .map(|url| {
+            serde_json::from_value(json!({ "metadata": { "sourceURL": url, "statusCode": 200 } }))
+                .unwrap()
+        })
+        .collect();
+
+        let groups = status.group_by_path_prefix();
+        let urls = |prefix: &str| -> Vec<&str> {
+            groups[prefix]
+                .iter()
+                .map(|document| document.metadata.source_url.as_str())
+                .collect()
+        };
+
+        assert_eq!(groups.len(), 4);
+        assert_eq!(
+            urls("/blog"),
+            vec![
+                "https://example.com/blog/first-post",
+                "https://example.com/blog/second-post?page=2"
+            ]
+        );
+        assert_eq!(
+            urls("/docs"),
+            vec!["https://example.com/docs/intro", "https://example.com/docs"]
+        );
+        assert_eq!(
+            urls("/"),
+            vec!["https://example.com/", "https://example.com"]
+        );
+        assert_eq!(urls(""), vec!["not a url"]);
+    }
+
+    #[test]
+    fn test_successful_documents() {
+        let mut status: CrawlStatus = serde_json::from_value(crawl_status_json()).unwrap();
+        status.data = serde_json::from_value(json!([
+            { "metadata": { "sourceURL": "https://example.com", "statusCode": 200 } },
+            { "metadata": { "sourceURL": "https://example.com/moved", "statusCode": 301 } },
+            { "metadata": { "sourceURL": "https://example.com/gone", "statusCode": 404 } },
+            { "metadata": { "sourceURL": "https://example.com/down", "statusCode": 503 } },
+            {
+                "metadata": {
+                    "sourceURL": "https://example.com/broken",
+                    "statusCode": 200,
+                    "error": "Failed to render the page"
+                }
+            }
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            status
+                .successful_documents()
+                .map(|document| document.metadata.source_url.as_str())
+                .collect::<Vec<_>>(),
+            vec!["https://example.com", "https://example.com/moved"]
+        );
+    }
+
+    #[test]
+    fn test_link_graph() {
+        let mut status: CrawlStatus = serde_json::from_value(crawl_status_json()).unwrap();
+        status.data = serde_json::from_value(json!([
+            {
+                "links": ["https://example.com/a", "https://example.com/b"],
+                "metadata": { "sourceURL": "https://example.com", "statusCode": 200 }
+            },
+            {
+                "links": ["https://example.com"],
+                "metadata": { "sourceURL": "https://example.com/a", "statusCode": 200 }
+            },
+            {
+                "markdown": "# B",
+                "metadata": { "sourceURL": "https://example.com/b", "statusCode": 200 }
+            }
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            status
+                .data
+                .iter()
+                .map(Document::link_count)
+                .collect::<Vec<_>>(),
+            vec![2, 1, 0]
+        );
+        assert_eq!(
+            status.link_graph(),
+            vec![
+                (
+                    "https://example.com".to_string(),
+                    vec![
+                        "https://example.com/a".to_string(),
+                        "https://example.com/b".to_string()
+                    ]
+                ),
+                (
+                    "https://example.com/a".to_string(),
+                    vec!["https://example.com".to_string()]
+                ),
+                ("https://example.com/b".to_string(), vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_status_is_terminal() {
+        assert!(!CrawlStatusTypes::Scraping.is_terminal());
+        assert!(CrawlStatusTypes::Completed.is_terminal());
+        assert!(CrawlStatusTypes::Failed.is_terminal());
+        assert!(CrawlStatusTypes::Cancelled.is_terminal());
+        assert!(!CrawlStatusTypes::Unknown("paused".to_string()).is_terminal());
+        assert_eq!(CrawlStatusTypes::default(), CrawlStatusTypes::Scraping);
+    }
+
+    #[tokio::test]
+    async fn test_crawl_keeps_polling_unknown_status() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/crawl"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "id": "crawl-paused",
+                "url": "https://api.firecrawl.dev/v1/crawl/crawl-paused"
+            })))
+            .mount(&server)
+            .await;
+        // A status introduced by a newer version of the API
+        let mut paused = crawl_status_json();
+        paused["status"] = json!("paused");
+        paused["completed"] = json!(1);
+        Mock::given(method("GET"))
+            .and(path("/v1/crawl/crawl-paused"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(paused.clone()))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/crawl/crawl-paused"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(crawl_status_json()))
+            .mount(&server)
+            .await;
+
+        let status: CrawlStatus = serde_json::from_value(paused).unwrap();
+        assert_eq!(
+            status.status,
+            CrawlStatusTypes::Unknown("paused".to_string())
+        );
+
+        let warnings = std::sync::Arc::new(crate::observer::WarningRecorder::default());
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>)
+            .unwrap()
+            .with_hook(warnings.clone());
+        let status = app
+            .crawl(CrawlParams::new("https://example.com", Webhook::dummy()).with_poll_interval(10))
+            .await
+            .unwrap();
+        assert_eq!(status.status, CrawlStatusTypes::Completed);
+        assert_eq!(status.completed, 2);
+        // Once for the job, not once per poll
+        assert_eq!(
+            warnings.take(),
+            vec![r#"crawl job crawl-paused has an unknown status "paused", polling again"#]
+        );
+    }
+
+    #[cfg(feature = "compression")]
+    #[tokio::test]
+    async fn test_crawl_status_gzip_encoded() {
+        use flate2::{Compression, write::GzEncoder};
+        use std::io::Write;
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{header_regex, method, path},
+        };
+
+        let mut status = crawl_status_json();
+        let documents: Vec<_> = (0..200)
+            .map(|i| {
+                json!({
+                    "markdown": format!("# Page {i}\n\n{}", "Lorem ipsum dolor sit amet. ".repeat(50)),
+                    "metadata": {
+                        "sourceURL": format!("https://example.com/{i}"),
+                        "statusCode": 200
+                    }
+                })
+            })
+            .collect();
+        status["total"] = json!(200);
+        status["completed"] = json!(200);
+        status["data"] = json!(documents);
+        let body = serde_json::to_vec(&status).unwrap();
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&body).unwrap();
+        let compressed = encoder.finish().unwrap();
+        assert!(compressed.len() * 10 < body.len());
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/crawl/crawl-123"))
+            .and(header_regex("accept-encoding", "gzip"))
+            .and(header_regex("accept-encoding", "br"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-encoding", "gzip")
+                    .set_body_raw(compressed, "application/json"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let status = app.check_crawl_status("crawl-123").await.unwrap();
+        assert_eq!(status.status, CrawlStatusTypes::Completed);
+        assert_eq!(status.data.len(), 200);
+        assert_eq!(
+            status.data[199].metadata.source_url,
+            "https://example.com/199"
+        );
+        assert!(
+            status.data[0]
+                .markdown
+                .as_deref()
+                .unwrap()
+                .starts_with("# Page 0\n\nLorem ipsum")
+        );
+    }
+
+    #[test]
+    fn test_crawl_request_body_json_format() {
+        use crate::scrape::JsonOptions;
+
+        let scrape_options = ScrapeOptions::default()
+            .with_formats(
+                [CrawlScrapeFormats::Markdown, CrawlScrapeFormats::JSON].map(ScrapeFormats::from),
+            )
+            .with_json_options(JsonOptions {
+                prompt: Some("Extract the product name and price".to_string()),
+                ..Default::default()
+            });
+        let body = CrawlRequestBody {
+            url: "https://example.com".to_string(),
+            options: CrawlOptions {
+                scrape_options: Some(scrape_options),
+                limit: Some(10),
+                ..Default::default()
+            },
+            webhook: Webhook::dummy(),
+        };
+
+        let json = serde_json::to_value(&body).unwrap();
+        assert_eq!(
+            json["scrapeOptions"],
+            json!({
+                "formats": ["markdown", "json"],
+                "jsonOptions": { "prompt": "Extract the product name and price" }
+            })
+        );
+        assert_eq!(json["limit"], json!(10));
+    }
+
+    #[test]
+    fn test_progress_fraction() {
+        let mut status: CrawlStatus = serde_json::from_value(crawl_status_json()).unwrap();
+        assert_eq!(status.progress_fraction(), Some(1.0));
+
+        status.completed = 1;
+        status.total = 4;
+        assert_eq!(status.progress_fraction(), Some(0.25));
+
+        status.completed = 0;
+        status.total = 0;
+        assert_eq!(status.progress_fraction(), None);
     }
 }
@@ -1,9 +1,19 @@
+use std::collections::HashSet;
+
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use thiserror::Error;
 
 #[cfg(feature = "mcp-tool")]
 use schemars::JsonSchema;
 
+#[cfg(feature = "lang-detect")]
+use crate::language::{LanguageSource, LanguageTag, detect_language};
+#[cfg(feature = "sanitize")]
+use crate::sanitize::{SanitizeOptions, sanitize_markdown};
+#[cfg(feature = "scrape")]
+use crate::scrape::ScrapeFormats;
+
 #[serde_with::skip_serializing_none]
 #[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "mcp-tool", derive(JsonSchema))]
@@ -28,6 +38,19 @@ pub struct DocumentMetadata {
     #[serde(deserialize_with = "deserialize_string_or_vec")]
     pub language: Option<String>,
 
+    // Response headers of the page, used to detect unchanged pages on later scrapes
+    #[serde(default, alias = "content-type", alias = "Content-Type")]
+    #[serde(deserialize_with = "deserialize_header_value")]
+    pub content_type: Option<String>,
+
+    #[serde(default, alias = "last-modified", alias = "Last-Modified")]
+    #[serde(deserialize_with = "deserialize_header_value")]
+    pub last_modified: Option<String>,
+
+    #[serde(default, alias = "eTag", alias = "ETag")]
+    #[serde(deserialize_with = "deserialize_header_value")]
+    pub etag: Option<String>,
+
     // All other metadata fields are captured here
     #[serde(flatten)]
     pub additional_fields: std::collections::HashMap<String, Value>,
@@ -90,6 +113,33 @@ where
     deserializer.deserialize_any(StringOrVec)
 }
 
+// Like `deserialize_string_or_vec`, but trims the value and treats an empty header as absent
+fn deserialize_header_value<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = deserialize_string_or_vec(deserializer)?;
+    Ok(value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty()))
+}
+
+/// Cache validators of a scraped page, to store and send back on the next scrape so unchanged
+/// pages can be skipped.
+#[serde_with::skip_serializing_none]
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheValidators {
+    /// The `Content-Type` of the page
+    pub content_type: Option<String>,
+
+    /// The `Last-Modified` date of the page, as sent by the server
+    pub last_modified: Option<String>,
+
+    /// The `ETag` of the page, as sent by the server
+    pub etag: Option<String>,
+}
+
 /// Represents a scrape result from an action
 #[serde_with::skip_serializing_none]
 #[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
@@ -113,6 +163,14 @@ pub struct JavaScriptReturnValue {
     pub value: Value,
 }
 
+impl JavaScriptReturnValue {
+    /// Deserializes the returned value into `T`, e.g. a `Vec` of structs for a script returning
+    /// an array of objects.
+    pub fn as_typed<T: serde::de::DeserializeOwned>(&self) -> Result<T, crate::FirecrawlError> {
+        T::deserialize(&self.value).map_err(crate::FirecrawlError::ResponseParseError)
+    }
+}
+
 /// Represents the results of actions performed during scraping
 #[serde_with::skip_serializing_none]
 #[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
@@ -167,4 +225,592 @@ pub struct Document {
     /// Can be present if `ScrapeFormats::Extract` is present in `ScrapeOptions.formats`.
     /// The warning message will contain any errors encountered during the extraction.
     pub warning: Option<String>,
+
+    /// Id of the scrape request, to share with Firecrawl support. Filled in by
+    /// `FirecrawlApp::scrape_url` from the `x-request-id` response header.
+    #[serde(skip)]
+    pub request_id: Option<String>,
+
+    /// Whether `html` was truncated to `FirecrawlApp::with_max_html_size`.
+    #[serde(skip)]
+    pub html_truncated: bool,
+
+    /// Whether `raw_html` was truncated to `FirecrawlApp::with_max_html_size`.
+    #[serde(skip)]
+    pub raw_html_truncated: bool,
+}
+
+/// The cleaned and raw HTML of a document, returned by [`Document::html_variants`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HtmlVariants<'a> {
+    /// `Document.html`, with non-content tags removed
+    pub cleaned: Option<HtmlVariant<'a>>,
+
+    /// `Document.raw_html`, the original HTML of the page
+    pub raw: Option<HtmlVariant<'a>>,
+}
+
+/// One of the [`HtmlVariants`] of a document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HtmlVariant<'a> {
+    pub html: &'a str,
+
+    /// Whether the HTML was truncated to `FirecrawlApp::with_max_html_size`
+    pub truncated: bool,
+}
+
+/// Truncates `html` to at most `max_bytes` at a character boundary, returning whether it was cut.
+#[cfg(feature = "scrape")]
+fn truncate_html(html: &mut Option<String>, max_bytes: usize) -> bool {
+    let Some(html) = html.as_mut().filter(|html| html.len() > max_bytes) else {
+        return false;
+    };
+    let end = (0..=max_bytes)
+        .rev()
+        .find(|&i| html.is_char_boundary(i))
+        .unwrap_or(0);
+    html.truncate(end);
+    true
+}
+
+/// Documents of a status page that failed to deserialize: their index in the results and the
+/// error.
+#[cfg(any(feature = "batch", feature = "crawl"))]
+pub(crate) type SkippedDocuments = Vec<(usize, String)>;
+
+/// Takes the documents out of the `data` array of a crawl or batch scrape status `page`, leaving
+/// it empty, then deserializes `page` as `T`.
+///
+/// Documents are deserialized one at a time, so a malformed one, e.g. sent by an outdated
+/// worker, is returned in the skipped documents with its index and error instead of failing the
+/// page.
+#[cfg(any(feature = "batch", feature = "crawl"))]
+pub(crate) fn deserialize_status_page<T: serde::de::DeserializeOwned>(
+    mut page: Value,
+) -> Result<(T, Vec<Document>, SkippedDocuments), serde_json::Error> {
+    let entries = match page.get_mut("data") {
+        Some(Value::Array(entries)) => std::mem::take(entries),
+        _ => Vec::new(),
+    };
+
+    let mut documents = Vec::with_capacity(entries.len());
+    let mut skipped = Vec::new();
+    for (i, entry) in entries.into_iter().enumerate() {
+        match serde_json::from_value(entry) {
+            Ok(document) => documents.push(document),
+            Err(e) => skipped.push((i, e.to_string())),
+        }
+    }
+
+    Ok((serde_json::from_value(page)?, documents, skipped))
+}
+
+/// Appends the documents of a status page to the ones collected so far, shifting the indices of
+/// its skipped documents to count from the first page.
+#[cfg(any(feature = "batch", feature = "crawl"))]
+pub(crate) fn append_status_page(
+    (data, skipped): (&mut Vec<Document>, &mut SkippedDocuments),
+    (mut page_data, page_skipped): (Vec<Document>, SkippedDocuments),
+) {
+    let offset = data.len() + skipped.len();
+    skipped.extend(page_skipped.into_iter().map(|(i, e)| (offset + i, e)));
+    data.append(&mut page_data);
+}
+
+/// Number of consecutive words in a shingle compared by [`dedupe_documents`].
+const SHINGLE_SIZE: usize = 3;
+
+/// Drops the documents whose markdown is a near-duplicate of an earlier one, e.g. the pages of a
+/// paginated listing, keeping the first of each group in order.
+///
+/// Two documents are near-duplicates when the Jaccard similarity of the sets of 3-word shingles
+/// of their markdown, after case folding, is at least `similarity_threshold`, from `0.0` to
+/// `1.0`. Documents without markdown are always kept. Each document is compared with every kept
+/// one, so this is meant for batches rather than whole crawls of large sites.
+pub fn dedupe_documents(docs: Vec<Document>, similarity_threshold: f64) -> Vec<Document> {
+    let mut kept: Vec<(Document, HashSet<String>)> = Vec::with_capacity(docs.len());
+    for document in docs {
+        let shingles = shingles(document.markdown.as_deref().unwrap_or_default());
+        let is_duplicate = !shingles.is_empty()
+            && kept.iter().any(|(_, other)| {
+                !other.is_empty() && jaccard_similarity(&shingles, other) >= similarity_threshold
+            });
+        if !is_duplicate {
+            kept.push((document, shingles));
+        }
+    }
+    kept.into_iter().map(|(document, _)| document).collect()
+}
+
+/// The set of `SHINGLE_SIZE` consecutive lowercase words of `text`, or all of its words when it
+/// has fewer.
+fn shingles(text: &str) -> HashSet<String> {
+    let words: Vec<String> = text.split_whitespace().map(str::to_lowercase).collect();
+    if words.is_empty() {
+        return HashSet::new();
+    }
+    words
+        .windows(SHINGLE_SIZE.min(words.len()))
+        .map(|window| window.join(" "))
+        .collect()
+}
+
+fn jaccard_similarity(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let intersection = a.intersection(b).count();
+    let union = a.len() + b.len() - intersection;
+    intersection as f64 / union as f64
+}
+
+/// Why [`Document::extraction_result`] found no usable extracted data.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ExtractionError {
+    /// Data was extracted, but the API warned about problems, so it may be incomplete. The data
+    /// is still available in `Document.json` or `Document.extract`.
+    #[error("Extraction incomplete: {0}")]
+    Partial(String),
+
+    /// Nothing was extracted, with the API's warning if it sent one.
+    #[error(
+        "Nothing was extracted{}",
+        .0.as_ref().map(|w| format!(": {w}")).unwrap_or_default()
+    )]
+    Missing(Option<String>),
+}
+
+impl ExtractionError {
+    /// The warning sent by the API, if any.
+    pub fn warning(&self) -> Option<&str> {
+        match self {
+            ExtractionError::Partial(warning) => Some(warning),
+            ExtractionError::Missing(warning) => warning.as_deref(),
+        }
+    }
+}
+
+impl Document {
+    /// Returns a stable hash of the document's content, suitable for change detection across runs.
+    ///
+    /// The markdown is hashed when present, falling back to the HTML and then the raw HTML.
+    /// Line endings and runs of whitespace are normalized first, and metadata is ignored, so
+    /// volatile fields such as scrape timestamps do not affect the result.
+    pub fn content_hash(&self) -> u64 {
+        // FNV-1a, so the value does not change between Rust versions like `DefaultHasher` may.
+        const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+        const PRIME: u64 = 0x100000001b3;
+
+        let content = self
+            .markdown
+            .as_deref()
+            .or(self.html.as_deref())
+            .or(self.raw_html.as_deref())
+            .unwrap_or_default();
+
+        let mut hash = OFFSET_BASIS;
+        for (i, word) in content.split_whitespace().enumerate() {
+            if i > 0 {
+                hash ^= u64::from(b' ');
+                hash = hash.wrapping_mul(PRIME);
+            }
+            for byte in word.bytes() {
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(PRIME);
+            }
+        }
+        hash
+    }
+
+    /// Returns the formats that actually came back with content, in `ScrapeFormats` declaration order.
+    ///
+    /// Empty strings, empty link lists and `null` JSON count as absent. A screenshot is always
+    /// reported as `ScrapeFormats::Screenshot`, since the document can't tell full-page ones apart.
+    #[cfg(feature = "scrape")]
+    pub fn present_formats(&self) -> Vec<ScrapeFormats> {
+        let non_empty = |s: &Option<String>| s.as_ref().is_some_and(|s| !s.is_empty());
+
+        [
+            (non_empty(&self.markdown), ScrapeFormats::Markdown),
+            (non_empty(&self.html), ScrapeFormats::HTML),
+            (non_empty(&self.raw_html), ScrapeFormats::RawHTML),
+            (
+                self.links.as_ref().is_some_and(|l| !l.is_empty()),
+                ScrapeFormats::Links,
+            ),
+            (non_empty(&self.screenshot), ScrapeFormats::Screenshot),
+            (
+                self.json.as_ref().is_some_and(|j| !j.is_null()),
+                ScrapeFormats::JSON,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(present, format)| present.then_some(format))
+        .collect()
+    }
+
+    /// Number of links found on the page, `0` unless `ScrapeFormats::Links` was requested.
+    pub fn link_count(&self) -> usize {
+        self.links.as_ref().map_or(0, Vec::len)
+    }
+
+    /// Returns the extracted data, from `json` or else `extract`, if it came back without a
+    /// warning.
+    ///
+    /// Any warning fails the extraction, since the API only sends one to report problems. `null`
+    /// data counts as absent.
+    pub fn extraction_result(&self) -> Result<&Value, ExtractionError> {
+        let data = [&self.json, &self.extract]
+            .into_iter()
+            .find_map(|data| data.as_ref().filter(|data| !data.is_null()));
+        let warning = self.warning.clone().filter(|w| !w.trim().is_empty());
+
+        match (data, warning) {
+            (Some(data), None) => Ok(data),
+            (Some(_), Some(warning)) => Err(ExtractionError::Partial(warning)),
+            (None, warning) => Err(ExtractionError::Missing(warning)),
+        }
+    }
+
+    /// Returns the HTML of the page with non-content tags removed and the original one, present
+    /// if `ScrapeFormats::HTML` and `ScrapeFormats::RawHTML` are in `ScrapeOptions.formats`.
+    pub fn html_variants(&self) -> HtmlVariants<'_> {
+        HtmlVariants {
+            cleaned: self.html.as_deref().map(|html| HtmlVariant {
+                html,
+                truncated: self.html_truncated,
+            }),
+            raw: self.raw_html.as_deref().map(|html| HtmlVariant {
+                html,
+                truncated: self.raw_html_truncated,
+            }),
+        }
+    }
+
+    /// Truncates `html` and `raw_html` to at most `max_bytes` each.
+    #[cfg(feature = "scrape")]
+    pub(crate) fn truncate_html(&mut self, max_bytes: usize) {
+        self.html_truncated |= truncate_html(&mut self.html, max_bytes);
+        self.raw_html_truncated |= truncate_html(&mut self.raw_html, max_bytes);
+    }
+
+    /// Returns the cache validators of the page, see `ScrapeOptions::with_conditional_headers`.
+    pub fn cache_validators(&self) -> CacheValidators {
+        CacheValidators {
+            content_type: self.metadata.content_type.clone(),
+            last_modified: self.metadata.last_modified.clone(),
+            etag: self.metadata.etag.clone(),
+        }
+    }
+
+    /// Whether the API served the page from its cache instead of fetching it again, as reported
+    /// by the `cacheState` metadata field.
+    pub fn is_cache_hit(&self) -> bool {
+        self.metadata
+            .additional_fields
+            .get("cacheState")
+            .and_then(Value::as_str)
+            == Some("hit")
+    }
+
+    /// Whether the page loaded without an error and with a `2xx` or `3xx` status code, e.g. to
+    /// leave out the error pages of a crawl.
+    pub fn was_successful(&self) -> bool {
+        self.metadata.error.is_none() && (200..400).contains(&self.metadata.status_code)
+    }
+
+    /// Returns the markdown with hidden prompt-injection vectors removed, see [`sanitize_markdown`].
+    #[cfg(feature = "sanitize")]
+    pub fn sanitized_markdown(&self, options: SanitizeOptions) -> Option<String> {
+        self.markdown
+            .as_deref()
+            .map(|markdown| sanitize_markdown(markdown, options))
+    }
+
+    /// Returns the language of the page: the one declared in `metadata.language`, or else the one
+    /// detected from the markdown with at least `min_confidence`, see [`detect_language`].
+    #[cfg(feature = "lang-detect")]
+    pub fn detect_language(&self, min_confidence: f64) -> Option<LanguageTag> {
+        if let Some(code) = self
+            .metadata
+            .language
+            .as_deref()
+            .map(str::trim)
+            .filter(|code| !code.is_empty())
+        {
+            return Some(LanguageTag {
+                code: code.to_string(),
+                source: LanguageSource::Metadata,
+            });
+        }
+
+        detect_language(self.markdown.as_deref()?, min_confidence)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn document(markdown: &str, source_url: &str) -> Document {
+        Document {
+            markdown: Some(markdown.to_string()),
+            metadata: DocumentMetadata {
+                source_url: source_url.to_string(),
+                status_code: 200,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_javascript_return_as_typed() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Product {
+            name: String,
+            price: f64,
+        }
+
+        let document: Document = serde_json::from_value(json!({
+            "metadata": { "sourceURL": "https://example.com", "statusCode": 200 },
+            "actions": {
+                "javascriptReturns": [{
+                    "type": "object",
+                    "value": [
+                        { "name": "Widget", "price": 9.99 },
+                        { "name": "Gadget", "price": 24.5 }
+                    ]
+                }]
+            }
+        }))
+        .unwrap();
+        let returns = document.actions.unwrap().javascript_returns.unwrap();
+
+        let products: Vec<Product> = returns[0].as_typed().unwrap();
+        assert_eq!(
+            products,
+            vec![
+                Product {
+                    name: "Widget".to_string(),
+                    price: 9.99,
+                },
+                Product {
+                    name: "Gadget".to_string(),
+                    price: 24.5,
+                },
+            ]
+        );
+        assert!(matches!(
+            returns[0].as_typed::<Vec<String>>(),
+            Err(crate::FirecrawlError::ResponseParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_content_hash_identical_content() {
+        let a = document("# Title\n\nSome content", "https://example.com/a");
+        let mut b = document("# Title\r\n\r\nSome   content\n", "https://example.com/b");
+        b.metadata
+            .additional_fields
+            .insert("scrapedAt".to_string(), Value::from("2025-01-01T00:00:00Z"));
+
+        assert_eq!(a.content_hash(), b.content_hash());
+    }
+
+    #[test]
+    fn test_dedupe_documents() {
+        let listing = |page: u32| {
+            document(
+                &format!(
+                    "# Products\n\nWidget, a small widget for everyday use. Gadget, a handy \
+                     gadget for the kitchen. Gizmo, a gizmo for tinkerers.\n\nPage {page}"
+                ),
+                &format!("https://example.com/products?page={page}"),
+            )
+        };
+        let about = document(
+            "# About us\n\nWe have been building widgets since 1999.",
+            "https://example.com/about",
+        );
+        let mut screenshot_only = document("", "https://example.com/screenshot");
+        screenshot_only.markdown = None;
+
+        let docs = vec![
+            listing(1),
+            about.clone(),
+            listing(2),
+            screenshot_only.clone(),
+            screenshot_only,
+        ];
+        let kept: Vec<_> = dedupe_documents(docs.clone(), 0.8)
+            .into_iter()
+            .map(|doc| doc.metadata.source_url)
+            .collect();
+        assert_eq!(
+            kept,
+            vec![
+                "https://example.com/products?page=1",
+                "https://example.com/about",
+                "https://example.com/screenshot",
+                "https://example.com/screenshot",
+            ]
+        );
+
+        // Only exact duplicates are dropped at 1.0
+        assert_eq!(dedupe_documents(docs.clone(), 1.0).len(), docs.len());
+        assert_eq!(dedupe_documents(vec![about.clone(), about], 1.0).len(), 1);
+    }
+
+    #[test]
+    fn test_extraction_result() {
+        let mut doc = document("# Title", "https://example.com");
+        doc.json = Some(json!({ "price": 12 }));
+        assert_eq!(doc.extraction_result(), Ok(&json!({ "price": 12 })));
+
+        // Older API versions return `extract`
+        doc.json = None;
+        doc.extract = Some(json!({ "price": 12 }));
+        assert_eq!(doc.extraction_result(), Ok(&json!({ "price": 12 })));
+    }
+
+    #[test]
+    fn test_extraction_result_partial_with_warning() {
+        let mut doc = document("# Title", "https://example.com");
+        doc.json = Some(json!({ "price": 12, "currency": null }));
+        doc.warning = Some("LLM extraction did not match the schema".to_string());
+
+        let error = doc.extraction_result().unwrap_err();
+        assert_eq!(
+            error,
+            ExtractionError::Partial("LLM extraction did not match the schema".to_string())
+        );
+        assert_eq!(
+            error.to_string(),
+            "Extraction incomplete: LLM extraction did not match the schema"
+        );
+        // The partial data stays available
+        assert_eq!(doc.json.unwrap()["price"], 12);
+    }
+
+    #[test]
+    fn test_extraction_result_absent() {
+        let mut doc = document("# Title", "https://example.com");
+        assert_eq!(doc.extraction_result(), Err(ExtractionError::Missing(None)));
+
+        doc.json = Some(Value::Null);
+        doc.warning = Some("Failed to extract".to_string());
+        let error = doc.extraction_result().unwrap_err();
+        assert_eq!(error.warning(), Some("Failed to extract"));
+        assert_eq!(
+            error.to_string(),
+            "Nothing was extracted: Failed to extract"
+        );
+    }
+
+    #[test]
+    fn test_content_hash_differing_content() {
+        let a = document("# Title\n\nSome content", "https://example.com");
+        let b = document("# Title\n\nOther content", "https://example.com");
+
+        assert_ne!(a.content_hash(), b.content_hash());
+    }
+
+    #[cfg(feature = "scrape")]
+    #[test]
+    fn test_present_formats() {
+        let mut doc = document("# Title", "https://example.com");
+        doc.links = Some(vec!["https://example.com/about".to_string()]);
+        doc.html = Some(String::new());
+        doc.screenshot = None;
+
+        assert_eq!(
+            doc.present_formats(),
+            vec![ScrapeFormats::Markdown, ScrapeFormats::Links]
+        );
+    }
+
+    #[test]
+    fn test_cache_validators_from_metadata() {
+        let doc: Document = serde_json::from_value(json!({
+            "markdown": "# Example",
+            "metadata": {
+                "sourceURL": "https://example.com",
+                "statusCode": 200,
+                "contentType": "text/html; charset=utf-8",
+                "last-modified": ["Wed, 21 Oct 2015 07:28:00 GMT"],
+                "ETag": " W/\"33a64df5\" "
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            doc.cache_validators(),
+            CacheValidators {
+                content_type: Some("text/html; charset=utf-8".to_string()),
+                last_modified: Some("Wed, 21 Oct 2015 07:28:00 GMT".to_string()),
+                etag: Some("W/\"33a64df5\"".to_string()),
+            }
+        );
+        assert!(doc.metadata.additional_fields.is_empty());
+    }
+
+    #[test]
+    fn test_cache_validators_without_headers() {
+        let doc: Document = serde_json::from_value(json!({
+            "markdown": "# Example",
+            "metadata": {
+                "sourceURL": "https://example.com",
+                "statusCode": 200,
+                "etag": "",
+                "lastModified": null
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(doc.cache_validators(), CacheValidators::default());
+    }
+
+    #[cfg(feature = "scrape")]
+    #[test]
+    fn test_html_variants_with_size_cap() {
+        let mut doc = Document {
+            html: Some("<p>Café au lait</p>".to_string()),
+            raw_html: Some("<html><body><p>Café au lait</p></body></html>".to_string()),
+            ..document("Café au lait", "https://example.com")
+        };
+        assert_eq!(
+            doc.html_variants().cleaned,
+            Some(HtmlVariant {
+                html: "<p>Café au lait</p>",
+                truncated: false
+            })
+        );
+
+        // The cap falls inside "é", which is kept out rather than split
+        doc.truncate_html(7);
+        let variants = doc.html_variants();
+        assert_eq!(
+            variants.cleaned,
+            Some(HtmlVariant {
+                html: "<p>Caf",
+                truncated: true
+            })
+        );
+        assert_eq!(
+            variants.raw,
+            Some(HtmlVariant {
+                html: "<html><",
+                truncated: true
+            })
+        );
+
+        // Variants within the cap are left alone
+        let mut doc = Document {
+            html: Some("<p>Hi</p>".to_string()),
+            ..Default::default()
+        };
+        doc.truncate_html(100);
+        let variants = doc.html_variants();
+        assert!(!variants.cleaned.unwrap().truncated);
+        assert_eq!(variants.raw, None);
+    }
 }
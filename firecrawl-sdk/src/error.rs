@@ -1,18 +1,156 @@
-use std::fmt::Display;
+use std::{fmt::Display, time::Duration};
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use thiserror::Error;
 
+use crate::RateLimitInfo;
+#[cfg(feature = "batch")]
+use crate::batch_scrape::BatchScrapeStatus;
+#[cfg(feature = "crawl")]
 use crate::crawl::CrawlStatus;
+#[cfg(feature = "extract")]
+use crate::extract::ExtractStatus;
+#[cfg(feature = "llmstxt")]
+use crate::llmstxt::LlmsTxtStatus;
 
+/// Error reported by the API.
+///
+/// Non-exhaustive, as the SDK fills in more of the response over time.
 #[derive(Debug, Deserialize, Serialize, Clone)]
+#[non_exhaustive]
 pub struct FirecrawlAPIError {
     /// Error message
     pub error: String,
 
-    /// Additional details of this error. Schema depends on the error itself.
+    /// Additional details of this error, kept as sent by the API. Schema depends on the error
+    /// itself, validation errors carry an array of issues with a `path` and a `message` each.
     pub details: Option<Value>,
+
+    /// HTTP status code of the response carrying this error, if it came from the API.
+    #[serde(skip)]
+    pub status_code: Option<u16>,
+
+    /// Rate limit state from the response headers, if the API reported any.
+    ///
+    /// Boxed to keep `FirecrawlError` small.
+    #[serde(skip)]
+    pub rate_limit: Option<Box<RateLimitInfo>>,
+
+    /// Id of the failed request, to share with Firecrawl support. Taken from the `x-request-id`
+    /// header, or the `requestId` field of the body.
+    #[serde(default, rename = "requestId", skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// Well-known categories of Firecrawl API failures.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiErrorKind {
+    /// The account has run out of credits.
+    InsufficientCredits,
+
+    /// Too many requests were made in the current window.
+    RateLimited,
+
+    /// The API key is missing, malformed or revoked.
+    InvalidApiKey,
+
+    /// The target URL is not allowed to be scraped.
+    UrlBlocked,
+
+    /// The request timed out on the server side.
+    Timeout,
+
+    /// Any other failure, carrying the original error message.
+    Unknown(String),
+}
+
+/// Kind of an asynchronous job, see `FirecrawlError::JobNotFound`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobKind {
+    /// A crawl started with `FirecrawlApp::crawl_url` or `crawl_url_async`.
+    Crawl,
+
+    /// A batch scrape started with `FirecrawlApp::batch_scrape_urls`.
+    BatchScrape,
+}
+
+impl Display for JobKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JobKind::Crawl => write!(f, "crawl"),
+            JobKind::BatchScrape => write!(f, "batch scrape"),
+        }
+    }
+}
+
+/// Status of a job whose monitoring stopped early, see `FirecrawlError::OperationCancelled` and
+/// `FirecrawlError::PollTimeout`.
+///
+/// Holds the last status received, with the documents collected until then.
+#[cfg(any(feature = "batch", feature = "crawl"))]
+#[derive(Debug)]
+pub enum PartialStatus {
+    #[cfg(feature = "crawl")]
+    Crawl(Box<CrawlStatus>),
+    #[cfg(feature = "batch")]
+    BatchScrape(Box<BatchScrapeStatus>),
+}
+
+#[cfg(any(feature = "batch", feature = "crawl"))]
+impl PartialStatus {
+    /// Kind of the job whose monitoring stopped.
+    pub fn kind(&self) -> JobKind {
+        match self {
+            #[cfg(feature = "crawl")]
+            PartialStatus::Crawl(_) => JobKind::Crawl,
+            #[cfg(feature = "batch")]
+            PartialStatus::BatchScrape(_) => JobKind::BatchScrape,
+        }
+    }
+}
+
+impl FirecrawlAPIError {
+    /// Classifies this error based on the HTTP status code and the error message.
+    ///
+    /// | Status | Message contains                                  | Kind                  |
+    /// |--------|---------------------------------------------------|-----------------------|
+    /// | 401    | `unauthorized`, `invalid token`, `invalid api key` | `InvalidApiKey`       |
+    /// | 402    | `insufficient credits`                            | `InsufficientCredits` |
+    /// | 403    | `no longer supported`, `does not support`         | `UrlBlocked`          |
+    /// | 408    | `timed out`                                       | `Timeout`             |
+    /// | 504    | `timed out`                                       | `Timeout`             |
+    /// | 429    | `rate limit`                                      | `RateLimited`         |
+    ///
+    /// Either the status code or the message is sufficient on its own.
+    pub fn kind(&self) -> ApiErrorKind {
+        let message = self.error.to_lowercase();
+        let contains_any = |needles: &[&str]| needles.iter().any(|n| message.contains(n));
+
+        match self.status_code {
+            Some(402) => return ApiErrorKind::InsufficientCredits,
+            Some(429) => return ApiErrorKind::RateLimited,
+            Some(401) => return ApiErrorKind::InvalidApiKey,
+            Some(408 | 504) => return ApiErrorKind::Timeout,
+            _ => {}
+        }
+
+        if contains_any(&["insufficient credits"]) {
+            ApiErrorKind::InsufficientCredits
+        } else if contains_any(&["rate limit"]) {
+            ApiErrorKind::RateLimited
+        } else if contains_any(&["unauthorized", "invalid token", "invalid api key"]) {
+            ApiErrorKind::InvalidApiKey
+        } else if contains_any(&["no longer supported", "does not support"]) {
+            ApiErrorKind::UrlBlocked
+        } else if contains_any(&["timed out"]) {
+            ApiErrorKind::Timeout
+        } else if self.status_code == Some(403) {
+            ApiErrorKind::UrlBlocked
+        } else {
+            ApiErrorKind::Unknown(self.error.clone())
+        }
+    }
 }
 
 impl Display for FirecrawlAPIError {
@@ -25,7 +163,12 @@ impl Display for FirecrawlAPIError {
     }
 }
 
+/// Errors of the SDK.
+///
+/// Some variants only exist with the feature of their endpoint, e.g. `CrawlJobFailed` with
+/// `crawl`, so matches must have a wildcard arm to build whatever features other crates enable.
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum FirecrawlError {
     #[error("{0} failed: HTTP error {1}: {2}")]
     HttpRequestFailed(String, u16, String),
@@ -37,10 +180,473 @@ pub enum FirecrawlError {
     ResponseParseError(serde_json::Error),
     #[error("{0} failed: {1}")]
     APIError(String, FirecrawlAPIError),
+    /// The status is boxed to keep `FirecrawlError` small.
+    #[cfg(feature = "crawl")]
     #[error("Crawl job failed: {0}")]
-    CrawlJobFailed(String, CrawlStatus),
+    CrawlJobFailed(String, Box<CrawlStatus>),
+    /// The status is boxed to keep `FirecrawlError` small.
+    #[cfg(feature = "crawl")]
     #[error("Crawl job cancelled")]
-    CrawlJobCancelled(CrawlStatus),
-    #[error("Batch scrape job failed: {0}")]
-    BatchScrapeJobFailed(String),
+    CrawlJobCancelled(Box<CrawlStatus>),
+    #[error("Batch scrape job {1} failed: {0}")]
+    BatchScrapeJobFailed(String, String),
+    /// Monitoring of a job was stopped through its `CancellationToken`, see
+    /// `FirecrawlApp::crawl_url_with_cancellation`. The job itself keeps running on the API.
+    #[cfg(any(feature = "batch", feature = "crawl"))]
+    #[error("Monitoring of the {} job was cancelled", .0.kind())]
+    OperationCancelled(PartialStatus),
+    /// Monitoring of a job gave up after the time set with `FirecrawlApp::with_max_poll_wait`.
+    /// The job itself keeps running on the API, so `job_id` can be checked on later.
+    #[cfg(any(feature = "batch", feature = "crawl"))]
+    #[error("Gave up waiting for the {} job {job_id} after {elapsed:?}", last_status.kind())]
+    PollTimeout {
+        job_id: String,
+        elapsed: Duration,
+        last_status: PartialStatus,
+    },
+    /// The status is boxed to keep `FirecrawlError` small.
+    #[cfg(feature = "extract")]
+    #[error("Extract job failed: {0}")]
+    ExtractJobFailed(String, Box<ExtractStatus>),
+    /// The status is boxed to keep `FirecrawlError` small.
+    #[cfg(feature = "llmstxt")]
+    #[error("llms.txt generation job failed: {0}")]
+    LlmsTxtJobFailed(String, Box<LlmsTxtStatus>),
+    /// The API answered 404 for the status of a job: its id is unknown, or its data expired.
+    ///
+    /// Malformed ids are rejected with a 400 status and reported as `FirecrawlError::APIError`.
+    #[error("The {kind} job {id} was not found or has expired")]
+    JobNotFound { kind: JobKind, id: String },
+    /// The API answered 404 for an endpoint the instance doesn't provide, e.g. the team usage
+    /// endpoints on self-hosted instances. Carries the path of the endpoint.
+    #[error("The {0} endpoint is not supported by this Firecrawl instance")]
+    UnsupportedEndpoint(String),
+    /// The API key was rejected with a 401 or 403 status, see [`FirecrawlApp::verify_connection`].
+    ///
+    /// [`FirecrawlApp::verify_connection`]: crate::FirecrawlApp::verify_connection
+    #[error("Invalid API key: HTTP error {0}")]
+    InvalidApiKey(u16),
+    /// The API at the given URL couldn't be reached, see [`FirecrawlApp::verify_connection`].
+    ///
+    /// [`FirecrawlApp::verify_connection`]: crate::FirecrawlApp::verify_connection
+    #[error("Firecrawl API at {0} is unreachable: {1}")]
+    Unreachable(String, reqwest::Error),
+    /// The options of a request can't be combined, detected before it is sent.
+    #[error("Invalid options: {0}")]
+    InvalidOptions(String),
+    #[error(
+        "{action} failed: rate limited{}",
+        retry_after.map(|d| format!(", retry after {}s", d.as_secs())).unwrap_or_default()
+    )]
+    RateLimited {
+        action: String,
+        /// Delay requested by the `Retry-After` header, if the API sent one
+        retry_after: Option<Duration>,
+        /// Rate limit state from the `x-ratelimit-*` headers, boxed to keep `FirecrawlError` small
+        rate_limit: Option<Box<RateLimitInfo>>,
+        /// Id of the failed request, to share with Firecrawl support
+        request_id: Option<String>,
+    },
+}
+
+impl FirecrawlError {
+    /// Error for an invalid client configuration, detected before any request is sent.
+    pub(crate) fn configuration(message: impl Into<String>) -> Self {
+        FirecrawlError::APIError(
+            "Configuration".to_string(),
+            FirecrawlAPIError {
+                error: message.into(),
+                details: None,
+                status_code: None,
+                rate_limit: None,
+                request_id: None,
+            },
+        )
+    }
+
+    /// HTTP status code of the failed response, if one was received.
+    fn status_code(&self) -> Option<u16> {
+        match self {
+            FirecrawlError::HttpRequestFailed(_, status, _) => Some(*status),
+            FirecrawlError::HttpError(_, e) => e.status().map(|s| s.as_u16()),
+            FirecrawlError::APIError(_, e) => e.status_code,
+            FirecrawlError::RateLimited { .. } => Some(429),
+            FirecrawlError::InvalidApiKey(status) => Some(*status),
+            FirecrawlError::JobNotFound { .. } | FirecrawlError::UnsupportedEndpoint(_) => {
+                Some(404)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the request was rejected because of rate limiting.
+    pub fn is_rate_limited(&self) -> bool {
+        match self {
+            FirecrawlError::APIError(_, e) => e.kind() == ApiErrorKind::RateLimited,
+            _ => self.status_code() == Some(429),
+        }
+    }
+
+    /// Rate limit state reported along with the failed response, if any.
+    ///
+    /// Use [`RateLimitInfo::wait_duration`] to wait until the rate limit window resets.
+    pub fn rate_limit_info(&self) -> Option<&RateLimitInfo> {
+        match self {
+            FirecrawlError::APIError(_, e) => e.rate_limit.as_deref(),
+            FirecrawlError::RateLimited { rate_limit, .. } => rate_limit.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Id of the failed request reported by the API, to share with Firecrawl support.
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            FirecrawlError::APIError(_, e) => e.request_id.as_deref(),
+            FirecrawlError::RateLimited { request_id, .. } => request_id.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Details of a request rejected by the API's validation with a 400 status, e.g. an array of
+    /// `{ "path": ["url"], "message": "Invalid URL", ... }` issues to show per field.
+    pub fn validation_details(&self) -> Option<&Value> {
+        match self {
+            FirecrawlError::APIError(_, e) if e.status_code == Some(400) => e.details.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Delay the API asked for before retrying, from the `Retry-After` header of a 429 response.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            FirecrawlError::RateLimited { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the same request may succeed when retried later.
+    ///
+    /// This covers network failures, request timeouts, rate limiting and 5xx responses.
+    pub fn is_retryable(&self) -> bool {
+        let retryable_status = matches!(self.status_code(), Some(408 | 429 | 500..=599));
+
+        match self {
+            FirecrawlError::HttpRequestFailed(..) | FirecrawlError::RateLimited { .. } => {
+                retryable_status
+            }
+            FirecrawlError::HttpError(_, e) => {
+                e.is_timeout() || is_connect_error(e) || retryable_status
+            }
+            FirecrawlError::Unreachable(..) => true,
+            FirecrawlError::APIError(_, e) => {
+                retryable_status
+                    || matches!(e.kind(), ApiErrorKind::RateLimited | ApiErrorKind::Timeout)
+            }
+            FirecrawlError::ResponseParseErrorText(_)
+            | FirecrawlError::ResponseParseError(_)
+            | FirecrawlError::BatchScrapeJobFailed(..)
+            | FirecrawlError::InvalidApiKey(_)
+            | FirecrawlError::JobNotFound { .. }
+            | FirecrawlError::UnsupportedEndpoint(_)
+            | FirecrawlError::InvalidOptions(_) => false,
+            #[cfg(feature = "crawl")]
+            FirecrawlError::CrawlJobFailed(..) | FirecrawlError::CrawlJobCancelled(_) => false,
+            #[cfg(any(feature = "batch", feature = "crawl"))]
+            FirecrawlError::OperationCancelled(_) | FirecrawlError::PollTimeout { .. } => false,
+            #[cfg(feature = "extract")]
+            FirecrawlError::ExtractJobFailed(..) => false,
+            #[cfg(feature = "llmstxt")]
+            FirecrawlError::LlmsTxtJobFailed(..) => false,
+        }
+    }
+}
+
+/// Whether `error` happened while connecting, which `fetch` doesn't tell apart on `wasm32`.
+#[cfg(not(target_arch = "wasm32"))]
+fn is_connect_error(error: &reqwest::Error) -> bool {
+    error.is_connect()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn is_connect_error(_error: &reqwest::Error) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api_error(status_code: Option<u16>, error: &str) -> FirecrawlAPIError {
+        FirecrawlAPIError {
+            error: error.to_string(),
+            details: None,
+            status_code,
+            rate_limit: None,
+            request_id: None,
+        }
+    }
+
+    #[test]
+    fn test_api_error_kind_mapping() {
+        let cases = [
+            (
+                Some(401),
+                "Unauthorized: Invalid token",
+                ApiErrorKind::InvalidApiKey,
+            ),
+            (
+                Some(402),
+                "Insufficient credits to perform this request. For more credits, you can upgrade your plan at https://firecrawl.dev/pricing.",
+                ApiErrorKind::InsufficientCredits,
+            ),
+            (
+                Some(403),
+                "This website is no longer supported, please reach out to help@firecrawl.com for more info on how to activate it on your account.",
+                ApiErrorKind::UrlBlocked,
+            ),
+            (
+                Some(403),
+                "Firecrawl currently does not support social media scraping due to policy restrictions.",
+                ApiErrorKind::UrlBlocked,
+            ),
+            (Some(408), "Request timed out", ApiErrorKind::Timeout),
+            (Some(504), "Gateway Timeout", ApiErrorKind::Timeout),
+            // About a timeout, not timed out itself
+            (
+                Some(400),
+                "Invalid timeout value",
+                ApiErrorKind::Unknown("Invalid timeout value".to_string()),
+            ),
+            (
+                Some(429),
+                "Rate limit exceeded. Consumed (req/min): 11, Remaining (req/min): 0. Upgrade your plan at https://firecrawl.dev/pricing for increased rate limits or please retry after 43s.",
+                ApiErrorKind::RateLimited,
+            ),
+            // Message alone is enough when the status code is unavailable
+            (
+                None,
+                "Insufficient credits to perform this request.",
+                ApiErrorKind::InsufficientCredits,
+            ),
+            (None, "Rate limit exceeded.", ApiErrorKind::RateLimited),
+            (None, "Unauthorized", ApiErrorKind::InvalidApiKey),
+            (
+                Some(500),
+                "Scrape operation timed out",
+                ApiErrorKind::Timeout,
+            ),
+            (
+                Some(500),
+                "Internal server error",
+                ApiErrorKind::Unknown("Internal server error".to_string()),
+            ),
+        ];
+
+        for (status_code, message, expected) in cases {
+            assert_eq!(
+                api_error(status_code, message).kind(),
+                expected,
+                "status {status_code:?}, message {message:?}"
+            );
+        }
+    }
+
+    #[cfg(feature = "crawl")]
+    fn crawl_status() -> CrawlStatus {
+        serde_json::from_value(serde_json::json!({
+            "status": "failed",
+            "total": 0,
+            "completed": 0,
+            "creditsUsed": 0,
+            "expiresAt": "2025-01-01T00:00:00.000Z",
+            "data": []
+        }))
+        .unwrap()
+    }
+
+    async fn connect_error() -> reqwest::Error {
+        // Nothing listens on port 1, so the connection is refused
+        reqwest::Client::new()
+            .get("http://127.0.0.1:1")
+            .send()
+            .await
+            .unwrap_err()
+    }
+
+    fn builder_error() -> reqwest::Error {
+        reqwest::Client::new().get("not a url").build().unwrap_err()
+    }
+
+    #[tokio::test]
+    async fn test_retry_classification() {
+        let http_failed = |status| {
+            FirecrawlError::HttpRequestFailed("scrape URL".to_string(), status, String::new())
+        };
+        let api = |status, message| {
+            FirecrawlError::APIError("scrape URL".to_string(), api_error(status, message))
+        };
+        let parse_error = serde_json::from_str::<Value>("{").unwrap_err();
+
+        // (error, is_retryable, is_rate_limited)
+        #[allow(unused_mut)]
+        let mut cases = vec![
+            (http_failed(500), true, false),
+            (http_failed(503), true, false),
+            (http_failed(429), true, true),
+            (http_failed(408), true, false),
+            (http_failed(404), false, false),
+            (http_failed(400), false, false),
+            (
+                FirecrawlError::HttpError("scrape URL".to_string(), connect_error().await),
+                true,
+                false,
+            ),
+            (
+                FirecrawlError::HttpError("scrape URL".to_string(), builder_error()),
+                false,
+                false,
+            ),
+            (api(Some(429), "Rate limit exceeded"), true, true),
+            (api(None, "Rate limit exceeded"), true, true),
+            (
+                FirecrawlError::RateLimited {
+                    action: "scrape URL".to_string(),
+                    retry_after: None,
+                    rate_limit: None,
+                    request_id: None,
+                },
+                true,
+                true,
+            ),
+            (api(Some(502), "Bad gateway"), true, false),
+            (api(Some(408), "Request timed out"), true, false),
+            (api(Some(401), "Unauthorized"), false, false),
+            (api(Some(402), "Insufficient credits"), false, false),
+            (
+                FirecrawlError::ResponseParseErrorText(builder_error()),
+                false,
+                false,
+            ),
+            (
+                FirecrawlError::ResponseParseError(parse_error),
+                false,
+                false,
+            ),
+            (
+                FirecrawlError::BatchScrapeJobFailed(
+                    "Batch scrape job failed.".to_string(),
+                    "batch-123".to_string(),
+                ),
+                false,
+                false,
+            ),
+            (
+                FirecrawlError::UnsupportedEndpoint("/v1/team/token-usage".to_string()),
+                false,
+                false,
+            ),
+            (
+                FirecrawlError::InvalidOptions("formats".to_string()),
+                false,
+                false,
+            ),
+        ];
+        #[cfg(feature = "crawl")]
+        cases.extend([
+            (
+                FirecrawlError::CrawlJobFailed(
+                    "Crawl job failed.".to_string(),
+                    Box::new(crawl_status()),
+                ),
+                false,
+                false,
+            ),
+            (
+                FirecrawlError::CrawlJobCancelled(Box::new(crawl_status())),
+                false,
+                false,
+            ),
+            (
+                FirecrawlError::OperationCancelled(PartialStatus::Crawl(Box::new(crawl_status()))),
+                false,
+                false,
+            ),
+            (
+                FirecrawlError::PollTimeout {
+                    job_id: "crawl-123".to_string(),
+                    elapsed: Duration::from_secs(600),
+                    last_status: PartialStatus::Crawl(Box::new(crawl_status())),
+                },
+                false,
+                false,
+            ),
+        ]);
+        #[cfg(feature = "extract")]
+        cases.push((
+            FirecrawlError::ExtractJobFailed(
+                "Extract job failed.".to_string(),
+                Box::new(
+                    serde_json::from_value(serde_json::json!({ "status": "failed" })).unwrap(),
+                ),
+            ),
+            false,
+            false,
+        ));
+        #[cfg(feature = "llmstxt")]
+        cases.push((
+            FirecrawlError::LlmsTxtJobFailed(
+                "llms.txt generation job failed.".to_string(),
+                serde_json::from_value(serde_json::json!({ "status": "failed" })).unwrap(),
+            ),
+            false,
+            false,
+        ));
+
+        for (error, retryable, rate_limited) in cases {
+            assert_eq!(
+                error.is_retryable(),
+                retryable,
+                "is_retryable for {error:?}"
+            );
+            assert_eq!(
+                error.is_rate_limited(),
+                rate_limited,
+                "is_rate_limited for {error:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_rate_limited_display() {
+        let error = |retry_after| FirecrawlError::RateLimited {
+            action: "scrape URL".to_string(),
+            retry_after,
+            rate_limit: None,
+            request_id: None,
+        };
+
+        assert_eq!(
+            error(Some(Duration::from_secs(43))).to_string(),
+            "scrape URL failed: rate limited, retry after 43s"
+        );
+        assert_eq!(error(None).to_string(), "scrape URL failed: rate limited");
+        assert_eq!(
+            error(Some(Duration::from_secs(43))).retry_after(),
+            Some(Duration::from_secs(43))
+        );
+    }
+
+    #[test]
+    fn test_validation_details_only_for_bad_requests() {
+        let error = |status| {
+            let mut api_error = api_error(status, "Bad Request");
+            api_error.details =
+                Some(serde_json::json!([{ "path": ["url"], "message": "Invalid URL" }]));
+            FirecrawlError::APIError("scrape URL".to_string(), api_error)
+        };
+
+        assert!(error(Some(400)).validation_details().is_some());
+        assert!(error(Some(500)).validation_details().is_none());
+        assert!(error(None).validation_details().is_none());
+    }
 }
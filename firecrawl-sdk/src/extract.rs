@@ -0,0 +1,315 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[cfg(feature = "mcp-tool")]
+use schemars::JsonSchema;
+
+use crate::{FirecrawlApp, FirecrawlError, routes, time};
+
+#[serde_with::skip_serializing_none]
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+#[cfg_attr(feature = "mcp-tool", derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ExtractRequest {
+    /// URLs to extract from. Glob patterns like `https://example.com/*` are supported.
+    pub urls: Vec<String>,
+
+    /// Prompt describing the data to extract
+    pub prompt: Option<String>,
+
+    /// JSON schema the extracted data should conform to
+    pub schema: Option<Value>,
+
+    /// Follow links outside of the given URLs through a web search. (default: `false`)
+    pub enable_web_search: Option<bool>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "mcp-tool", derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ExtractAsyncResponse {
+    success: bool,
+
+    /// Extract job ID
+    pub id: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "mcp-tool", derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum ExtractStatusTypes {
+    /// The extract job is in progress.
+    #[default]
+    Processing,
+
+    /// The extract job has been completed successfully.
+    Completed,
+
+    /// The extract job has failed.
+    Failed,
+
+    /// The extract job has been cancelled.
+    Cancelled,
+
+    /// A status this version of the SDK doesn't know, as sent by the API, which is monitored
+    /// like `Processing`.
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtractStatus {
+    /// The status of the extract job.
+    pub status: ExtractStatusTypes,
+
+    /// The extracted data, once the job has completed.
+    pub data: Option<Value>,
+
+    /// Error message of a failed job.
+    pub error: Option<String>,
+
+    /// Expiry time of the extracted data. After this date, it will be unavailable from the API.
+    ///
+    /// Enable the `chrono` feature to have it parsed by `expires_at_timestamp`.
+    pub expires_at: Option<String>,
+}
+
+impl ExtractStatus {
+    /// `expires_at` parsed into a timestamp, or `None` if it's missing or not an ISO-8601
+    /// timestamp.
+    #[cfg(feature = "chrono")]
+    pub fn expires_at_timestamp(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.expires_at.as_deref().and_then(crate::timestamp::parse)
+    }
+}
+
+impl FirecrawlApp {
+    /// Initiates an extract job for a set of URLs using the Firecrawl API.
+    pub async fn extract_async(
+        &self,
+        request: &ExtractRequest,
+    ) -> Result<ExtractAsyncResponse, FirecrawlError> {
+        let http_request = self
+            .inner
+            .client
+            .post(self.endpoint(routes::extract()))
+            .headers(self.prepare_headers(None)?)
+            .json(request);
+        let response = self.send_request(http_request).await.map_err(|e| {
+            FirecrawlError::HttpError(format!("Extracting from {:?}", request.urls), e)
+        })?;
+
+        self.handle_response::<ExtractAsyncResponse>(response, "start extract job")
+            .await
+    }
+
+    /// Performs an extract job using the Firecrawl API, waiting for the end result.
+    pub async fn extract(
+        &self,
+        request: &ExtractRequest,
+        poll_interval: Option<u64>,
+    ) -> Result<ExtractStatus, FirecrawlError> {
+        let poll_interval = poll_interval.unwrap_or(2000);
+
+        let res = self.extract_async(request).await?;
+
+        self.monitor_extract_status(&res.id, poll_interval).await
+    }
+
+    /// Checks for the status of an extract job, based on the job's ID. To be used in conjunction with `FirecrawlApp::extract_async`.
+    pub async fn check_extract_status(
+        &self,
+        id: impl AsRef<str>,
+    ) -> Result<ExtractStatus, FirecrawlError> {
+        let request = self
+            .inner
+            .client
+            .get(self.endpoint(routes::extract_status(id.as_ref())))
+            .headers(self.prepare_headers(None)?);
+        let response = self.send_request(request).await.map_err(|e| {
+            FirecrawlError::HttpError(format!("Checking status of extract job {}", id.as_ref()), e)
+        })?;
+
+        self.handle_response(
+            response,
+            format!("Checking status of extract job {}", id.as_ref()),
+        )
+        .await
+    }
+
+    async fn monitor_extract_status(
+        &self,
+        id: &str,
+        poll_interval: u64,
+    ) -> Result<ExtractStatus, FirecrawlError> {
+        // Warn about an unknown status once, not on every poll
+        let mut warned = false;
+        loop {
+            let status = self.check_extract_status(id).await?;
+
+            match status.status {
+                ExtractStatusTypes::Completed => break Ok(status),
+                ExtractStatusTypes::Processing | ExtractStatusTypes::Unknown(_) => {
+                    if let ExtractStatusTypes::Unknown(status) = &status.status
+                        && !warned
+                    {
+                        self.warn(&format!(
+                            "extract job {id} has an unknown status {status:?}, polling again"
+                        ));
+                        warned = true;
+                    }
+                    time::delay(std::time::Duration::from_millis(poll_interval)).await;
+                }
+                ExtractStatusTypes::Failed => {
+                    let message = status
+                        .error
+                        .clone()
+                        .unwrap_or_else(|| "Extract job failed.".to_string());
+                    break Err(FirecrawlError::ExtractJobFailed(message, Box::new(status)));
+                }
+                ExtractStatusTypes::Cancelled => {
+                    break Err(FirecrawlError::ExtractJobFailed(
+                        "Extract job cancelled.".to_string(),
+                        Box::new(status),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{body_json, method, path},
+    };
+
+    async fn mock_extract(server: &MockServer, status: serde_json::Value) {
+        Mock::given(method("POST"))
+            .and(path("/v1/extract"))
+            .and(body_json(json!({
+                "urls": ["https://example.com/*"],
+                "prompt": "Extract the title",
+                "enableWebSearch": false
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "id": "extract-1"
+            })))
+            .mount(server)
+            .await;
+
+        // The first status check reports the job as still running
+        Mock::given(method("GET"))
+            .and(path("/v1/extract/extract-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "status": "processing",
+                "expiresAt": "2025-01-02T03:04:05.000Z"
+            })))
+            .up_to_n_times(1)
+            .mount(server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/v1/extract/extract-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(status))
+            .mount(server)
+            .await;
+    }
+
+    fn request() -> ExtractRequest {
+        ExtractRequest {
+            urls: vec!["https://example.com/*".to_string()],
+            prompt: Some("Extract the title".to_string()),
+            schema: None,
+            enable_web_search: Some(false),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_extract_polls_until_completed() {
+        let server = MockServer::start().await;
+        mock_extract(
+            &server,
+            json!({
+                "success": true,
+                "status": "completed",
+                "data": { "title": "Example Domain" },
+                "expiresAt": "2025-01-02T03:04:05.000Z"
+            }),
+        )
+        .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let status = app.extract(&request(), Some(1)).await.unwrap();
+
+        assert_eq!(status.status, ExtractStatusTypes::Completed);
+        assert_eq!(status.data, Some(json!({ "title": "Example Domain" })));
+    }
+
+    #[tokio::test]
+    async fn test_extract_warns_once_about_unknown_status() {
+        let server = MockServer::start().await;
+        // A status introduced by a newer version of the API, mounted first to answer first
+        Mock::given(method("GET"))
+            .and(path("/v1/extract/extract-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "status": "queued"
+            })))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        mock_extract(
+            &server,
+            json!({
+                "success": true,
+                "status": "completed",
+                "data": { "title": "Example Domain" }
+            }),
+        )
+        .await;
+
+        let warnings = std::sync::Arc::new(crate::observer::WarningRecorder::default());
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>)
+            .unwrap()
+            .with_hook(warnings.clone());
+        let status = app.extract(&request(), Some(1)).await.unwrap();
+
+        assert_eq!(status.status, ExtractStatusTypes::Completed);
+        assert_eq!(
+            warnings.take(),
+            vec![r#"extract job extract-1 has an unknown status "queued", polling again"#]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_extract_failed() {
+        let server = MockServer::start().await;
+        mock_extract(
+            &server,
+            json!({
+                "success": true,
+                "status": "failed",
+                "error": "No content could be extracted"
+            }),
+        )
+        .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let result = app.extract(&request(), Some(1)).await;
+
+        assert!(matches!(
+            result,
+            Err(FirecrawlError::ExtractJobFailed(message, status))
+                if message == "No content could be extracted"
+                    && status.status == ExtractStatusTypes::Failed
+        ));
+    }
+}
@@ -0,0 +1,214 @@
+use reqwest::StatusCode;
+use serde_json::Value;
+
+use crate::{FirecrawlApp, FirecrawlError, is_cloud_api_url, routes};
+
+/// State of a self-hosted instance, returned by [`FirecrawlApp::health_check`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthStatus {
+    /// Whether the instance answered with a success status
+    pub reachable: bool,
+
+    /// Version the instance reported, if any
+    pub version: Option<String>,
+}
+
+impl FirecrawlApp {
+    /// Checks that the API can be reached and accepts the API key, without using credits, e.g.
+    /// at startup.
+    ///
+    /// The cloud API is asked for the credit usage, self-hosted instances for
+    /// `/is-production-ready`. A 401 or 403 status fails with `FirecrawlError::InvalidApiKey` and
+    /// a network failure with `FirecrawlError::Unreachable`.
+    pub async fn verify_connection(&self) -> Result<(), FirecrawlError> {
+        let url = if is_cloud_api_url(&self.inner.api_url) {
+            self.endpoint(routes::credit_usage())
+        } else {
+            self.endpoint(routes::is_production_ready())
+        };
+
+        let request = self
+            .inner
+            .client
+            .get(url)
+            .headers(self.prepare_headers(None)?);
+        let response = self
+            .send_request(request)
+            .await
+            .map_err(|e| FirecrawlError::Unreachable(self.inner.api_url.to_string(), e))?;
+
+        match response.status() {
+            status if status.is_success() => Ok(()),
+            status @ (StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN) => {
+                Err(FirecrawlError::InvalidApiKey(status.as_u16()))
+            }
+            _ => self
+                .handle_response::<serde_json::Value>(response, "verify connection")
+                .await
+                .map(|_| ()),
+        }
+    }
+
+    /// Checks that a self-hosted instance is up, before starting jobs on it.
+    ///
+    /// Asks the instance for `/health` and reads the `version` of its JSON answer, if any. A
+    /// network failure or an error status returns `reachable: false` instead of an error, so
+    /// only building the request can fail.
+    pub async fn health_check(&self) -> Result<HealthStatus, FirecrawlError> {
+        let url = self.endpoint(routes::health());
+        let request = self
+            .inner
+            .client
+            .get(url)
+            .headers(self.prepare_headers(None)?);
+
+        let response = match self.send_request(request).await {
+            Ok(response) if response.status().is_success() => response,
+            _ => {
+                return Ok(HealthStatus {
+                    reachable: false,
+                    version: None,
+                });
+            }
+        };
+
+        // Instances may answer with plain text, which reports no version
+        let version = response
+            .json::<Value>()
+            .await
+            .ok()
+            .and_then(|body| body.get("version")?.as_str().map(str::to_string));
+        Ok(HealthStatus {
+            reachable: true,
+            version,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{header, method, path},
+    };
+
+    #[tokio::test]
+    async fn test_verify_connection() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/is-production-ready"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "status": "ok" })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        app.verify_connection().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_verify_connection_with_bad_key() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/is-production-ready"))
+            .and(header("Authorization", "Bearer fc-revoked"))
+            .respond_with(ResponseTemplate::new(401).set_body_json(json!({
+                "success": false,
+                "error": "Unauthorized: Invalid token"
+            })))
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), Some("fc-revoked")).unwrap();
+        let error = app.verify_connection().await.unwrap_err();
+        assert!(
+            matches!(error, FirecrawlError::InvalidApiKey(401)),
+            "{error:?}"
+        );
+        assert!(!error.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_verify_connection_with_unreachable_host() {
+        // Nothing listens on the port once the listener is dropped
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let api_url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let app = FirecrawlApp::new_selfhosted(&api_url, None::<String>).unwrap();
+        let error = app.verify_connection().await.unwrap_err();
+        assert!(
+            matches!(&error, FirecrawlError::Unreachable(url, _) if url.starts_with(&api_url)),
+            "{error:?}"
+        );
+        assert!(error.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_health_check() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({ "status": "ok", "version": "1.2.0" })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        assert_eq!(
+            app.health_check().await.unwrap(),
+            HealthStatus {
+                reachable: true,
+                version: Some("1.2.0".to_string()),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_health_check_without_version() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/health"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("OK"))
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let status = app.health_check().await.unwrap();
+        assert!(status.reachable);
+        assert_eq!(status.version, None);
+    }
+
+    #[tokio::test]
+    async fn test_health_check_with_unreachable_host() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let api_url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let app = FirecrawlApp::new_selfhosted(&api_url, None::<String>).unwrap();
+        let status = app.health_check().await.unwrap();
+        assert!(!status.reachable);
+        assert_eq!(status.version, None);
+    }
+
+    #[tokio::test]
+    async fn test_verify_connection_with_server_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let error = app.verify_connection().await.unwrap_err();
+        assert!(
+            matches!(error, FirecrawlError::HttpRequestFailed(_, 503, _)),
+            "{error:?}"
+        );
+    }
+}
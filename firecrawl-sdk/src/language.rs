@@ -0,0 +1,168 @@
+//! Language of scraped documents, from their metadata or else detected from their markdown.
+//!
+//! The API only reports the language a page declares, e.g. in `<html lang>`, which many pages
+//! omit. [`detect_language`] guesses it from the text with
+//! [whatlang](https://docs.rs/whatlang), and [`enrich_languages`] fills it in on a batch of
+//! documents before they are routed, e.g. to translation.
+
+use crate::document::Document;
+
+/// Where a [`LanguageTag`] comes from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LanguageSource {
+    /// The language declared by the page, reported in `DocumentMetadata.language`.
+    Metadata,
+
+    /// The language detected from the markdown, with the confidence of the detection, from `0.0`
+    /// to `1.0`.
+    Detected { confidence: f64 },
+}
+
+/// Language of a document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanguageTag {
+    /// The language as declared by the page, usually a BCP 47 tag like `en-US`, or the ISO
+    /// 639-3 code of the detected language, like `eng`.
+    pub code: String,
+
+    /// Where `code` comes from.
+    pub source: LanguageSource,
+}
+
+/// Detects the language of `markdown`, or returns `None` if the detection is less confident than
+/// `min_confidence`, from `0.0` to `1.0`.
+///
+/// URLs are left out, as their words would skew the detection towards English. Short texts are
+/// often detected with low confidence, so a threshold of about `0.5` keeps most guesses while
+/// leaving out the unreliable ones.
+pub fn detect_language(markdown: &str, min_confidence: f64) -> Option<LanguageTag> {
+    let text = markdown
+        .split_whitespace()
+        .filter(|word| !word.contains("://"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let info = whatlang::detect(&text)?;
+    if info.confidence() < min_confidence {
+        return None;
+    }
+
+    Some(LanguageTag {
+        code: info.lang().code().to_string(),
+        source: LanguageSource::Detected {
+            confidence: info.confidence(),
+        },
+    })
+}
+
+/// Sets `DocumentMetadata.language` of the documents without one to the language detected from
+/// their markdown, see [`Document::detect_language`]. Returns how many documents were updated.
+pub fn enrich_languages(documents: &mut [Document], min_confidence: f64) -> usize {
+    let mut updated = 0;
+    for document in documents {
+        if let Some(LanguageTag {
+            code,
+            source: LanguageSource::Detected { .. },
+        }) = document.detect_language(min_confidence)
+        {
+            document.metadata.language = Some(code);
+            updated += 1;
+        }
+    }
+    updated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn document(markdown: &str, language: Option<&str>) -> Document {
+        serde_json::from_value(json!({
+            "markdown": markdown,
+            "metadata": {
+                "sourceURL": "https://example.com",
+                "statusCode": 200,
+                "language": language
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_detect_language() {
+        let cases = [
+            (
+                "The quick brown fox jumps over the lazy dog while the farmer watches from the porch.",
+                "eng",
+            ),
+            (
+                "Le renard brun rapide saute par-dessus le chien paresseux pendant que le fermier regarde.",
+                "fra",
+            ),
+            (
+                "Der schnelle braune Fuchs springt über den faulen Hund, während der Bauer zuschaut.",
+                "deu",
+            ),
+            (
+                "El rápido zorro marrón salta sobre el perro perezoso mientras el granjero observa.",
+                "spa",
+            ),
+            (
+                "素早い茶色の狐が怠け者の犬を飛び越え、農夫はそれを見ています。",
+                "jpn",
+            ),
+        ];
+
+        for (markdown, expected) in cases {
+            let tag = detect_language(markdown, 0.5).unwrap();
+            assert_eq!(tag.code, expected, "{markdown}");
+            assert!(
+                matches!(tag.source, LanguageSource::Detected { confidence } if confidence >= 0.5)
+            );
+        }
+    }
+
+    #[test]
+    fn test_detect_language_below_threshold() {
+        // Detected, but with a low confidence
+        assert!(detect_language("Hello", 0.0).is_some());
+        assert_eq!(detect_language("Hello", 0.5), None);
+        assert_eq!(detect_language("", 0.0), None);
+    }
+
+    #[test]
+    fn test_document_language_prefers_metadata() {
+        let text = "Le renard brun rapide saute par-dessus le chien paresseux pendant que le fermier regarde.";
+
+        assert_eq!(
+            document(text, Some("fr-CA")).detect_language(0.5),
+            Some(LanguageTag {
+                code: "fr-CA".to_string(),
+                source: LanguageSource::Metadata,
+            })
+        );
+        assert_eq!(
+            document(text, None).detect_language(0.5).unwrap().code,
+            "fra"
+        );
+    }
+
+    #[test]
+    fn test_enrich_languages() {
+        let mut documents = vec![
+            document(
+                "The quick brown fox jumps over the lazy dog while the farmer watches from the porch.",
+                None,
+            ),
+            document("Der schnelle braune Fuchs springt.", Some("de")),
+            document("Ok", None),
+        ];
+
+        assert_eq!(enrich_languages(&mut documents, 0.5), 1);
+        let languages: Vec<_> = documents
+            .iter()
+            .map(|document| document.metadata.language.as_deref())
+            .collect();
+        assert_eq!(languages, vec![Some("eng"), Some("de"), None]);
+    }
+}
@@ -1,28 +1,367 @@
-use reqwest::{Client, Response};
+use std::sync::{
+    Arc, Mutex, PoisonError,
+    atomic::{AtomicBool, Ordering},
+};
+
+use reqwest::{
+    Client, RequestBuilder, Response, StatusCode,
+    header::{HeaderMap, HeaderValue, USER_AGENT},
+};
 use serde::de::DeserializeOwned;
+use url::Url;
+
+use crate::{routes::Route, time::Instant};
+
+#[cfg(all(target_arch = "wasm32", not(feature = "wasm")))]
+compile_error!("building for wasm32 requires the `wasm` feature of firecrawl-sdk");
 
+#[cfg(any(feature = "llmstxt", feature = "map", feature = "scrape"))]
+#[macro_use]
+mod macros;
+
+#[cfg(feature = "batch")]
 pub mod batch_scrape;
+#[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+pub mod blocking;
+mod builder;
+mod capture;
+#[cfg(all(
+    test,
+    feature = "batch",
+    feature = "crawl",
+    feature = "map",
+    feature = "search"
+))]
+mod casing_tests;
+#[cfg(all(test, feature = "batch", feature = "crawl"))]
+mod compat_tests;
+mod config;
+mod cost;
+#[cfg(feature = "crawl")]
 pub mod crawl;
 pub mod document;
 mod error;
+#[cfg(feature = "extract")]
+pub mod extract;
+mod health;
+#[cfg(feature = "lang-detect")]
+pub mod language;
+#[cfg(feature = "llmstxt")]
+pub mod llmstxt;
+#[cfg(feature = "map")]
 pub mod map;
+#[cfg(feature = "otel")]
+mod metrics;
+mod observer;
+#[cfg(all(feature = "map", feature = "scrape"))]
+pub mod overview;
+#[cfg(any(feature = "batch", feature = "crawl"))]
+mod poll;
+mod rate_limit;
+mod request;
+mod routes;
+#[cfg(feature = "sanitize")]
+pub mod sanitize;
+#[cfg(feature = "scrape")]
 pub mod scrape;
+#[cfg(feature = "search")]
 pub mod search;
+#[cfg(feature = "strict")]
+mod strict;
+mod time;
+#[cfg(all(
+    feature = "chrono",
+    any(
+        feature = "batch",
+        feature = "crawl",
+        feature = "extract",
+        feature = "llmstxt"
+    )
+))]
+mod timestamp;
+pub mod usage;
+pub mod webhook;
 
-use error::FirecrawlAPIError;
-pub use error::FirecrawlError;
+pub use builder::FirecrawlAppBuilder;
+pub use capture::ExchangeRecord;
+pub use config::ClientConfig;
+pub use cost::{CostEvent, CostOperation};
+#[cfg(any(feature = "batch", feature = "crawl"))]
+pub use error::PartialStatus;
+pub use error::{ApiErrorKind, FirecrawlAPIError, FirecrawlError, JobKind};
+pub use health::HealthStatus;
+pub use observer::{RequestHook, RequestInfo};
+#[cfg(any(feature = "batch", feature = "crawl"))]
+pub use poll::PollPolicy;
+pub use rate_limit::RateLimitInfo;
+#[cfg(any(feature = "batch", feature = "crawl"))]
+pub use tokio_util::sync::CancellationToken;
 
-#[derive(Clone, Debug)]
+/// Client of the Firecrawl API.
+///
+/// Cloning is cheap: the configuration is shared behind an `Arc` and the HTTP client is a handle
+/// to a shared connection pool, so a clone can be made per task or request.
+///
+/// Clones share the state of the client: the rate limit of
+/// [`with_rate_limit`](Self::with_rate_limit) applies to all of them together, and they report
+/// to the same observer, hooks and captures. Configuring a clone with a `with_*` method leaves
+/// the other clones as they are.
+#[derive(Clone)]
 pub struct FirecrawlApp {
-    api_key: Option<String>,
-    api_url: String,
+    inner: Arc<Inner>,
+}
+
+/// Configuration and shared state of a [`FirecrawlApp`], copied on write by the `with_*`
+/// methods of a client that has been cloned.
+#[derive(Clone)]
+struct Inner {
+    api_key: Option<Arc<str>>,
+    api_url: Arc<Url>,
+    api_version: Arc<str>,
     client: Client,
+    default_headers: Arc<HeaderMap>,
+    observer: Option<observer::Observer>,
+    hooks: Arc<Vec<Arc<dyn RequestHook>>>,
+    middlewares: Arc<Vec<observer::RequestMiddleware>>,
+    max_html_size: Option<usize>,
+    max_poll_wait: Option<std::time::Duration>,
+    auto_idempotency: bool,
+    /// Body of the last successful response, when captured with `with_capture_raw`
+    raw_response: Option<Arc<Mutex<Option<serde_json::Value>>>>,
+    /// Last exchanges, when captured with `enable_capture`
+    capture: Option<Arc<capture::Capture>>,
+    cost_hook: Option<cost::CostHook>,
+    cost_center: Option<Arc<str>>,
+    /// Limit of the request rate, shared by the clones
+    rate_limiter: Option<Arc<rate_limit::TokenBucket>>,
+    /// Set by `try_acquire` when it took the token of the next request
+    prepaid: Option<Arc<AtomicBool>>,
+    /// Warnings about unknown fields reported so far, shared by the clones
+    #[cfg(feature = "strict")]
+    reported_fields: Arc<Mutex<std::collections::BTreeSet<String>>>,
+    #[cfg(feature = "otel")]
+    metrics: metrics::Metrics,
+}
+
+impl std::fmt::Debug for FirecrawlApp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FirecrawlApp")
+            .field(
+                "api_key",
+                &self.inner.api_key.as_deref().map(redact_api_key),
+            )
+            .field("api_url", &self.inner.api_url.as_str())
+            .field("api_version", &self.inner.api_version)
+            .field("client", &self.inner.client)
+            .field("default_headers", &self.inner.default_headers)
+            .field(
+                "observer",
+                &self.inner.observer.as_ref().map(|_| "Fn(&RequestInfo)"),
+            )
+            .field("hooks", &self.inner.hooks.len())
+            .field("middlewares", &self.inner.middlewares.len())
+            .field("max_html_size", &self.inner.max_html_size)
+            .field("max_poll_wait", &self.inner.max_poll_wait)
+            .field("auto_idempotency", &self.inner.auto_idempotency)
+            .field("capture_raw", &self.inner.raw_response.is_some())
+            .field("capture", &self.inner.capture.is_some())
+            .field(
+                "cost_hook",
+                &self.inner.cost_hook.as_ref().map(|_| "Fn(&CostEvent)"),
+            )
+            .field("cost_center", &self.inner.cost_center)
+            .finish()
+    }
+}
+
+/// Masks an API key so only its `fc-` prefix and last four characters remain, e.g. `fc-****1234`.
+pub(crate) fn redact_api_key(api_key: &str) -> String {
+    let (prefix, secret) = match api_key.strip_prefix("fc-") {
+        Some(secret) => ("fc-", secret),
+        None => ("", api_key),
+    };
+
+    let char_count = secret.chars().count();
+    if char_count <= 4 {
+        return format!("{prefix}****");
+    }
+
+    let last_four: String = secret.chars().skip(char_count - 4).collect();
+    format!("{prefix}****{last_four}")
+}
+
+/// Name the SDK identifies itself with in the `User-Agent` and `X-Origin` headers.
+const SDK_NAME: &str = "firecrawl-sdk-rs";
+
+/// Headers identifying the SDK and its version, so server-side dashboards can attribute traffic.
+fn sdk_headers() -> HeaderMap {
+    let version = env!("CARGO_PKG_VERSION");
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        USER_AGENT,
+        HeaderValue::from_str(&format!("{SDK_NAME}/{version}"))
+            .expect("the SDK name and version are valid header values"),
+    );
+    headers.insert("X-Origin", HeaderValue::from_static(SDK_NAME));
+    headers.insert("X-Origin-Version", HeaderValue::from_static(version));
+    headers
+}
+
+/// Header carrying the id Firecrawl support asks for when investigating a request.
+const REQUEST_ID_HEADER: &str = "x-request-id";
+
+fn request_id(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(REQUEST_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Response of endpoints whose body carries nothing of interest, e.g. cancelling a job.
+///
+/// Deserializes from any JSON value, and from the empty body of a `204 No Content` or
+/// `202 Accepted` response.
+///
+/// No endpoint returns it yet; it becomes public with the first one that does.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(test), allow(dead_code))]
+pub(crate) struct EmptyResponse;
+
+impl<'de> serde::Deserialize<'de> for EmptyResponse {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        serde::de::IgnoredAny::deserialize(deserializer)?;
+        Ok(EmptyResponse)
+    }
+}
+
+/// Builds the `Authorization` header value for `api_key`.
+fn bearer_header(api_key: &str) -> Result<HeaderValue, FirecrawlError> {
+    let mut value = HeaderValue::from_str(&format!("Bearer {}", api_key))
+        .map_err(|_| FirecrawlError::configuration("API key is not a valid header value"))?;
+    // Keeps the key out of reqwest's Debug output of requests and headers
+    value.set_sensitive(true);
+    Ok(value)
+}
+
+/// Marks the values of `headers` sensitive, e.g. the credentials of a gateway.
+fn sensitive_headers(mut headers: HeaderMap) -> HeaderMap {
+    for value in headers.values_mut() {
+        value.set_sensitive(true);
+    }
+    headers
+}
+
+/// Returns a client builder using the TLS backend selected by the crate features, accepting
+/// compressed responses with the `compression` feature.
+pub(crate) fn client_builder() -> reqwest::ClientBuilder {
+    let builder = Client::builder();
+    #[cfg(feature = "rustls-tls")]
+    let builder = builder.tls_backend_rustls();
+    #[cfg(all(feature = "native-tls", not(feature = "rustls-tls")))]
+    let builder = builder.tls_backend_native();
+    // Browsers and workers decompress responses themselves
+    #[cfg(all(feature = "compression", not(target_arch = "wasm32")))]
+    let builder = builder.gzip(true).brotli(true);
+    builder
+}
+
+/// Extra time given to a request on top of `ScrapeOptions.timeout`, so the client doesn't give up
+/// before the server reports its own timeout.
+#[cfg(feature = "scrape")]
+const SCRAPE_TIMEOUT_GRACE: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// HTTP timeout for requests waiting on pages loaded with the given `ScrapeOptions.timeout`.
+#[cfg(feature = "scrape")]
+pub(crate) fn scrape_request_timeout(timeout_ms: Option<u32>) -> Option<std::time::Duration> {
+    timeout_ms.map(|ms| std::time::Duration::from_millis(ms.into()) + SCRAPE_TIMEOUT_GRACE)
+}
+
+/// Overrides the client's timeout for this request, if `timeout` is set.
+#[cfg(feature = "scrape")]
+pub(crate) fn apply_timeout(
+    request: RequestBuilder,
+    timeout: Option<std::time::Duration>,
+) -> RequestBuilder {
+    match timeout {
+        Some(timeout) => request.timeout(timeout),
+        None => request,
+    }
+}
+
+/// Why monitoring a job stopped before the job ended, see `FirecrawlApp::wait_for_next_poll`.
+#[cfg(any(feature = "batch", feature = "crawl"))]
+pub(crate) enum PollStop {
+    Cancelled,
+    TimedOut,
+}
+
+#[cfg(any(feature = "batch", feature = "crawl"))]
+impl PollStop {
+    /// Error for the job `id`, monitored since `started`, that stopped at `last_status`.
+    pub(crate) fn into_error(
+        self,
+        id: &str,
+        started: Instant,
+        last_status: PartialStatus,
+    ) -> FirecrawlError {
+        match self {
+            PollStop::Cancelled => FirecrawlError::OperationCancelled(last_status),
+            PollStop::TimedOut => FirecrawlError::PollTimeout {
+                job_id: id.to_string(),
+                elapsed: started.elapsed(),
+                last_status,
+            },
+        }
+    }
 }
 
-pub(crate) const API_VERSION: &str = "v1";
 const CLOUD_API_URL: &str = "https://api.firecrawl.dev";
 
+/// Parses the base URL of a Firecrawl instance, keeping any path prefix without trailing slashes.
+fn parse_api_url(api_url: &str) -> Result<Url, FirecrawlError> {
+    let mut url = Url::parse(api_url).map_err(|e| {
+        FirecrawlError::configuration(format!("API URL {api_url:?} is not a valid URL: {e}"))
+    })?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(FirecrawlError::configuration(format!(
+            "API URL {api_url:?} must use http or https, not {}",
+            url.scheme()
+        )));
+    }
+    if url.query().is_some() || url.fragment().is_some() {
+        return Err(FirecrawlError::configuration(format!(
+            "API URL {api_url:?} must not have a query or fragment"
+        )));
+    }
+
+    let path = url.path().trim_end_matches('/').to_string();
+    url.set_path(&path);
+    Ok(url)
+}
+
+fn is_cloud_api_url(url: &Url) -> bool {
+    url.as_str().trim_end_matches('/') == CLOUD_API_URL
+}
+
 impl FirecrawlApp {
+    fn from_inner(inner: Inner) -> Self {
+        FirecrawlApp {
+            inner: Arc::new(inner),
+        }
+    }
+
+    /// Configuration of this client, copied first if it is shared with clones so that they keep
+    /// theirs.
+    fn inner_mut(&mut self) -> &mut Inner {
+        Arc::make_mut(&mut self.inner)
+    }
+
+    /// Returns a builder to configure the underlying HTTP client.
+    pub fn builder() -> FirecrawlAppBuilder {
+        FirecrawlAppBuilder::default()
+    }
+
     pub fn new(api_key: impl AsRef<str>) -> Result<Self, FirecrawlError> {
         FirecrawlApp::new_selfhosted(CLOUD_API_URL, Some(api_key))
     }
@@ -31,57 +370,564 @@ impl FirecrawlApp {
         api_key: impl AsRef<str>,
         client: Client,
     ) -> Result<Self, FirecrawlError> {
-        Ok(FirecrawlApp {
-            api_key: Some(api_key.as_ref().to_string()),
-            api_url: CLOUD_API_URL.to_string(),
+        bearer_header(api_key.as_ref())?;
+
+        Ok(FirecrawlApp::from_inner(Inner {
+            api_key: Some(api_key.as_ref().into()),
+            api_url: Arc::new(Url::parse(CLOUD_API_URL).expect("the cloud API URL is valid")),
+            api_version: routes::API_VERSION.into(),
             client,
-        })
+            default_headers: Default::default(),
+            observer: None,
+            hooks: Default::default(),
+            middlewares: Default::default(),
+            max_html_size: None,
+            max_poll_wait: None,
+            auto_idempotency: false,
+            raw_response: None,
+            capture: None,
+            cost_hook: None,
+            cost_center: None,
+            rate_limiter: None,
+            prepaid: None,
+            #[cfg(feature = "strict")]
+            reported_fields: Default::default(),
+            #[cfg(feature = "otel")]
+            metrics: Default::default(),
+        }))
     }
 
+    /// Connects to a self-hosted instance with the default [`ClientConfig`], which times
+    /// requests out after 120 seconds.
     pub fn new_selfhosted(
         api_url: impl AsRef<str>,
         api_key: Option<impl AsRef<str>>,
     ) -> Result<Self, FirecrawlError> {
-        FirecrawlApp::new_selfhosted_with_client(api_url, api_key, Client::new())
+        FirecrawlApp::new_selfhosted_with_config(api_url, api_key, ClientConfig::default())
     }
 
+    /// Connects to a self-hosted instance with an HTTP client built from `config`.
+    pub fn new_selfhosted_with_config(
+        api_url: impl AsRef<str>,
+        api_key: Option<impl AsRef<str>>,
+        config: ClientConfig,
+    ) -> Result<Self, FirecrawlError> {
+        FirecrawlApp::new_selfhosted_with_client(api_url, api_key, config.build_client()?)
+    }
+
+    /// Connects to a self-hosted instance through `client`, e.g. one configured with an egress
+    /// proxy or a client certificate for mutual TLS.
+    ///
+    /// The client is used as is, so the timeouts of [`ClientConfig`] don't apply.
     pub fn new_selfhosted_with_client(
         api_url: impl AsRef<str>,
         api_key: Option<impl AsRef<str>>,
         client: Client,
     ) -> Result<Self, FirecrawlError> {
-        let url = api_url.as_ref().to_string();
-
-        if url == CLOUD_API_URL && api_key.is_none() {
-            return Err(FirecrawlError::APIError(
-                "Configuration".to_string(),
-                FirecrawlAPIError {
-                    error: "API key is required for cloud service".to_string(),
-                    details: None,
-                },
+        let url = parse_api_url(api_url.as_ref())?;
+
+        if is_cloud_api_url(&url) && api_key.is_none() {
+            return Err(FirecrawlError::configuration(
+                "API key is required for cloud service",
             ));
         }
 
-        Ok(FirecrawlApp {
-            api_key: api_key.map(|x| x.as_ref().to_string()),
-            api_url: url,
-            client: Client::new(),
+        let api_key: Option<Arc<str>> = api_key.map(|x| x.as_ref().into());
+        if let Some(api_key) = api_key.as_deref() {
+            bearer_header(api_key)?;
+        }
+
+        Ok(FirecrawlApp::from_inner(Inner {
+            api_key,
+            api_url: Arc::new(url),
+            api_version: routes::API_VERSION.into(),
+            client,
+            default_headers: Default::default(),
+            observer: None,
+            hooks: Default::default(),
+            middlewares: Default::default(),
+            max_html_size: None,
+            max_poll_wait: None,
+            auto_idempotency: false,
+            raw_response: None,
+            capture: None,
+            cost_hook: None,
+            cost_center: None,
+            rate_limiter: None,
+            prepaid: None,
+            #[cfg(feature = "strict")]
+            reported_fields: Default::default(),
+            #[cfg(feature = "otel")]
+            metrics: Default::default(),
+        }))
+    }
+
+    /// URL of the Firecrawl instance, e.g. `https://api.firecrawl.dev`.
+    pub fn api_url(&self) -> &str {
+        self.inner.api_url.as_str()
+    }
+
+    /// Whether requests are authenticated with an API key.
+    pub fn has_api_key(&self) -> bool {
+        self.inner.api_key.is_some()
+    }
+
+    /// Version of the API the requests are sent to, `v1` unless changed with
+    /// [`with_api_version`](Self::with_api_version).
+    pub fn api_version(&self) -> &str {
+        &self.inner.api_version
+    }
+
+    /// Sends the requests to `version` of the API, e.g. `v2`, instead of `v1`.
+    ///
+    /// Only the path changes: requests and responses keep the format of the SDK, so this is
+    /// meant for instances serving compatible endpoints under another version. The health
+    /// checks aren't versioned and are unaffected.
+    pub fn with_api_version(mut self, version: impl AsRef<str>) -> Self {
+        self.inner_mut().api_version = version.as_ref().trim_matches('/').into();
+        self
+    }
+
+    /// Returns a copy of this client sending its requests to `version` of the API, to override
+    /// the version for some calls only, e.g. `app.at_api_version("v2").crawl(params)`.
+    ///
+    /// The copy shares the HTTP client and the rest of the configuration, see
+    /// [`with_api_version`](Self::with_api_version).
+    pub fn at_api_version(&self, version: impl AsRef<str>) -> Self {
+        self.clone().with_api_version(version)
+    }
+
+    /// URL of `route` under the API URL, e.g. `{api_url}/v1/crawl/{id}` for
+    /// `routes::crawl_status(id)`. Segments and query are percent-encoded.
+    pub(crate) fn endpoint(&self, route: Route) -> Url {
+        let mut url = Url::clone(&self.inner.api_url);
+        {
+            let mut segments = url
+                .path_segments_mut()
+                .expect("the API URL is an http(s) URL");
+            segments.pop_if_empty();
+            if route.versioned {
+                segments.push(&self.inner.api_version);
+            }
+            segments.extend(&route.segments);
+        }
+        if let Some((name, value)) = &route.query {
+            url.query_pairs_mut().append_pair(name, value);
+        }
+        url
+    }
+
+    /// Sends `headers` with every request, e.g. headers required by a gateway in front of a
+    /// self-hosted instance.
+    ///
+    /// The SDK's own `Content-Type`, `Authorization` and `x-idempotency-key` headers take
+    /// precedence over default headers with the same name. Default headers replace the
+    /// `User-Agent`, `X-Origin` and `X-Origin-Version` headers identifying the SDK.
+    ///
+    /// Their values are marked sensitive, so they don't appear in `Debug` output.
+    pub fn with_default_headers(mut self, headers: HeaderMap) -> Self {
+        self.inner_mut().default_headers = Arc::new(sensitive_headers(headers));
+        self
+    }
+
+    /// Adds `headers` to the default headers, replacing those with the same name, e.g. to trace
+    /// a single call through a gateway with its own `X-Request-Id`:
+    ///
+    /// ```no_run
+    /// # async fn example(app: &firecrawl_sdk::FirecrawlApp) {
+    /// use reqwest::header::{HeaderMap, HeaderValue};
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert("X-Request-Id", HeaderValue::from_static("req-42"));
+    /// let usage = app.clone().with_extra_headers(headers).get_credit_usage().await;
+    /// # }
+    /// ```
+    ///
+    /// As with [`with_default_headers`](Self::with_default_headers), the SDK's own
+    /// `Content-Type`, `Authorization` and `x-idempotency-key` headers take precedence.
+    pub fn with_extra_headers(mut self, headers: HeaderMap) -> Self {
+        let mut merged = HeaderMap::clone(&self.inner.default_headers);
+        merged.extend(sensitive_headers(headers));
+        self.inner_mut().default_headers = Arc::new(merged);
+        self
+    }
+
+    /// Truncates `html` and `raw_html` of every returned document to at most `max_bytes` each, at
+    /// a character boundary, e.g. to bound memory when requesting both.
+    ///
+    /// Truncated documents are flagged in `Document.html_truncated` and `Document.raw_html_truncated`,
+    /// see [`Document::html_variants`](document::Document::html_variants).
+    pub fn with_max_html_size(mut self, max_bytes: usize) -> Self {
+        self.inner_mut().max_html_size = Some(max_bytes);
+        self
+    }
+
+    /// Gives up monitoring a crawl or batch scrape after `max_wait` in total, e.g. for jobs stuck
+    /// in `Scraping` after a worker of a self-hosted instance crashed.
+    ///
+    /// Monitoring then fails with `FirecrawlError::PollTimeout`, carrying the last status. The
+    /// job itself keeps running, so its id can be checked on later.
+    pub fn with_max_poll_wait(mut self, max_wait: std::time::Duration) -> Self {
+        self.inner_mut().max_poll_wait = Some(max_wait);
+        self
+    }
+
+    /// Waits `interval` before polling a job monitored since `started` again.
+    ///
+    /// Stops waiting once `cancel` is cancelled or `with_max_poll_wait` is exceeded, and returns
+    /// why.
+    #[cfg(any(feature = "batch", feature = "crawl"))]
+    pub(crate) async fn wait_for_next_poll(
+        &self,
+        interval: std::time::Duration,
+        cancel: &CancellationToken,
+        started: Instant,
+    ) -> Option<PollStop> {
+        if cancel.is_cancelled() {
+            return Some(PollStop::Cancelled);
+        }
+
+        let mut wait = interval;
+        let mut times_out = false;
+        if let Some(max_wait) = self.inner.max_poll_wait {
+            let remaining = max_wait.saturating_sub(started.elapsed());
+            if remaining <= wait {
+                wait = remaining;
+                times_out = true;
+            }
+        }
+
+        tokio::select! {
+            _ = time::delay(wait) => times_out.then_some(PollStop::TimedOut),
+            _ = cancel.cancelled() => Some(PollStop::Cancelled),
+        }
+    }
+
+    /// Applies `with_max_html_size` to `documents`.
+    #[cfg(feature = "scrape")]
+    pub(crate) fn cap_html(&self, documents: &mut [document::Document]) {
+        if let Some(max_bytes) = self.inner.max_html_size {
+            for document in documents {
+                document.truncate_html(max_bytes);
+            }
+        }
+    }
+
+    /// Calls `observer` before every request and after every response, e.g. for audit logging.
+    ///
+    /// See [`RequestInfo`] for what is reported. Replaces any previously registered observer.
+    pub fn with_observer(
+        mut self,
+        observer: impl Fn(&RequestInfo<'_>) + Send + Sync + 'static,
+    ) -> Self {
+        self.inner_mut().observer = Some(Arc::new(observer));
+        self
+    }
+
+    /// Calls `hook` around every request. Hooks run in the order they were registered, after the
+    /// observer.
+    pub fn with_hook(mut self, hook: Arc<dyn RequestHook>) -> Self {
+        Arc::make_mut(&mut self.inner_mut().hooks).push(hook);
+        self
+    }
+
+    /// Passes `message` to the hooks, or prints it to stderr if there are none.
+    #[cfg(any(
+        feature = "batch",
+        feature = "crawl",
+        feature = "extract",
+        feature = "llmstxt",
+        feature = "strict"
+    ))]
+    fn warn(&self, message: &str) {
+        if self.inner.hooks.is_empty() {
+            eprintln!("Warning: {message}");
+        }
+        for hook in self.inner.hooks.iter() {
+            hook.on_warning(message);
+        }
+    }
+
+    /// Passes every request to `middleware` right before it is sent, e.g. to sign it or to add
+    /// headers to it. Middlewares run in the order they were registered, before the observer and
+    /// hooks, which see the modified request.
+    pub fn with_request_middleware(
+        mut self,
+        middleware: impl Fn(RequestBuilder) -> RequestBuilder + Send + Sync + 'static,
+    ) -> Self {
+        Arc::make_mut(&mut self.inner_mut().middlewares).push(Arc::new(middleware));
+        self
+    }
+
+    /// Calls `hook` whenever the SDK observes credits being consumed: after each successful
+    /// scrape, with an estimate, and when crawls and batch scrapes end, with the credits reported
+    /// by the API, including the ones that failed.
+    ///
+    /// Other endpoints don't report their credits, and the scrapes of a crawl or batch scrape are
+    /// only counted in its total.
+    pub fn with_cost_hook(mut self, hook: impl Fn(&CostEvent<'_>) + Send + Sync + 'static) -> Self {
+        self.inner_mut().cost_hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// Labels the [`CostEvent`]s of this client with `cost_center`, e.g. the name of the service
+    /// using it, unless the parameters of an operation set their own.
+    pub fn with_cost_center(mut self, cost_center: impl AsRef<str>) -> Self {
+        self.inner_mut().cost_center = Some(cost_center.as_ref().into());
+        self
+    }
+
+    /// Notifies the hook set with `with_cost_hook` of `credits` consumed by `operation`.
+    #[cfg(feature = "scrape")]
+    pub(crate) fn report_cost(
+        &self,
+        operation: CostOperation,
+        targets: &[String],
+        credits: u64,
+        succeeded: bool,
+        cost_center: Option<&str>,
+    ) {
+        let Some(hook) = self.inner.cost_hook.as_ref() else {
+            return;
+        };
+        hook(&CostEvent {
+            operation,
+            targets,
+            credits,
+            estimated: operation == CostOperation::Scrape,
+            succeeded,
+            cost_center: cost_center.or(self.inner.cost_center.as_deref()),
+        });
+    }
+
+    /// Records metrics with `meter` instead of the meter of the global provider set when the
+    /// client was created.
+    ///
+    /// | Instrument | Kind | Attributes |
+    /// |---|---|---|
+    /// | `firecrawl.requests` | counter | `firecrawl.endpoint`, `http.request.method` |
+    /// | `firecrawl.errors` | counter | the above, `http.response.status_code` if a response arrived |
+    /// | `firecrawl.request.duration` | histogram, seconds | the above |
+    /// | `firecrawl.credits_used` | counter | `firecrawl.endpoint` |
+    ///
+    /// Errors are requests that failed without a response or got a `4xx`/`5xx` status. Credits
+    /// are recorded once crawls and batch scrapes complete, since other endpoints don't report
+    /// them.
+    #[cfg(feature = "otel")]
+    pub fn with_meter(mut self, meter: &opentelemetry::metrics::Meter) -> Self {
+        self.inner_mut().metrics = metrics::Metrics::new(meter);
+        self
+    }
+
+    /// Sends a request built from `self.inner.client` through the middlewares, notifying the observer
+    /// and hooks and recording metrics. Every endpoint sends its requests through here.
+    async fn send_request(&self, request: RequestBuilder) -> Result<Response, reqwest::Error> {
+        let request = self
+            .inner
+            .middlewares
+            .iter()
+            .fold(request, |request, middleware| middleware(request));
+        if let Some(rate_limiter) = self.inner.rate_limiter.as_deref() {
+            let prepaid = self
+                .inner
+                .prepaid
+                .as_deref()
+                .is_some_and(|prepaid| prepaid.swap(false, Ordering::AcqRel));
+            if !prepaid {
+                rate_limiter.acquire().await;
+            }
+        }
+        if self.inner.observer.is_none()
+            && self.inner.hooks.is_empty()
+            && self.inner.capture.is_none()
+            && !cfg!(feature = "otel")
+        {
+            return request.send().await;
+        }
+
+        let request = request.build()?;
+        let method = request.method().clone();
+        let url = request.url().clone();
+        let body = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .map(|body| body.to_vec());
+
+        if let Some(observer) = self.inner.observer.as_ref() {
+            observer(&RequestInfo {
+                method: &method,
+                url: &url,
+                body: body.as_deref(),
+                status: None,
+                elapsed: None,
+            });
+        }
+        if !self.inner.hooks.is_empty() {
+            let body_json = body
+                .as_deref()
+                .and_then(|body| serde_json::from_slice::<serde_json::Value>(body).ok());
+            for hook in self.inner.hooks.iter() {
+                hook.on_request(&method, &url, body_json.as_ref());
+            }
+        }
+
+        let sent_at = time::now();
+        let started = Instant::now();
+        let result = self.inner.client.execute(request).await;
+        let elapsed = started.elapsed();
+        let status = match &result {
+            Ok(response) => Some(response.status().as_u16()),
+            Err(e) => e.status().map(|s| s.as_u16()),
+        };
+
+        if let Some(observer) = self.inner.observer.as_ref() {
+            observer(&RequestInfo {
+                method: &method,
+                url: &url,
+                body: body.as_deref(),
+                status,
+                elapsed: Some(elapsed),
+            });
+        }
+        for hook in self.inner.hooks.iter() {
+            hook.on_response(status, elapsed);
+        }
+        if let Some(capture) = self.inner.capture.as_deref() {
+            capture.record_request(&method, &url, body.as_deref(), sent_at, status);
+        }
+        #[cfg(feature = "otel")]
+        self.inner
+            .metrics
+            .record_request(&method, &url, status, elapsed);
+
+        result
+    }
+
+    /// Sends a generated `x-idempotency-key` with the requests that accept one but were given
+    /// none: scrapes, maps, searches, crawls and batch scrapes.
+    ///
+    /// The key is generated once per call, so it dedupes a request re-sent by the HTTP client or
+    /// a proxy, but not a call made again. Pass the same key to e.g.
+    /// [`scrape_url_with_idempotency`](Self::scrape_url_with_idempotency) for those.
+    pub fn with_auto_idempotency(mut self, enabled: bool) -> Self {
+        self.inner_mut().auto_idempotency = enabled;
+        self
+    }
+
+    /// Keeps the JSON body of the last successful response, returned by
+    /// [`last_raw_response`](Self::last_raw_response). Off by default.
+    ///
+    /// A debugging aid, e.g. to see the fields a self-hosted fork sends that the typed results
+    /// drop. The body stays in memory until the next response replaces it, which for crawl
+    /// status pages can be megabytes of HTML. Clones made afterwards share the captured body.
+    pub fn with_capture_raw(mut self, enabled: bool) -> Self {
+        self.inner_mut().raw_response = enabled.then(Default::default);
+        self
+    }
+
+    /// JSON body of the last successful response, if [`with_capture_raw`](Self::with_capture_raw)
+    /// is on and a response arrived since.
+    pub fn last_raw_response(&self) -> Option<serde_json::Value> {
+        let raw_response = self.inner.raw_response.as_deref()?;
+        raw_response
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Sends at most `max_requests` requests every `per`, e.g. `with_rate_limit(10, 1s)` to stay
+    /// under the rate limit of the plan when many tasks share the client. Requests beyond the
+    /// limit wait for their turn rather than fail.
+    ///
+    /// The limit is a token bucket: up to `max_requests` requests are sent at once, then one
+    /// every `per / max_requests`. Clones made afterwards share it, so the limit holds for all of
+    /// them together.
+    pub fn with_rate_limit(mut self, max_requests: u32, per: std::time::Duration) -> Self {
+        self.inner_mut().rate_limiter =
+            Some(Arc::new(rate_limit::TokenBucket::new(max_requests, per)));
+        self
+    }
+
+    /// Takes a token of the rate limit without waiting, returning a client that sends its next
+    /// request at once with it, or how long until a token is available.
+    ///
+    /// This lets callers fail fast, or try another client, instead of queueing behind the limit
+    /// of [`with_rate_limit`](Self::with_rate_limit). Tokens are handed out in order, so this
+    /// fails while other requests are waiting for one. Only the next request of the returned
+    /// client uses the token, and it is lost if no request is sent. Without a rate limit, returns
+    /// a clone of the client.
+    pub fn try_acquire(&self) -> Result<FirecrawlApp, std::time::Duration> {
+        let Some(rate_limiter) = self.inner.rate_limiter.as_deref() else {
+            return Ok(self.clone());
+        };
+        rate_limiter.try_acquire()?;
+
+        let mut app = self.clone();
+        app.inner_mut().prepaid = Some(Arc::new(AtomicBool::new(true)));
+        Ok(app)
+    }
+
+    /// Keeps the last 20 requests and responses, returned by
+    /// [`last_exchanges`](Self::last_exchanges). Off by default.
+    ///
+    /// A debugging aid, e.g. to send Firecrawl support the exact request of a scrape with
+    /// unexpected output rather than reconstructing it from the `ScrapeOptions`. The API key is
+    /// redacted from the captured data, and responses are truncated to 8 KiB. Clones made
+    /// afterwards share the captured exchanges.
+    pub fn enable_capture(mut self, enabled: bool) -> Self {
+        let capture =
+            enabled.then(|| Arc::new(capture::Capture::new(self.inner.api_key.as_deref())));
+        self.inner_mut().capture = capture;
+        self
+    }
+
+    /// Requests and responses captured since [`enable_capture`](Self::enable_capture), oldest
+    /// first. Empty if capture is off.
+    pub fn last_exchanges(&self) -> Vec<ExchangeRecord> {
+        self.inner
+            .capture
+            .as_deref()
+            .map(capture::Capture::exchanges)
+            .unwrap_or_default()
+    }
+
+    /// `idempotency_key`, or a generated one if it is `None` and `with_auto_idempotency` is on.
+    #[cfg(feature = "scrape")]
+    fn idempotency_key(&self, idempotency_key: Option<String>) -> Option<String> {
+        idempotency_key.or_else(|| {
+            self.inner
+                .auto_idempotency
+                .then(|| uuid::Uuid::new_v4().to_string())
         })
     }
 
-    fn prepare_headers(&self, idempotency_key: Option<&String>) -> reqwest::header::HeaderMap {
-        let mut headers = reqwest::header::HeaderMap::new();
-        headers.insert("Content-Type", "application/json".parse().unwrap());
-        if let Some(api_key) = self.api_key.as_ref() {
-            headers.insert(
-                "Authorization",
-                format!("Bearer {}", api_key).parse().unwrap(),
-            );
+    fn prepare_headers(
+        &self,
+        idempotency_key: Option<&String>,
+    ) -> Result<HeaderMap, FirecrawlError> {
+        let mut headers = sdk_headers();
+        headers.extend(HeaderMap::clone(&self.inner.default_headers));
+        headers.insert("Content-Type", HeaderValue::from_static("application/json"));
+        if let Some(api_key) = self.inner.api_key.as_ref() {
+            headers.insert("Authorization", bearer_header(api_key)?);
         }
         if let Some(key) = idempotency_key {
-            headers.insert("x-idempotency-key", key.parse().unwrap());
+            let value = HeaderValue::from_str(key).map_err(|_| {
+                FirecrawlError::configuration("Idempotency key is not a valid header value")
+            })?;
+            headers.insert("x-idempotency-key", value);
         }
-        headers
+        Ok(headers)
+    }
+
+    /// Reads the body of `response`, recording it with the exchange if `enable_capture` is on.
+    async fn read_body(&self, response: Response) -> Result<impl AsRef<[u8]>, reqwest::Error> {
+        let url = response.url().clone();
+        let body = response.bytes().await?;
+        if let Some(capture) = self.inner.capture.as_deref() {
+            capture.record_response(&url, &body);
+        }
+        Ok(body)
     }
 
     async fn handle_response<T: DeserializeOwned>(
@@ -89,18 +935,51 @@ impl FirecrawlApp {
         response: Response,
         action: impl AsRef<str>,
     ) -> Result<T, FirecrawlError> {
+        self.handle_response_with_request_id(response, action)
+            .await
+            .map(|(value, _)| value)
+    }
+
+    /// Like `handle_response`, also returning the request id of a successful response.
+    async fn handle_response_with_request_id<T: DeserializeOwned>(
+        &self,
+        response: Response,
+        action: impl AsRef<str>,
+    ) -> Result<(T, Option<String>), FirecrawlError> {
         let status = response.status();
+        // Headers are gone once the body is consumed
+        let request_id = request_id(response.headers());
 
         if !status.is_success() {
+            let rate_limit = RateLimitInfo::from_headers(response.headers()).map(Box::new);
+
+            if status == StatusCode::TOO_MANY_REQUESTS {
+                return Err(FirecrawlError::RateLimited {
+                    action: action.as_ref().to_string(),
+                    retry_after: rate_limit::retry_after_from_headers(response.headers()),
+                    rate_limit,
+                    request_id,
+                });
+            }
+
             // For non-successful status codes, try to extract error details
-            match response.json::<FirecrawlAPIError>().await {
-                Ok(api_error) => {
+            let api_error = match self.inner.capture {
+                Some(_) => self.read_body(response).await.ok().and_then(|body| {
+                    serde_json::from_slice::<FirecrawlAPIError>(body.as_ref()).ok()
+                }),
+                None => response.json::<FirecrawlAPIError>().await.ok(),
+            };
+            match api_error {
+                Some(mut api_error) => {
+                    api_error.status_code = Some(status.as_u16());
+                    api_error.rate_limit = rate_limit;
+                    api_error.request_id = request_id.or(api_error.request_id);
                     return Err(FirecrawlError::APIError(
                         action.as_ref().to_string(),
                         api_error,
                     ));
                 }
-                Err(_) => {
+                None => {
                     return Err(FirecrawlError::HttpRequestFailed(
                         action.as_ref().to_string(),
                         status.as_u16(),
@@ -110,13 +989,817 @@ impl FirecrawlApp {
             }
         }
 
-        // For successful responses, directly deserialize to T
-        response.json::<T>().await.map_err(|e| {
+        if matches!(status, StatusCode::NO_CONTENT | StatusCode::ACCEPTED) {
+            // Cancel and other administrative endpoints may answer without a body, read as
+            // `null` so only targets like `EmptyResponse` accept it
+            let body = self
+                .read_body(response)
+                .await
+                .map_err(|e| FirecrawlError::HttpError(action.as_ref().to_string(), e))?;
+            let raw = if body.as_ref().trim_ascii().is_empty() {
+                serde_json::Value::Null
+            } else {
+                serde_json::from_slice(body.as_ref()).map_err(FirecrawlError::ResponseParseError)?
+            };
+            if let Some(raw_response) = self.inner.raw_response.as_deref() {
+                *raw_response.lock().unwrap_or_else(PoisonError::into_inner) = Some(raw.clone());
+            }
+            let value = serde_json::from_value(raw).map_err(FirecrawlError::ResponseParseError)?;
+            return Ok((value, request_id));
+        }
+
+        let body_error = |e: reqwest::Error| {
             if e.is_decode() {
                 FirecrawlError::ResponseParseErrorText(e)
             } else {
                 FirecrawlError::HttpError(action.as_ref().to_string(), e)
             }
-        })
+        };
+        if self.inner.raw_response.is_none() && self.inner.capture.is_none() {
+            // For successful responses, directly deserialize to T
+            let value = response.json::<T>().await.map_err(body_error)?;
+            return Ok((value, request_id));
+        }
+
+        let raw: serde_json::Value = match self.inner.capture {
+            Some(_) => {
+                let body = self.read_body(response).await.map_err(body_error)?;
+                serde_json::from_slice(body.as_ref()).map_err(FirecrawlError::ResponseParseError)?
+            }
+            None => response.json().await.map_err(body_error)?,
+        };
+        if let Some(raw_response) = self.inner.raw_response.as_deref() {
+            *raw_response.lock().unwrap_or_else(PoisonError::into_inner) = Some(raw.clone());
+        }
+        let value = serde_json::from_value(raw).map_err(FirecrawlError::ResponseParseError)?;
+        Ok((value, request_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debug_redacts_api_key() {
+        let app = FirecrawlApp::new("fc-0123456789abcdef1234").unwrap();
+        let debug = format!("{:?}", app);
+
+        assert!(!debug.contains("fc-0123456789abcdef1234"));
+        assert!(!debug.contains("0123456789abcdef"));
+        assert!(debug.contains(r#"api_key: Some("fc-****1234")"#));
+
+        let builder = FirecrawlApp::builder().api_key("fc-0123456789abcdef1234");
+        assert!(!format!("{:?}", builder).contains("0123456789abcdef"));
+
+        // Default headers may carry the credentials of a gateway
+        let mut headers = HeaderMap::new();
+        headers.insert("X-Gateway-Key", HeaderValue::from_static("gw-secret-1"));
+        let mut extra = HeaderMap::new();
+        extra.insert("X-Gateway-Token", HeaderValue::from_static("gw-secret-2"));
+        let app = app.with_default_headers(headers).with_extra_headers(extra);
+        let debug = format!("{:?}", app);
+        assert!(debug.contains("x-gateway-key"), "{debug}");
+        assert!(!debug.contains("gw-secret"), "{debug}");
+
+        let builder = FirecrawlApp::builder()
+            .api_key("fc-0123456789abcdef1234")
+            .default_header("X-Gateway-Key", "gw-secret-1");
+        let debug = format!("{:?}", builder);
+        assert!(debug.contains("X-Gateway-Key"), "{debug}");
+        assert!(!debug.contains("gw-secret"), "{debug}");
+        let debug = format!("{:?}", builder.build().unwrap());
+        assert!(!debug.contains("gw-secret"), "{debug}");
+    }
+
+    #[test]
+    fn test_redact_api_key() {
+        assert_eq!(redact_api_key("fc-abcdef1234"), "fc-****1234");
+        assert_eq!(redact_api_key("abcdef1234"), "****1234");
+        assert_eq!(redact_api_key("fc-1234"), "fc-****");
+        assert_eq!(redact_api_key(""), "****");
+    }
+
+    #[test]
+    fn test_authorization_header_is_sensitive() {
+        let app = FirecrawlApp::new("fc-0123456789abcdef1234").unwrap();
+        let headers = app.prepare_headers(None).unwrap();
+
+        assert!(headers["Authorization"].is_sensitive());
+        assert!(!format!("{:?}", headers).contains("0123456789abcdef"));
+    }
+
+    fn is_configuration_error<T>(result: &Result<T, FirecrawlError>) -> bool {
+        matches!(result, Err(FirecrawlError::APIError(msg, _)) if msg == "Configuration")
+    }
+
+    #[test]
+    fn test_default_headers_are_merged() {
+        let mut defaults = HeaderMap::new();
+        defaults.insert("X-Org-Id", HeaderValue::from_static("org-123"));
+        defaults.insert("Content-Type", HeaderValue::from_static("text/plain"));
+        defaults.insert("Authorization", HeaderValue::from_static("Basic gateway"));
+
+        let app = FirecrawlApp::new_selfhosted("http://localhost:3002", Some("fc-test"))
+            .unwrap()
+            .with_default_headers(defaults);
+        let headers = app.prepare_headers(Some(&"key-1".to_string())).unwrap();
+
+        assert_eq!(headers["X-Org-Id"], "org-123");
+        // The SDK's own headers win on conflict
+        assert_eq!(headers["Content-Type"], "application/json");
+        assert_eq!(headers["Authorization"], "Bearer fc-test");
+        assert_eq!(headers.get_all("Authorization").iter().count(), 1);
+        assert_eq!(headers["x-idempotency-key"], "key-1");
+    }
+
+    #[test]
+    fn test_extra_headers_precedence() {
+        let mut defaults = HeaderMap::new();
+        defaults.insert("X-Org-Id", HeaderValue::from_static("org-123"));
+        defaults.insert("X-Request-Id", HeaderValue::from_static("req-default"));
+        let app = FirecrawlApp::new_selfhosted("http://localhost:3002", Some("fc-test"))
+            .unwrap()
+            .with_default_headers(defaults);
+
+        let mut extra = HeaderMap::new();
+        extra.insert("X-Request-Id", HeaderValue::from_static("req-42"));
+        extra.insert("Authorization", HeaderValue::from_static("Basic gateway"));
+        extra.insert("x-idempotency-key", HeaderValue::from_static("key-extra"));
+        let call = app.clone().with_extra_headers(extra);
+        let headers = call.prepare_headers(Some(&"key-1".to_string())).unwrap();
+
+        // Extra headers replace default headers, but not the SDK's own
+        assert_eq!(headers["X-Request-Id"], "req-42");
+        assert_eq!(headers.get_all("X-Request-Id").iter().count(), 1);
+        assert_eq!(headers["X-Org-Id"], "org-123");
+        assert_eq!(headers["Authorization"], "Bearer fc-test");
+        assert_eq!(headers.get_all("Authorization").iter().count(), 1);
+        assert_eq!(headers["x-idempotency-key"], "key-1");
+
+        // The client they were added to is unchanged
+        let headers = app.prepare_headers(None).unwrap();
+        assert_eq!(headers["X-Request-Id"], "req-default");
+    }
+
+    #[test]
+    fn test_invalid_header_values_do_not_panic() {
+        assert!(is_configuration_error(&FirecrawlApp::new("fc-bad\nkey")));
+        assert!(is_configuration_error(&FirecrawlApp::new_selfhosted(
+            "http://localhost:3002",
+            Some("fc-bad\rkey"),
+        )));
+        assert!(is_configuration_error(&FirecrawlApp::new_with_client(
+            "fc-bad\0key",
+            Client::new(),
+        )));
+
+        let app = FirecrawlApp::new_selfhosted("http://localhost:3002", None::<String>).unwrap();
+        assert!(is_configuration_error(
+            &app.prepare_headers(Some(&"bad\nkey".to_string()))
+        ));
+    }
+
+    #[test]
+    fn test_api_url_is_normalized() {
+        let cases = [
+            (
+                "http://localhost:3002",
+                "http://localhost:3002/v1/team/credit-usage",
+            ),
+            (
+                "https://firecrawl.internal/",
+                "https://firecrawl.internal/v1/team/credit-usage",
+            ),
+            (
+                "https://gateway.internal/firecrawl",
+                "https://gateway.internal/firecrawl/v1/team/credit-usage",
+            ),
+            (
+                "https://gateway.internal/firecrawl//",
+                "https://gateway.internal/firecrawl/v1/team/credit-usage",
+            ),
+        ];
+
+        for (api_url, expected) in cases {
+            let app = FirecrawlApp::new_selfhosted(api_url, None::<String>).unwrap();
+            assert_eq!(
+                app.endpoint(routes::credit_usage()).as_str(),
+                expected,
+                "{api_url}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_clone_shares_configuration() {
+        let app = FirecrawlApp::new_selfhosted("http://localhost:3002", Some("fc-test")).unwrap();
+        assert_eq!(app.api_url(), "http://localhost:3002/");
+        assert!(app.has_api_key());
+
+        let clone = app.clone();
+        assert!(Arc::ptr_eq(&app.inner, &clone.inner));
+
+        // Configuring a clone leaves the others as they are
+        let clone = clone.with_max_html_size(1024);
+        assert!(!Arc::ptr_eq(&app.inner, &clone.inner));
+        assert!(Arc::ptr_eq(&app.inner.api_url, &clone.inner.api_url));
+        assert_eq!(app.inner.max_html_size, None);
+        assert_eq!(clone.inner.max_html_size, Some(1024));
+
+        let app = FirecrawlApp::new_selfhosted("http://localhost:3002", None::<String>).unwrap();
+        assert!(!app.has_api_key());
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_shared_by_clones() {
+        use std::time::Duration;
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/team/credit-usage"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "data": { "remaining_credits": 42 }
+            })))
+            .expect(25)
+            .mount(&server)
+            .await;
+
+        // A burst of 5 requests, then 20 per second
+        let app = FirecrawlApp::new_selfhosted(server.uri(), Some("fc-test"))
+            .unwrap()
+            .with_rate_limit(5, Duration::from_millis(250));
+        let started = Instant::now();
+        let tasks: Vec<_> = (0..25)
+            .map(|_| {
+                let app = app.clone();
+                tokio::spawn(async move { app.get_credit_usage().await.unwrap() })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        // The 20 requests beyond the burst take a second at 20 per second, which would be
+        // instant if each clone had its own limit
+        let elapsed = started.elapsed();
+        assert!(elapsed >= Duration::from_millis(950), "{elapsed:?}");
+        assert!(elapsed < Duration::from_secs(3), "{elapsed:?}");
+    }
+
+    #[test]
+    fn test_invalid_api_url() {
+        for api_url in [
+            "localhost:3002",
+            "firecrawl.internal",
+            "ftp://firecrawl.internal",
+            "https://firecrawl.internal/?token=1",
+        ] {
+            let result = FirecrawlApp::new_selfhosted(api_url, None::<String>);
+            assert!(is_configuration_error(&result), "{api_url}");
+        }
+
+        let error = FirecrawlApp::new_selfhosted("ftp://firecrawl.internal", None::<String>)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("must use http or https"), "{error}");
+        assert!(
+            FirecrawlApp::new_selfhosted("https://api.firecrawl.dev/", None::<String>).is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_api_url_with_path_prefix() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/firecrawl/v1/team/credit-usage"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "data": { "remaining_credits": 1 }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let api_url = format!("{}/firecrawl/", server.uri());
+        let app = FirecrawlApp::new_selfhosted(api_url, Some("fc-test")).unwrap();
+        assert!(app.get_credit_usage().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_api_version_overrides() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let server = MockServer::start().await;
+        for version in ["v1", "v2"] {
+            Mock::given(method("GET"))
+                .and(path(format!("/{version}/team/credit-usage")))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "success": true,
+                    "data": { "remaining_credits": 1 }
+                })))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        // Per call, leaving the client on v1
+        let app = FirecrawlApp::new_selfhosted(server.uri(), Some("fc-test")).unwrap();
+        assert!(app.at_api_version("v2").get_credit_usage().await.is_ok());
+        assert_eq!(app.api_version(), "v1");
+        assert!(app.get_credit_usage().await.is_ok());
+
+        // For every call, except the unversioned routes
+        let app = app.with_api_version("/v2/");
+        assert_eq!(app.api_version(), "v2");
+        assert_eq!(
+            app.endpoint(routes::credit_usage()).path(),
+            "/v2/team/credit-usage"
+        );
+        assert_eq!(app.endpoint(routes::health()).path(), "/health");
+        assert_eq!(
+            app.endpoint(routes::custom("/v1/scrape")).path(),
+            "/v1/scrape"
+        );
+        assert_eq!(
+            app.endpoint(routes::custom("team/queue-status")).path(),
+            "/v2/team/queue-status"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_selfhosted_with_custom_client() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{header, method, path},
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/team/credit-usage"))
+            .and(header("x-egress-token", "egress-secret"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "data": { "remaining_credits": 1 }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-egress-token", HeaderValue::from_static("egress-secret"));
+        let client = Client::builder().default_headers(headers).build().unwrap();
+        let app = FirecrawlApp::new_selfhosted_with_client(server.uri(), Some("fc-test"), client)
+            .unwrap();
+        assert_eq!(app.api_url(), format!("{}/", server.uri()));
+        assert!(app.get_credit_usage().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_empty_response_bodies() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let server = MockServer::start().await;
+        for (route, response) in [
+            ("/no-content", ResponseTemplate::new(204)),
+            ("/ok-empty", ResponseTemplate::new(200)),
+            ("/accepted-empty", ResponseTemplate::new(202)),
+            (
+                "/accepted",
+                ResponseTemplate::new(202).set_body_json(serde_json::json!({
+                    "success": true,
+                    "id": "job-1"
+                })),
+            ),
+        ] {
+            Mock::given(method("DELETE"))
+                .and(path(route))
+                .respond_with(response)
+                .mount(&server)
+                .await;
+        }
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let send = |route: &str| {
+            app.inner
+                .client
+                .delete(format!("{}{route}", server.uri()))
+                .send()
+        };
+
+        let response = send("/no-content").await.unwrap();
+        let result = app
+            .handle_response::<EmptyResponse>(response, "cancel")
+            .await;
+        assert_eq!(result.unwrap(), EmptyResponse);
+
+        let response = send("/accepted-empty").await.unwrap();
+        let result = app
+            .handle_response::<EmptyResponse>(response, "cancel")
+            .await;
+        assert_eq!(result.unwrap(), EmptyResponse);
+
+        // An empty body is only expected without content, not from a plain 200
+        let response = send("/ok-empty").await.unwrap();
+        let result = app
+            .handle_response::<EmptyResponse>(response, "cancel")
+            .await;
+        assert!(
+            matches!(result, Err(FirecrawlError::ResponseParseErrorText(_))),
+            "{result:?}"
+        );
+
+        // Types expecting data still fail on an empty body
+        let response = send("/no-content").await.unwrap();
+        let result = app
+            .handle_response::<serde_json::Map<String, serde_json::Value>>(response, "cancel")
+            .await;
+        assert!(
+            matches!(result, Err(FirecrawlError::ResponseParseError(_))),
+            "{result:?}"
+        );
+
+        let response = send("/accepted").await.unwrap();
+        let body: serde_json::Value = app.handle_response(response, "start job").await.unwrap();
+        assert_eq!(body["id"], "job-1");
+        let response = send("/accepted").await.unwrap();
+        let result = app
+            .handle_response::<EmptyResponse>(response, "start job")
+            .await;
+        assert_eq!(result.unwrap(), EmptyResponse);
+    }
+
+    #[tokio::test]
+    async fn test_sdk_identification_headers() {
+        use wiremock::{Mock, MockServer, ResponseTemplate, matchers::method};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "data": { "remaining_credits": 1 }
+            })))
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), Some("fc-test")).unwrap();
+        app.get_credit_usage().await.unwrap();
+        let app = FirecrawlApp::builder()
+            .api_url(server.uri())
+            .api_key("fc-test")
+            .default_header("User-Agent", "my-crawler/2.0")
+            .default_header("X-Origin", "my-crawler")
+            .build()
+            .unwrap();
+        app.get_credit_usage().await.unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let identification = |i: usize| {
+            ["user-agent", "x-origin", "x-origin-version"]
+                .map(|name| requests[i].headers[name].to_str().unwrap().to_string())
+        };
+        let version = env!("CARGO_PKG_VERSION");
+        assert_eq!(
+            identification(0),
+            [
+                format!("firecrawl-sdk-rs/{version}"),
+                "firecrawl-sdk-rs".to_string(),
+                version.to_string()
+            ]
+        );
+        assert_eq!(
+            identification(1),
+            [
+                "my-crawler/2.0".to_string(),
+                "my-crawler".to_string(),
+                version.to_string()
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_info_from_429() {
+        use std::time::{Duration, UNIX_EPOCH};
+        use wiremock::{Mock, MockServer, ResponseTemplate, matchers::method};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(429)
+                    .insert_header("x-ratelimit-limit", "20")
+                    .insert_header("x-ratelimit-remaining", "0")
+                    .insert_header("x-ratelimit-reset", "1700000043")
+                    .insert_header("retry-after", "43")
+                    .set_body_json(serde_json::json!({
+                        "success": false,
+                        "error": "Rate limit exceeded. Consumed (req/min): 21, Remaining (req/min): 0."
+                    })),
+            )
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), Some("fc-test")).unwrap();
+        let error = app.get_credit_usage().await.unwrap_err();
+
+        assert!(error.is_rate_limited());
+        assert!(matches!(
+            &error,
+            FirecrawlError::RateLimited { action, retry_after: Some(d), .. }
+                if action == "check credit usage" && *d == Duration::from_secs(43)
+        ));
+        assert_eq!(
+            error.rate_limit_info(),
+            Some(&RateLimitInfo {
+                limit: Some(20),
+                remaining: Some(0),
+                reset_at: Some(UNIX_EPOCH + Duration::from_secs(1_700_000_043)),
+            })
+        );
+        // The reset time is in the past
+        assert_eq!(
+            error.rate_limit_info().unwrap().wait_duration(),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_client_with_selected_tls_backend() {
+        use wiremock::{Mock, MockServer, ResponseTemplate, matchers::method};
+
+        assert!(FirecrawlApp::new("fc-test").is_ok());
+        assert!(FirecrawlApp::builder().api_key("fc-test").build().is_ok());
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "data": { "remaining_credits": 1 }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), Some("fc-test")).unwrap();
+        assert!(app.get_credit_usage().await.is_ok());
+    }
+
+    #[cfg(all(feature = "scrape", feature = "map", feature = "crawl"))]
+    #[tokio::test]
+    async fn test_hook_called_for_every_endpoint() {
+        use reqwest::{Method, Url};
+        use std::{
+            sync::Mutex,
+            sync::atomic::{AtomicUsize, Ordering},
+            time::Duration,
+        };
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        #[derive(Default)]
+        struct CountingHook {
+            requests: Mutex<Vec<(String, String, Option<serde_json::Value>)>>,
+            responses: AtomicUsize,
+        }
+
+        impl RequestHook for CountingHook {
+            fn on_request(
+                &self,
+                method: &Method,
+                url: &Url,
+                body_json: Option<&serde_json::Value>,
+            ) {
+                self.requests.lock().unwrap().push((
+                    method.to_string(),
+                    url.path().to_string(),
+                    body_json.map(|b| b["url"].clone()),
+                ));
+            }
+
+            fn on_response(&self, status: Option<u16>, _elapsed: Duration) {
+                assert_eq!(status, Some(200));
+                self.responses.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/scrape"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "data": {
+                    "markdown": "# Example",
+                    "metadata": { "sourceURL": "https://example.com", "statusCode": 200 }
+                }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/map"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "links": ["https://example.com/about"]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/crawl/crawl-123"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "status": "completed",
+                "total": 0,
+                "completed": 0,
+                "creditsUsed": 0,
+                "expiresAt": "2025-01-02T03:04:05.000Z",
+                "data": []
+            })))
+            .mount(&server)
+            .await;
+
+        let hook = Arc::new(CountingHook::default());
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>)
+            .unwrap()
+            .with_hook(hook.clone());
+
+        app.scrape_url("https://example.com", None).await.unwrap();
+        app.map_url("https://example.com", None).await.unwrap();
+        app.check_crawl_status("crawl-123").await.unwrap();
+
+        assert_eq!(
+            *hook.requests.lock().unwrap(),
+            vec![
+                (
+                    "POST".to_string(),
+                    "/v1/scrape".to_string(),
+                    Some(serde_json::json!("https://example.com"))
+                ),
+                (
+                    "POST".to_string(),
+                    "/v1/map".to_string(),
+                    Some(serde_json::json!("https://example.com"))
+                ),
+                ("GET".to_string(), "/v1/crawl/crawl-123".to_string(), None),
+            ]
+        );
+        assert_eq!(hook.responses.load(Ordering::SeqCst), 3);
+    }
+
+    #[cfg(feature = "scrape")]
+    #[tokio::test]
+    async fn test_capture_raw_response() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let body = serde_json::json!({
+            "success": true,
+            "data": {
+                "markdown": "# Example",
+                "metadata": { "sourceURL": "https://example.com", "statusCode": 200 },
+                "forkOnly": { "renderer": "custom" }
+            }
+        });
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/scrape"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&body))
+            .mount(&server)
+            .await;
+
+        // Off by default
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        app.scrape_url("https://example.com", None).await.unwrap();
+        assert_eq!(app.last_raw_response(), None);
+
+        let app = FirecrawlApp::builder()
+            .api_url(server.uri())
+            .capture_raw()
+            .build()
+            .unwrap();
+        assert_eq!(app.last_raw_response(), None);
+        let clone = app.clone();
+        let document = app.scrape_url("https://example.com", None).await.unwrap();
+        assert_eq!(document.markdown.as_deref(), Some("# Example"));
+        assert_eq!(app.last_raw_response(), Some(body.clone()));
+        assert_eq!(clone.last_raw_response(), Some(body));
+
+        assert_eq!(app.with_capture_raw(false).last_raw_response(), None);
+    }
+
+    #[cfg(feature = "scrape")]
+    #[tokio::test]
+    async fn test_request_middleware_adds_header() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{header, headers, method, path},
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/scrape"))
+            .and(header("x-signature", "signed"))
+            .and(headers("x-middlewares", vec!["first", "second"]))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "data": {
+                    "markdown": "# Example",
+                    "metadata": { "sourceURL": "https://example.com", "statusCode": 200 }
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>)
+            .unwrap()
+            .with_request_middleware(|request| request.header("x-signature", "signed"))
+            .with_request_middleware(|request| request.header("x-middlewares", "first"))
+            .with_request_middleware(|request| request.header("x-middlewares", "second"));
+
+        app.scrape_url("https://example.com", None).await.unwrap();
+    }
+
+    #[cfg(feature = "scrape")]
+    #[tokio::test]
+    async fn test_request_id_from_response_header() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{body_partial_json, method, path},
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/scrape"))
+            .and(body_partial_json(
+                serde_json::json!({ "url": "https://example.com" }),
+            ))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-request-id", "req_8f3a2c")
+                    .set_body_json(serde_json::json!({
+                        "success": true,
+                        "data": {
+                            "markdown": "# Example",
+                            "metadata": { "sourceURL": "https://example.com", "statusCode": 200 }
+                        }
+                    })),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/scrape"))
+            .and(body_partial_json(
+                serde_json::json!({ "url": "https://example.org" }),
+            ))
+            .respond_with(
+                ResponseTemplate::new(500)
+                    .insert_header("x-request-id", "req_91bd07")
+                    .set_body_json(serde_json::json!({
+                        "success": false,
+                        "error": "Internal server error"
+                    })),
+            )
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+
+        let document = app.scrape_url("https://example.com", None).await.unwrap();
+        assert_eq!(document.request_id.as_deref(), Some("req_8f3a2c"));
+
+        let error = app
+            .scrape_url("https://example.org", None)
+            .await
+            .unwrap_err();
+        assert_eq!(error.request_id(), Some("req_91bd07"));
+    }
+
+    #[test]
+    fn test_request_id_from_error_body() {
+        let error: FirecrawlAPIError = serde_json::from_value(serde_json::json!({
+            "success": false,
+            "error": "Internal server error",
+            "requestId": "req_5e61aa"
+        }))
+        .unwrap();
+
+        assert_eq!(error.request_id.as_deref(), Some("req_5e61aa"));
     }
 }
@@ -0,0 +1,359 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "mcp-tool")]
+use schemars::JsonSchema;
+
+use crate::{FirecrawlApp, FirecrawlError, routes, time};
+
+#[serde_with::skip_serializing_none]
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "mcp-tool", derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct LlmsTxtOptions {
+    /// Maximum number of pages of the site to summarize. (default: `10`)
+    pub max_urls: Option<u32>,
+
+    /// Also generate `llms-full.txt`, with the full text of every page. (default: `false`)
+    pub show_full_text: Option<bool>,
+}
+
+option_setters!(LlmsTxtOptions, {
+    with_max_urls => max_urls: u32,
+    with_show_full_text => show_full_text: bool,
+});
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct LlmsTxtRequestBody<'a> {
+    url: &'a str,
+
+    #[serde(flatten)]
+    options: LlmsTxtOptions,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[cfg_attr(feature = "mcp-tool", derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct LlmsTxtAsyncResponse {
+    success: bool,
+
+    /// ID of the generation job
+    pub id: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "mcp-tool", derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum LlmsTxtStatusTypes {
+    /// The generation job is in progress.
+    #[default]
+    Processing,
+
+    /// The generation job has been completed successfully.
+    Completed,
+
+    /// The generation job has failed.
+    Failed,
+
+    /// A status this version of the SDK doesn't know, as sent by the API, which is monitored
+    /// like `Processing`.
+    #[serde(untagged)]
+    Unknown(String),
+}
+
+/// The generated files.
+#[serde_with::skip_serializing_none]
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "mcp-tool", derive(JsonSchema))]
+pub struct LlmsTxtResult {
+    /// Content of `llms.txt`, a summary of the site for LLMs.
+    pub llmstxt: String,
+
+    /// Content of `llms-full.txt`, only generated with `LlmsTxtOptions.show_full_text`.
+    pub llmsfulltxt: Option<String>,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LlmsTxtStatus {
+    /// The status of the generation job.
+    pub status: LlmsTxtStatusTypes,
+
+    /// The generated files, once the job has completed.
+    pub data: Option<LlmsTxtResult>,
+
+    /// Error message of a failed job.
+    pub error: Option<String>,
+
+    /// Expiry time of the generated files. After this date, they will be unavailable from the API.
+    ///
+    /// Enable the `chrono` feature to have it parsed by `expires_at_timestamp`.
+    pub expires_at: Option<String>,
+}
+
+impl LlmsTxtStatus {
+    /// `expires_at` parsed into a timestamp, or `None` if it's missing or not an ISO-8601
+    /// timestamp.
+    #[cfg(feature = "chrono")]
+    pub fn expires_at_timestamp(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.expires_at.as_deref().and_then(crate::timestamp::parse)
+    }
+}
+
+impl FirecrawlApp {
+    /// Starts generating the `llms.txt`, and optionally `llms-full.txt`, of the site at `url`.
+    pub async fn generate_llms_txt_async(
+        &self,
+        url: impl AsRef<str>,
+        options: impl Into<Option<LlmsTxtOptions>>,
+    ) -> Result<LlmsTxtAsyncResponse, FirecrawlError> {
+        let body = LlmsTxtRequestBody {
+            url: url.as_ref(),
+            options: options.into().unwrap_or_default(),
+        };
+        let request = self
+            .inner
+            .client
+            .post(self.endpoint(routes::llmstxt()))
+            .headers(self.prepare_headers(None)?)
+            .json(&body);
+        let response = self.send_request(request).await.map_err(|e| {
+            FirecrawlError::HttpError(format!("Generating llms.txt of {:?}", url.as_ref()), e)
+        })?;
+
+        self.handle_response::<LlmsTxtAsyncResponse>(response, "start llms.txt generation job")
+            .await
+    }
+
+    /// Generates the `llms.txt`, and optionally `llms-full.txt`, of the site at `url`, waiting
+    /// for the end result.
+    pub async fn generate_llms_txt(
+        &self,
+        url: impl AsRef<str>,
+        options: impl Into<Option<LlmsTxtOptions>>,
+        poll_interval: Option<u64>,
+    ) -> Result<LlmsTxtResult, FirecrawlError> {
+        let poll_interval = poll_interval.unwrap_or(2000);
+
+        let res = self.generate_llms_txt_async(url, options).await?;
+
+        self.monitor_llms_txt_status(&res.id, poll_interval).await
+    }
+
+    /// Checks for the status of an `llms.txt` generation job, based on the job's ID. To be used
+    /// in conjunction with `FirecrawlApp::generate_llms_txt_async`.
+    pub async fn check_generate_llms_txt_status(
+        &self,
+        id: impl AsRef<str>,
+    ) -> Result<LlmsTxtStatus, FirecrawlError> {
+        let request = self
+            .inner
+            .client
+            .get(self.endpoint(routes::llmstxt_status(id.as_ref())))
+            .headers(self.prepare_headers(None)?);
+        let response = self.send_request(request).await.map_err(|e| {
+            FirecrawlError::HttpError(
+                format!("Checking status of llms.txt generation job {}", id.as_ref()),
+                e,
+            )
+        })?;
+
+        self.handle_response(
+            response,
+            format!("Checking status of llms.txt generation job {}", id.as_ref()),
+        )
+        .await
+    }
+
+    async fn monitor_llms_txt_status(
+        &self,
+        id: &str,
+        poll_interval: u64,
+    ) -> Result<LlmsTxtResult, FirecrawlError> {
+        // Warn about an unknown status once, not on every poll
+        let mut warned = false;
+        loop {
+            let mut status = self.check_generate_llms_txt_status(id).await?;
+
+            match status.status {
+                LlmsTxtStatusTypes::Completed => match status.data.take() {
+                    Some(result) => break Ok(result),
+                    None => {
+                        break Err(FirecrawlError::LlmsTxtJobFailed(
+                            "llms.txt generation job completed without a result.".to_string(),
+                            Box::new(status),
+                        ));
+                    }
+                },
+                LlmsTxtStatusTypes::Processing | LlmsTxtStatusTypes::Unknown(_) => {
+                    if let LlmsTxtStatusTypes::Unknown(status) = &status.status
+                        && !warned
+                    {
+                        self.warn(&format!(
+                            "llms.txt generation job {id} has an unknown status {status:?}, polling again"
+                        ));
+                        warned = true;
+                    }
+                    time::delay(std::time::Duration::from_millis(poll_interval)).await;
+                }
+                LlmsTxtStatusTypes::Failed => {
+                    let message = status
+                        .error
+                        .clone()
+                        .unwrap_or_else(|| "llms.txt generation job failed.".to_string());
+                    break Err(FirecrawlError::LlmsTxtJobFailed(message, Box::new(status)));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{body_json, method, path},
+    };
+
+    async fn mock_llms_txt(server: &MockServer, status: serde_json::Value) {
+        Mock::given(method("POST"))
+            .and(path("/v1/llmstxt"))
+            .and(body_json(json!({
+                "url": "https://example.com",
+                "maxUrls": 5,
+                "showFullText": true
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "id": "llmstxt-1"
+            })))
+            .mount(server)
+            .await;
+
+        // The first status check reports the job as still running
+        Mock::given(method("GET"))
+            .and(path("/v1/llmstxt/llmstxt-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "status": "processing",
+                "expiresAt": "2025-01-02T03:04:05.000Z"
+            })))
+            .up_to_n_times(1)
+            .mount(server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/v1/llmstxt/llmstxt-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(status))
+            .mount(server)
+            .await;
+    }
+
+    fn options() -> LlmsTxtOptions {
+        LlmsTxtOptions::default()
+            .with_max_urls(5)
+            .with_show_full_text(true)
+    }
+
+    #[tokio::test]
+    async fn test_generate_llms_txt_polls_until_completed() {
+        let server = MockServer::start().await;
+        mock_llms_txt(
+            &server,
+            json!({
+                "success": true,
+                "status": "completed",
+                "data": {
+                    "llmstxt": "# Example\n\n- [Home](https://example.com)",
+                    "llmsfulltxt": "# Example\n\nExample Domain"
+                },
+                "expiresAt": "2025-01-02T03:04:05.000Z"
+            }),
+        )
+        .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let result = app
+            .generate_llms_txt("https://example.com", options(), Some(1))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result,
+            LlmsTxtResult {
+                llmstxt: "# Example\n\n- [Home](https://example.com)".to_string(),
+                llmsfulltxt: Some("# Example\n\nExample Domain".to_string()),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_generate_llms_txt_warns_once_about_unknown_status() {
+        let server = MockServer::start().await;
+        // A status introduced by a newer version of the API, mounted first to answer first
+        Mock::given(method("GET"))
+            .and(path("/v1/llmstxt/llmstxt-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "status": "queued"
+            })))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        mock_llms_txt(
+            &server,
+            json!({
+                "success": true,
+                "status": "completed",
+                "data": { "llmstxt": "# Example" }
+            }),
+        )
+        .await;
+
+        let warnings = std::sync::Arc::new(crate::observer::WarningRecorder::default());
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>)
+            .unwrap()
+            .with_hook(warnings.clone());
+        let result = app
+            .generate_llms_txt("https://example.com", options(), Some(1))
+            .await
+            .unwrap();
+
+        assert_eq!(result.llmstxt, "# Example");
+        assert_eq!(
+            warnings.take(),
+            vec![
+                r#"llms.txt generation job llmstxt-1 has an unknown status "queued", polling again"#
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_generate_llms_txt_failed() {
+        let server = MockServer::start().await;
+        mock_llms_txt(
+            &server,
+            json!({
+                "success": true,
+                "status": "failed",
+                "error": "Failed to map the site"
+            }),
+        )
+        .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let result = app
+            .generate_llms_txt("https://example.com", options(), Some(1))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(FirecrawlError::LlmsTxtJobFailed(message, status))
+                if message == "Failed to map the site"
+                    && status.status == LlmsTxtStatusTypes::Failed
+        ));
+    }
+}
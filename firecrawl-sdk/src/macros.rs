@@ -0,0 +1,17 @@
+/// Implements chainable `with_*` setters for the `Option` fields of an options struct, each
+/// setting its field to `Some`. The parameter is converted with `Into`, so it can be declared as
+/// e.g. `impl Into<String>`. Doc comments before the braces document the `impl` block.
+macro_rules! option_setters {
+    ($options:ty, $(#[$doc:meta])* { $($setter:ident => $field:ident: $param:ty),* $(,)? }) => {
+        $(#[$doc])*
+        impl $options {
+            $(
+                #[doc = concat!("Sets `", stringify!($field), "`.")]
+                pub fn $setter(mut self, $field: $param) -> Self {
+                    self.$field = Some($field.into());
+                    self
+                }
+            )*
+        }
+    };
+}
@@ -1,9 +1,14 @@
+use std::collections::HashSet;
+
 use serde::{Deserialize, Serialize};
+use url::Url;
 
 #[cfg(feature = "mcp-tool")]
 use schemars::JsonSchema;
 
-use crate::{API_VERSION, FirecrawlApp, FirecrawlError, error::FirecrawlAPIError};
+use crate::{
+    FirecrawlApp, FirecrawlError, error::FirecrawlAPIError, routes, scrape::LocationOptions,
+};
 
 #[serde_with::skip_serializing_none]
 #[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
@@ -28,6 +33,86 @@ pub struct MapOptions {
     /// Timeout in milliseconds. There is no timeout by default.
     #[cfg_attr(feature = "mcp-tool", schemars(skip))]
     pub timeout: Option<u32>,
+
+    /// Location settings, to discover the URLs served to users in a given country
+    pub location: Option<LocationOptions>,
+
+    /// Collapse returned URLs that only differ by a trailing slash or tracking query parameters
+    /// (`utm_*`, `fbclid`, `gclid`), returning them without these. Applied by the SDK, not sent
+    /// to the API. (default: `false`)
+    #[serde(skip)]
+    #[cfg_attr(feature = "mcp-tool", schemars(skip))]
+    pub normalize_results: Option<bool>,
+}
+
+option_setters!(MapOptions,
+    /// Setters to build options without struct update syntax.
+    ///
+    /// ```
+    /// use firecrawl_sdk::map::MapOptions;
+    ///
+    /// let options = MapOptions::default()
+    ///     .with_search("docs")
+    ///     .with_include_subdomains(true)
+    ///     .with_limit(100);
+    /// assert_eq!(options.search.as_deref(), Some("docs"));
+    /// ```
+    {
+        with_search => search: impl Into<String>,
+        with_ignore_sitemap => ignore_sitemap: bool,
+        with_sitemap_only => sitemap_only: bool,
+        with_include_subdomains => include_subdomains: bool,
+        with_limit => limit: u32,
+        with_timeout => timeout: u32,
+        with_location => location: LocationOptions,
+        with_normalize_results => normalize_results: bool,
+    }
+);
+
+/// Whether `name` is a query parameter only used to track where visitors come from.
+fn is_tracking_param(name: &str) -> bool {
+    name.starts_with("utm_") || matches!(name, "fbclid" | "gclid")
+}
+
+/// `link` without tracking query parameters and the trailing slash of its path. Links that can't
+/// be parsed are kept as is.
+fn normalize_link(link: String) -> String {
+    let Ok(mut url) = Url::parse(&link) else {
+        return link;
+    };
+
+    if let Some(query) = url.query() {
+        // The other pairs are kept as sent, since re-encoding them would change the link
+        let pairs: Vec<&str> = query.split('&').collect();
+        let kept: Vec<&str> = pairs
+            .iter()
+            .copied()
+            .filter(|pair| {
+                !url::form_urlencoded::parse(pair.as_bytes())
+                    .next()
+                    .is_some_and(|(name, _)| is_tracking_param(&name))
+            })
+            .collect();
+        if kept.len() < pairs.len() {
+            let query = (!kept.is_empty()).then(|| kept.join("&"));
+            url.set_query(query.as_deref());
+        }
+    }
+    if url.path().len() > 1 && url.path().ends_with('/') {
+        let path = url.path().trim_end_matches('/').to_string();
+        url.set_path(&path);
+    }
+    url.into()
+}
+
+/// Normalizes `links` with `normalize_link`, keeping the first of each in order.
+fn normalize_links(links: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    links
+        .into_iter()
+        .map(normalize_link)
+        .filter(|link| seen.insert(link.clone()))
+        .collect()
 }
 
 #[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
@@ -39,12 +124,22 @@ pub struct MapRequestBody {
     pub options: MapOptions,
 }
 
+/// Response of the map endpoint as sent by the API, returned by [`FirecrawlApp::map_url_raw`].
+#[serde_with::skip_serializing_none]
 #[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
-struct MapResponse {
-    success: Option<bool>,
-    links: Option<Vec<String>>,
-    error: Option<String>,
+pub struct MapResponse {
+    /// Whether the map succeeded
+    pub success: Option<bool>,
+
+    /// The URLs found on the site
+    pub links: Option<Vec<String>>,
+
+    /// Error message when `success` is `false`
+    pub error: Option<String>,
+
+    /// Warning about the request, e.g. an option the API ignored.
+    pub warning: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
@@ -64,25 +159,20 @@ impl FirecrawlApp {
         url: impl AsRef<str>,
         options: impl Into<Option<MapOptions>>,
     ) -> Result<Vec<String>, FirecrawlError> {
-        let body = MapRequestBody {
-            url: url.as_ref().to_string(),
-            options: options.into().unwrap_or_default(),
-        };
-
-        let headers = self.prepare_headers(None);
-
-        let response = self
-            .client
-            .post(format!("{}/{}/map", self.api_url, API_VERSION))
-            .headers(headers)
-            .json(&body)
-            .send()
-            .await
-            .map_err(|e| FirecrawlError::HttpError(format!("Mapping {:?}", url.as_ref()), e))?;
+        self.map_url_with_idempotency(url, options, None).await
+    }
 
-        let response = self
-            .handle_response::<MapResponse>(response, "map URL")
-            .await?;
+    /// Maps a URL like [`map_url`](Self::map_url), sending `idempotency_key` so a retried request
+    /// isn't charged twice.
+    pub async fn map_url_with_idempotency(
+        &self,
+        url: impl AsRef<str>,
+        options: impl Into<Option<MapOptions>>,
+        idempotency_key: Option<String>,
+    ) -> Result<Vec<String>, FirecrawlError> {
+        let options = options.into().unwrap_or_default();
+        let normalize_results = options.normalize_results.unwrap_or(false);
+        let response = self.send_map(url, options, idempotency_key).await?;
 
         if matches!(response.success, Some(false)) {
             return Err(FirecrawlError::APIError(
@@ -90,11 +180,61 @@ impl FirecrawlApp {
                 FirecrawlAPIError {
                     error: response.error.unwrap_or_default(),
                     details: None,
+                    status_code: None,
+                    rate_limit: None,
+                    request_id: None,
                 },
             ));
         }
 
-        Ok(response.links.unwrap_or_default())
+        let links = response.links.unwrap_or_default();
+        if normalize_results {
+            return Ok(normalize_links(links));
+        }
+        Ok(links)
+    }
+
+    /// Maps a URL like [`map_url`](Self::map_url), returning the response of the API as is, e.g.
+    /// to forward it unchanged.
+    ///
+    /// A response with `success: false` is returned rather than failing, and
+    /// `MapOptions.normalize_results` doesn't apply.
+    pub async fn map_url_raw(
+        &self,
+        url: impl AsRef<str>,
+        options: impl Into<Option<MapOptions>>,
+    ) -> Result<MapResponse, FirecrawlError> {
+        self.send_map(url, options.into().unwrap_or_default(), None)
+            .await
+    }
+
+    async fn send_map(
+        &self,
+        url: impl AsRef<str>,
+        options: MapOptions,
+        idempotency_key: Option<String>,
+    ) -> Result<MapResponse, FirecrawlError> {
+        let body = MapRequestBody {
+            url: url.as_ref().to_string(),
+            options,
+        };
+
+        let idempotency_key = self.idempotency_key(idempotency_key);
+        let headers = self.prepare_headers(idempotency_key.as_ref())?;
+
+        let request = self
+            .inner
+            .client
+            .post(self.endpoint(routes::map()))
+            .headers(headers)
+            .json(&body);
+        let response = self
+            .send_request(request)
+            .await
+            .map_err(|e| FirecrawlError::HttpError(format!("Mapping {:?}", url.as_ref()), e))?;
+
+        self.handle_response::<MapResponse>(response, "map URL")
+            .await
     }
 }
 
@@ -127,6 +267,8 @@ mod tests {
             include_subdomains: Some(true),
             limit: Some(100),
             timeout: Some(5000),
+            location: None,
+            normalize_results: None,
         };
 
         // Compare the entire structs
@@ -144,6 +286,10 @@ mod tests {
             "includeSubdomains": true,
             "limit": 100,
             "timeout": 5000,
+            "location": {
+                "country": "DE",
+                "languages": ["de-DE", "en"]
+            },
         });
 
         // Deserialize the JSON to our struct
@@ -160,6 +306,11 @@ mod tests {
                 include_subdomains: Some(true),
                 limit: Some(100),
                 timeout: Some(5000),
+                location: Some(LocationOptions {
+                    country: "DE".to_string(),
+                    languages: vec!["de-DE".to_string(), "en".to_string()],
+                }),
+                normalize_results: None,
             },
         };
 
@@ -192,9 +343,121 @@ mod tests {
                 "https://example.com/page3".to_string(),
             ]),
             error: None,
+            warning: None,
         };
 
         // Compare the entire structs
         assert_eq!(response, expected_response);
     }
+
+    #[test]
+    fn test_map_response_round_trip() {
+        for body in [
+            r#"{"success":true,"links":["https://example.com","https://example.com/about"],"warning":"Sitemap not found"}"#,
+            r#"{"success":false,"error":"Invalid URL"}"#,
+        ] {
+            let response: MapResponse = serde_json::from_str(body).unwrap();
+            assert_eq!(serde_json::to_string(&response).unwrap(), body);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_map_url_with_idempotency() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{header, method, path},
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/map"))
+            .and(header("x-idempotency-key", "map-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "links": ["https://example.com/about"]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let links = app
+            .map_url_with_idempotency("https://example.com", None, Some("map-1".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(links, vec!["https://example.com/about"]);
+    }
+
+    #[test]
+    fn test_normalize_links() {
+        let links = [
+            "https://example.com/",
+            "https://example.com",
+            "https://example.com/docs/",
+            "https://example.com/docs?utm_source=newsletter&utm_medium=email",
+            "https://example.com/docs?fbclid=abc123",
+            "https://example.com/search?q=rust&gclid=xyz",
+            "https://example.com/search?q=rust",
+            "https://example.com/search?q=go",
+            // Queries without tracking parameters are left as is
+            "https://example.com/search?q=a%20b",
+            "https://example.com/search?flag",
+            "https://example.com/search?q=a+b&flag&utm_source=newsletter",
+            "not a url",
+        ]
+        .map(String::from)
+        .to_vec();
+
+        assert_eq!(
+            normalize_links(links),
+            vec![
+                "https://example.com/",
+                "https://example.com/docs",
+                "https://example.com/search?q=rust",
+                "https://example.com/search?q=go",
+                "https://example.com/search?q=a%20b",
+                "https://example.com/search?flag",
+                "https://example.com/search?q=a+b&flag",
+                "not a url",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_map_url_normalize_results() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{body_json, method, path},
+        };
+
+        let links = json!([
+            "https://example.com/about/",
+            "https://example.com/about?utm_campaign=spring",
+            "https://example.com/about"
+        ]);
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/map"))
+            .and(body_json(json!({ "url": "https://example.com" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "links": links
+            })))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let normalized = app
+            .map_url(
+                "https://example.com",
+                MapOptions::default().with_normalize_results(true),
+            )
+            .await
+            .unwrap();
+        assert_eq!(normalized, vec!["https://example.com/about"]);
+
+        let raw = app.map_url("https://example.com", None).await.unwrap();
+        assert_eq!(raw.len(), 3);
+    }
 }
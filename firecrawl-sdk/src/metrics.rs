@@ -0,0 +1,218 @@
+//! OpenTelemetry metrics of the requests made by the SDK, enabled by the `otel` feature. The
+//! instruments are listed in the docs of [`FirecrawlApp::with_meter`].
+//!
+//! [`FirecrawlApp::with_meter`]: crate::FirecrawlApp::with_meter
+
+use std::time::Duration;
+
+use opentelemetry::{
+    KeyValue, global,
+    metrics::{Counter, Histogram, Meter},
+};
+use reqwest::{Method, Url};
+
+/// Name of the meter used unless another one is passed to
+/// [`FirecrawlApp::with_meter`](crate::FirecrawlApp::with_meter).
+const METER_NAME: &str = "firecrawl-sdk";
+
+/// Instruments recording the requests of a [`FirecrawlApp`](crate::FirecrawlApp).
+#[derive(Debug, Clone)]
+pub(crate) struct Metrics {
+    requests: Counter<u64>,
+    errors: Counter<u64>,
+    duration: Histogram<f64>,
+    #[cfg(any(feature = "batch", feature = "crawl"))]
+    credits_used: Counter<u64>,
+}
+
+impl Default for Metrics {
+    /// Instruments of the global meter provider, as set when the client is created.
+    fn default() -> Self {
+        Self::new(&global::meter(METER_NAME))
+    }
+}
+
+impl Metrics {
+    pub(crate) fn new(meter: &Meter) -> Self {
+        Self {
+            requests: meter
+                .u64_counter("firecrawl.requests")
+                .with_description("Requests sent to the Firecrawl API")
+                .build(),
+            errors: meter
+                .u64_counter("firecrawl.errors")
+                .with_description("Requests that failed or got an error status")
+                .build(),
+            duration: meter
+                .f64_histogram("firecrawl.request.duration")
+                .with_unit("s")
+                .with_description("Time until the response headers of a request arrived")
+                .build(),
+            #[cfg(any(feature = "batch", feature = "crawl"))]
+            credits_used: meter
+                .u64_counter("firecrawl.credits_used")
+                .with_description("Credits used by completed crawls and batch scrapes")
+                .build(),
+        }
+    }
+
+    pub(crate) fn record_request(
+        &self,
+        method: &Method,
+        url: &Url,
+        status: Option<u16>,
+        elapsed: Duration,
+    ) {
+        let mut attributes = vec![
+            KeyValue::new("firecrawl.endpoint", endpoint(url)),
+            KeyValue::new("http.request.method", method.to_string()),
+        ];
+        self.requests.add(1, &attributes);
+
+        if let Some(status) = status {
+            attributes.push(KeyValue::new(
+                "http.response.status_code",
+                i64::from(status),
+            ));
+        }
+        if status.is_none_or(|status| status >= 400) {
+            self.errors.add(1, &attributes);
+        }
+        self.duration.record(elapsed.as_secs_f64(), &attributes);
+    }
+
+    #[cfg(any(feature = "batch", feature = "crawl"))]
+    pub(crate) fn record_credits_used(&self, endpoint: &'static str, credits: u64) {
+        self.credits_used
+            .add(credits, &[KeyValue::new("firecrawl.endpoint", endpoint)]);
+    }
+}
+
+/// Whether `segment` is the version of the API, e.g. `v1`.
+fn is_api_version(segment: &str) -> bool {
+    segment
+        .strip_prefix('v')
+        .is_some_and(|number| !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Name of the endpoint `url` belongs to, without job ids so the attribute stays low-cardinality.
+fn endpoint(url: &Url) -> &'static str {
+    let mut segments = url
+        .path_segments()
+        .into_iter()
+        .flatten()
+        .skip_while(|segment| !is_api_version(segment))
+        .skip(1);
+
+    match (segments.next(), segments.next()) {
+        (Some("scrape"), _) => "scrape",
+        (Some("batch"), Some("scrape")) => "batch/scrape",
+        (Some("crawl"), _) => "crawl",
+        (Some("map"), _) => "map",
+        (Some("search"), _) => "search",
+        (Some("extract"), _) => "extract",
+        (Some("team"), Some("credit-usage")) => "team/credit-usage",
+        (Some("team"), Some("token-usage")) => "team/token-usage",
+        _ => "other",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "scrape")]
+    use crate::FirecrawlApp;
+    #[cfg(feature = "scrape")]
+    use opentelemetry::metrics::MeterProvider;
+    #[cfg(feature = "scrape")]
+    use opentelemetry_sdk::metrics::{
+        InMemoryMetricExporter, PeriodicReader, SdkMeterProvider,
+        data::{AggregatedMetrics, MetricData},
+    };
+    #[cfg(feature = "scrape")]
+    use serde_json::json;
+    #[cfg(feature = "scrape")]
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path},
+    };
+
+    #[cfg(feature = "scrape")]
+    fn counter_value(exporter: &InMemoryMetricExporter, name: &str) -> u64 {
+        exporter
+            .get_finished_metrics()
+            .unwrap()
+            .iter()
+            .flat_map(|resource| resource.scope_metrics())
+            .flat_map(|scope| scope.metrics())
+            .filter(|metric| metric.name() == name)
+            .map(|metric| match metric.data() {
+                AggregatedMetrics::U64(MetricData::Sum(sum)) => {
+                    sum.data_points().map(|point| point.value()).sum()
+                }
+                data => panic!("{name} is not a u64 counter: {data:?}"),
+            })
+            .last()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn test_endpoint() {
+        let cases = [
+            ("http://localhost:3002/v1/scrape", "scrape"),
+            ("http://localhost:3002/v1/batch/scrape/abc", "batch/scrape"),
+            (
+                "https://gateway.internal/firecrawl/v1/crawl/abc?skip=10",
+                "crawl",
+            ),
+            (
+                "http://localhost:3002/v1/team/credit-usage",
+                "team/credit-usage",
+            ),
+            (
+                "http://localhost:3002/v1/team/token-usage",
+                "team/token-usage",
+            ),
+            ("http://localhost:3002/v2/crawl/abc", "crawl"),
+            ("http://localhost:3002/health", "other"),
+        ];
+
+        for (url, expected) in cases {
+            assert_eq!(endpoint(&Url::parse(url).unwrap()), expected, "{url}");
+        }
+    }
+
+    #[cfg(feature = "scrape")]
+    #[tokio::test]
+    async fn test_request_counter_increments_after_scrape() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/scrape"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "data": {
+                    "markdown": "# Example",
+                    "metadata": { "sourceURL": "https://example.com", "statusCode": 200 }
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let exporter = InMemoryMetricExporter::default();
+        let provider = SdkMeterProvider::builder()
+            .with_reader(PeriodicReader::builder(exporter.clone()).build())
+            .build();
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>)
+            .unwrap()
+            .with_meter(&provider.meter("test"));
+
+        app.scrape_url("https://example.com", None).await.unwrap();
+        provider.force_flush().unwrap();
+        assert_eq!(counter_value(&exporter, "firecrawl.requests"), 1);
+        assert_eq!(counter_value(&exporter, "firecrawl.errors"), 0);
+
+        app.scrape_url("https://example.com", None).await.unwrap();
+        provider.force_flush().unwrap();
+        assert_eq!(counter_value(&exporter, "firecrawl.requests"), 2);
+    }
+}
@@ -0,0 +1,94 @@
+use std::{sync::Arc, time::Duration};
+
+use reqwest::{Method, RequestBuilder, Url};
+use serde_json::Value;
+
+/// Callback registered with [`FirecrawlApp::with_observer`](crate::FirecrawlApp::with_observer).
+pub(crate) type Observer = Arc<dyn Fn(&RequestInfo<'_>) + Send + Sync>;
+
+/// Middleware registered with
+/// [`FirecrawlApp::with_request_middleware`](crate::FirecrawlApp::with_request_middleware).
+pub(crate) type RequestMiddleware = Arc<dyn Fn(RequestBuilder) -> RequestBuilder + Send + Sync>;
+
+/// A request made by the SDK, passed to the observer registered with
+/// [`FirecrawlApp::with_observer`](crate::FirecrawlApp::with_observer).
+///
+/// The observer is called twice per request: once before it is sent, with `status` and `elapsed`
+/// unset, and once after the response arrives or the request fails.
+#[derive(Debug, Clone)]
+pub struct RequestInfo<'a> {
+    /// HTTP method of the request
+    pub method: &'a Method,
+
+    /// Full URL of the request
+    pub url: &'a Url,
+
+    /// JSON body of the request, if any
+    pub body: Option<&'a [u8]>,
+
+    /// HTTP status code of the response. `None` before the request is sent, or if it failed
+    /// without a response.
+    pub status: Option<u16>,
+
+    /// Time between sending the request and receiving the response headers. `None` before the
+    /// request is sent.
+    pub elapsed: Option<Duration>,
+}
+
+impl RequestInfo<'_> {
+    /// Returns `true` if this is the notification sent before the request.
+    pub fn is_before_send(&self) -> bool {
+        self.elapsed.is_none()
+    }
+}
+
+/// Hook registered with [`FirecrawlApp::with_hook`](crate::FirecrawlApp::with_hook), e.g. for
+/// tracing, metrics or logging request bodies.
+///
+/// All methods do nothing by default.
+pub trait RequestHook: Send + Sync {
+    /// Called before a request is sent, with its JSON body if it has one.
+    fn on_request(&self, method: &Method, url: &Url, body_json: Option<&Value>) {
+        let _ = (method, url, body_json);
+    }
+
+    /// Called once the response headers arrive or the request fails, with the HTTP status code
+    /// if a response was received.
+    fn on_response(&self, status: Option<u16>, elapsed: Duration) {
+        let _ = (status, elapsed);
+    }
+
+    /// Called with a warning about a response the SDK could still handle, e.g. a job status it
+    /// doesn't know.
+    fn on_warning(&self, message: &str) {
+        let _ = message;
+    }
+}
+
+/// Hook recording the warnings it receives, for tests.
+#[cfg(all(
+    test,
+    any(feature = "scrape", feature = "extract", feature = "llmstxt")
+))]
+#[derive(Default)]
+pub(crate) struct WarningRecorder(std::sync::Mutex<Vec<String>>);
+
+#[cfg(all(
+    test,
+    any(feature = "scrape", feature = "extract", feature = "llmstxt")
+))]
+impl WarningRecorder {
+    pub(crate) fn take(&self) -> Vec<String> {
+        std::mem::take(&mut self.0.lock().unwrap())
+    }
+}
+
+#[cfg(all(
+    test,
+    any(feature = "scrape", feature = "extract", feature = "llmstxt")
+))]
+impl RequestHook for WarningRecorder {
+    fn on_warning(&self, message: &str) {
+        self.0.lock().unwrap().push(message.to_string());
+    }
+}
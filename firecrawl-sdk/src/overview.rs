@@ -0,0 +1,300 @@
+use std::time::Duration;
+
+use futures::{StreamExt, stream};
+
+use crate::{
+    FirecrawlApp, FirecrawlError,
+    document::Document,
+    map::MapOptions,
+    scrape::{ScrapeFormats, ScrapeOptions},
+    time::{self, Instant},
+};
+
+/// Number of pages scraped at the same time by [`FirecrawlApp::fetch_site_overview`].
+const OVERVIEW_CONCURRENCY: usize = 4;
+
+/// Options for [`FirecrawlApp::fetch_site_overview`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SiteOverviewOptions {
+    /// Only scrape pages matching this search term, most relevant first. (default: none)
+    pub search: Option<String>,
+
+    /// Maximum number of pages scraped besides the root page. (default: `5`)
+    pub max_pages: usize,
+
+    /// Timeout of each scrape, passed on as `ScrapeOptions.timeout`. (default: 30 seconds)
+    pub per_page_timeout: Duration,
+
+    /// Time after which no more pages are waited for, counted from the start of the call.
+    /// (default: 60 seconds)
+    ///
+    /// Mapping the site and scraping the root page are always waited for.
+    pub deadline: Duration,
+}
+
+impl Default for SiteOverviewOptions {
+    fn default() -> Self {
+        Self {
+            search: None,
+            max_pages: 5,
+            per_page_timeout: Duration::from_secs(30),
+            deadline: Duration::from_secs(60),
+        }
+    }
+}
+
+/// A quick overview of a site, returned by [`FirecrawlApp::fetch_site_overview`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SiteOverview {
+    /// The page at the URL the overview was requested for
+    pub root: Document,
+
+    /// The scraped pages, in the order the map returned them
+    pub pages: Vec<Document>,
+
+    /// Number of URLs found by the map, only counting matches if a search term was given
+    pub sitemap_size: usize,
+
+    /// Pages that failed, timed out or weren't reached before the deadline
+    pub failed_urls: Vec<String>,
+}
+
+impl FirecrawlApp {
+    /// Maps `url` and scrapes its root page plus the first `options.max_pages` pages of the map
+    /// as markdown, a few at a time.
+    ///
+    /// Only failing to map the site or to scrape the root page fails the call. Other pages that
+    /// fail or miss the deadline are listed in `SiteOverview.failed_urls`.
+    pub async fn fetch_site_overview(
+        &self,
+        url: impl AsRef<str>,
+        options: SiteOverviewOptions,
+    ) -> Result<SiteOverview, FirecrawlError> {
+        let deadline = Instant::now() + options.deadline;
+        let url = url.as_ref();
+        let scrape_options = ScrapeOptions {
+            formats: Some(vec![ScrapeFormats::Markdown]),
+            only_main_content: Some(true),
+            timeout: Some(
+                options
+                    .per_page_timeout
+                    .as_millis()
+                    .try_into()
+                    .unwrap_or(u32::MAX),
+            ),
+            ..Default::default()
+        };
+        let map_options = MapOptions {
+            search: options.search,
+            ..Default::default()
+        };
+
+        let (links, root) = futures::join!(
+            self.map_url(url, map_options),
+            self.scrape_url(url, scrape_options.clone()),
+        );
+        let (links, root) = (links?, root?);
+
+        let page_urls: Vec<&String> = links
+            .iter()
+            .filter(|link| !is_same_page(link, url))
+            .take(options.max_pages)
+            .collect();
+
+        let mut scrapes = stream::iter(page_urls.iter().enumerate())
+            .map(|(i, page_url)| {
+                let scrape_options = scrape_options.clone();
+                async move { (i, self.scrape_url(page_url.as_str(), scrape_options).await) }
+            })
+            .buffer_unordered(OVERVIEW_CONCURRENCY);
+
+        let mut scraped: Vec<Option<Document>> = vec![None; page_urls.len()];
+        while let Some(Some((i, result))) = time::run_until(deadline, scrapes.next()).await {
+            scraped[i] = result.ok();
+        }
+        // Cancels the scrapes still running after the deadline
+        drop(scrapes);
+
+        let mut pages = Vec::new();
+        let mut failed_urls = Vec::new();
+        for (page_url, document) in page_urls.into_iter().zip(scraped) {
+            match document {
+                Some(document) => pages.push(document),
+                None => failed_urls.push(page_url.clone()),
+            }
+        }
+
+        Ok(SiteOverview {
+            root,
+            pages,
+            sitemap_size: links.len(),
+            failed_urls,
+        })
+    }
+}
+
+/// Compares URLs ignoring a trailing slash, so the root page isn't scraped twice.
+fn is_same_page(a: &str, b: &str) -> bool {
+    a.trim_end_matches('/') == b.trim_end_matches('/')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{body_partial_json, method, path},
+    };
+
+    async fn mock_page(server: &MockServer, url: &str, response: ResponseTemplate) {
+        Mock::given(method("POST"))
+            .and(path("/v1/scrape"))
+            .and(body_partial_json(json!({ "url": url })))
+            .respond_with(response)
+            .mount(server)
+            .await;
+    }
+
+    fn page(url: &str) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(json!({
+            "success": true,
+            "data": {
+                "markdown": format!("# {url}"),
+                "metadata": { "sourceURL": url, "statusCode": 200 }
+            }
+        }))
+    }
+
+    async fn mock_site(server: &MockServer) {
+        Mock::given(method("POST"))
+            .and(path("/v1/map"))
+            .and(body_partial_json(json!({ "search": "docs" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "links": [
+                    "https://example.com/",
+                    "https://example.com/docs",
+                    "https://example.com/docs/broken",
+                    "https://example.com/docs/api",
+                    "https://example.com/docs/slow",
+                ]
+            })))
+            .mount(server)
+            .await;
+
+        mock_page(server, "https://example.com", page("https://example.com")).await;
+        mock_page(
+            server,
+            "https://example.com/docs",
+            page("https://example.com/docs"),
+        )
+        .await;
+        mock_page(
+            server,
+            "https://example.com/docs/broken",
+            ResponseTemplate::new(500).set_body_json(json!({
+                "success": false,
+                "error": "Internal server error"
+            })),
+        )
+        .await;
+        mock_page(
+            server,
+            "https://example.com/docs/api",
+            page("https://example.com/docs/api"),
+        )
+        .await;
+        mock_page(
+            server,
+            "https://example.com/docs/slow",
+            page("https://example.com/docs/slow").set_delay(Duration::from_secs(5)),
+        )
+        .await;
+    }
+
+    fn source_urls(pages: &[Document]) -> Vec<&str> {
+        pages
+            .iter()
+            .map(|page| page.metadata.source_url.as_str())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_fetch_site_overview_with_partial_failures() {
+        let server = MockServer::start().await;
+        mock_site(&server).await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let options = SiteOverviewOptions {
+            search: Some("docs".to_string()),
+            deadline: Duration::from_millis(500),
+            ..Default::default()
+        };
+        let overview = app
+            .fetch_site_overview("https://example.com", options)
+            .await
+            .unwrap();
+
+        assert_eq!(overview.root.metadata.source_url, "https://example.com");
+        assert_eq!(
+            source_urls(&overview.pages),
+            vec!["https://example.com/docs", "https://example.com/docs/api"]
+        );
+        assert_eq!(overview.sitemap_size, 5);
+        assert_eq!(
+            overview.failed_urls,
+            vec![
+                "https://example.com/docs/broken",
+                "https://example.com/docs/slow"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_site_overview_max_pages() {
+        let server = MockServer::start().await;
+        mock_site(&server).await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let options = SiteOverviewOptions {
+            search: Some("docs".to_string()),
+            max_pages: 1,
+            ..Default::default()
+        };
+        let overview = app
+            .fetch_site_overview("https://example.com", options)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            source_urls(&overview.pages),
+            vec!["https://example.com/docs"]
+        );
+        assert!(overview.failed_urls.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_site_overview_root_failure() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/map"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "links": ["https://example.com/"]
+            })))
+            .mount(&server)
+            .await;
+        mock_page(&server, "https://example.com", ResponseTemplate::new(502)).await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let result = app
+            .fetch_site_overview("https://example.com", SiteOverviewOptions::default())
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(FirecrawlError::HttpRequestFailed(_, 502, _))
+        ));
+    }
+}
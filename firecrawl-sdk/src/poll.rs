@@ -0,0 +1,255 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+/// Interval of the first status check with the default policy.
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(2000);
+
+/// How the interval between two status checks grows.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Growth {
+    /// The interval stays the same
+    Fixed,
+
+    /// The interval grows by this step after every check
+    Linear(Duration),
+
+    /// The interval is multiplied by this factor after every check
+    Exponential(f64),
+}
+
+/// Intervals between the status checks of a crawl or batch scrape, see
+/// `CrawlParams::with_poll_policy`.
+///
+/// The interval grows while the job makes no progress and goes back to the initial one whenever
+/// its `completed` count increases, so active jobs stay responsive while stalled ones are polled
+/// less and less.
+///
+/// ```
+/// use std::time::Duration;
+/// use firecrawl_sdk::PollPolicy;
+///
+/// // 1s, 2s, 4s, ... up to 30s, each shortened by up to 20% so workers don't poll in step
+/// let policy = PollPolicy::exponential(Duration::from_secs(1), 2.0, Duration::from_secs(30))
+///     .with_jitter(0.2);
+/// # let _ = policy;
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PollPolicy {
+    initial: Duration,
+    growth: Growth,
+    max: Duration,
+    jitter: f64,
+}
+
+impl Default for PollPolicy {
+    /// Checks every 2 seconds.
+    fn default() -> Self {
+        Self::fixed(DEFAULT_POLL_INTERVAL)
+    }
+}
+
+impl PollPolicy {
+    /// Checks every `interval`.
+    pub fn fixed(interval: Duration) -> Self {
+        Self {
+            initial: interval,
+            growth: Growth::Fixed,
+            max: interval,
+            jitter: 0.0,
+        }
+    }
+
+    /// Checks every `interval` milliseconds, as with the `poll_interval` arguments.
+    pub fn from_millis(interval: u64) -> Self {
+        Self::fixed(Duration::from_millis(interval))
+    }
+
+    /// Starts with `initial` and waits `step` longer after every check, up to `max`.
+    pub fn linear(initial: Duration, step: Duration, max: Duration) -> Self {
+        Self {
+            initial,
+            growth: Growth::Linear(step),
+            max: max.max(initial),
+            jitter: 0.0,
+        }
+    }
+
+    /// Starts with `initial` and multiplies the interval by `factor` after every check, up to
+    /// `max`.
+    pub fn exponential(initial: Duration, factor: f64, max: Duration) -> Self {
+        Self {
+            initial,
+            growth: Growth::Exponential(factor.max(1.0)),
+            max: max.max(initial),
+            jitter: 0.0,
+        }
+    }
+
+    /// Shortens every interval by a random fraction of up to `jitter`, clamped to `0.0..=1.0`.
+    /// A NaN or infinite `jitter` disables it.
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = if jitter.is_finite() {
+            jitter.clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        self
+    }
+
+    /// The interval before the check following `checks` checks without progress, without
+    /// jitter.
+    fn interval(&self, checks: u32) -> Duration {
+        let interval = match self.growth {
+            Growth::Fixed => self.initial,
+            Growth::Linear(step) => self.initial.saturating_add(step.saturating_mul(checks)),
+            Growth::Exponential(factor) => {
+                let secs = self.initial.as_secs_f64() * factor.powf(checks.into());
+                Duration::try_from_secs_f64(secs).unwrap_or(self.max)
+            }
+        };
+        interval.min(self.max)
+    }
+
+    /// Starts the intervals of one job.
+    pub(crate) fn schedule(&self) -> PollSchedule<'_> {
+        PollSchedule {
+            policy: self,
+            checks: 0,
+            completed: 0,
+            random: RandomState::new().build_hasher().finish() | 1,
+        }
+    }
+}
+
+/// Intervals of one job following a [`PollPolicy`].
+pub(crate) struct PollSchedule<'a> {
+    policy: &'a PollPolicy,
+    /// Checks since the job last made progress
+    checks: u32,
+    /// Highest `completed` count seen so far
+    completed: u64,
+    /// State of the xorshift generator of the jitter, never zero
+    random: u64,
+}
+
+impl PollSchedule<'_> {
+    /// The interval to wait after a check that found `completed` items done.
+    pub(crate) fn next_interval(&mut self, completed: u64) -> Duration {
+        if completed > self.completed {
+            self.completed = completed;
+            self.checks = 0;
+        }
+        let interval = self.policy.interval(self.checks);
+        self.checks = self.checks.saturating_add(1);
+
+        if self.policy.jitter == 0.0 {
+            return interval;
+        }
+        interval.mul_f64(1.0 - self.policy.jitter * self.next_random())
+    }
+
+    /// A pseudo-random number in `0.0..1.0`.
+    fn next_random(&mut self) -> f64 {
+        self.random ^= self.random << 13;
+        self.random ^= self.random >> 7;
+        self.random ^= self.random << 17;
+        (self.random >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn intervals(policy: &PollPolicy, completed: &[u64]) -> Vec<u64> {
+        let mut schedule = policy.schedule();
+        completed
+            .iter()
+            .map(|&completed| schedule.next_interval(completed).as_millis() as u64)
+            .collect()
+    }
+
+    #[test]
+    fn test_fixed_policy() {
+        assert_eq!(
+            intervals(&PollPolicy::default(), &[0, 0, 0]),
+            vec![2000, 2000, 2000]
+        );
+        assert_eq!(
+            intervals(&PollPolicy::from_millis(500), &[0, 1, 1]),
+            vec![500, 500, 500]
+        );
+    }
+
+    #[test]
+    fn test_linear_policy() {
+        let policy = PollPolicy::linear(
+            Duration::from_millis(1000),
+            Duration::from_millis(500),
+            Duration::from_millis(2200),
+        );
+        assert_eq!(
+            intervals(&policy, &[0, 0, 0, 0, 0]),
+            vec![1000, 1500, 2000, 2200, 2200]
+        );
+    }
+
+    #[test]
+    fn test_exponential_policy() {
+        let policy = PollPolicy::exponential(
+            Duration::from_millis(1000),
+            2.0,
+            Duration::from_millis(10_000),
+        );
+        assert_eq!(
+            intervals(&policy, &[0, 0, 0, 0, 0, 0]),
+            vec![1000, 2000, 4000, 8000, 10_000, 10_000]
+        );
+
+        // Neither a huge number of checks nor a shrinking factor go past the bounds
+        let mut schedule = policy.schedule();
+        for _ in 0..2000 {
+            schedule.next_interval(0);
+        }
+        assert_eq!(schedule.next_interval(0), Duration::from_millis(10_000));
+        let policy = PollPolicy::exponential(Duration::from_secs(1), 0.5, Duration::from_secs(10));
+        assert_eq!(intervals(&policy, &[0, 0]), vec![1000, 1000]);
+    }
+
+    #[test]
+    fn test_progress_resets_the_backoff() {
+        let policy = PollPolicy::exponential(
+            Duration::from_millis(1000),
+            2.0,
+            Duration::from_millis(30_000),
+        );
+        assert_eq!(
+            intervals(&policy, &[0, 0, 0, 3, 3, 3, 3, 5]),
+            vec![1000, 2000, 4000, 1000, 2000, 4000, 8000, 1000]
+        );
+    }
+
+    #[test]
+    fn test_jitter_shortens_intervals() {
+        let policy = PollPolicy::fixed(Duration::from_millis(1000)).with_jitter(0.25);
+        let jittered = intervals(&policy, &[0; 100]);
+        assert!(
+            jittered.iter().all(|&ms| (750..=1000).contains(&ms)),
+            "{jittered:?}"
+        );
+        assert!(jittered.iter().any(|&ms| ms != jittered[0]), "{jittered:?}");
+
+        // Clamped to a full jitter
+        let policy = PollPolicy::default().with_jitter(7.0);
+        assert!(intervals(&policy, &[0; 50]).iter().all(|&ms| ms <= 2000));
+
+        // Disabled rather than panicking
+        for jitter in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let policy = PollPolicy::fixed(Duration::from_millis(1000)).with_jitter(jitter);
+            assert_eq!(intervals(&policy, &[0; 10]), vec![1000; 10], "{jitter}");
+        }
+    }
+}
@@ -0,0 +1,295 @@
+use std::{
+    sync::{Mutex, PoisonError},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use reqwest::header::HeaderMap;
+
+use crate::time;
+
+/// Values above this are read as a Unix timestamp rather than a number of seconds from now.
+const EPOCH_THRESHOLD: u64 = 1_000_000_000;
+
+/// Rate limit state reported by the API through the `x-ratelimit-*` headers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    /// Number of requests allowed in the current window, from `x-ratelimit-limit`
+    pub limit: Option<u64>,
+
+    /// Number of requests left in the current window, from `x-ratelimit-remaining`
+    pub remaining: Option<u64>,
+
+    /// When the window resets, from `x-ratelimit-reset`. Accepts both a Unix timestamp and a
+    /// number of seconds from now.
+    pub reset_at: Option<SystemTime>,
+}
+
+impl RateLimitInfo {
+    /// Parses the `x-ratelimit-*` headers, returning `None` if none of them is present.
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        Self::from_headers_at(headers, time::now())
+    }
+
+    fn from_headers_at(headers: &HeaderMap, now: SystemTime) -> Option<Self> {
+        let number = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<u64>().ok())
+        };
+
+        let info = RateLimitInfo {
+            limit: number("x-ratelimit-limit"),
+            remaining: number("x-ratelimit-remaining"),
+            reset_at: number("x-ratelimit-reset").map(|reset| {
+                if reset >= EPOCH_THRESHOLD {
+                    UNIX_EPOCH + Duration::from_secs(reset)
+                } else {
+                    now + Duration::from_secs(reset)
+                }
+            }),
+        };
+
+        if info.limit.is_none() && info.remaining.is_none() && info.reset_at.is_none() {
+            None
+        } else {
+            Some(info)
+        }
+    }
+
+    /// How long to wait until the window resets. Zero if the reset time has already passed.
+    pub fn wait_duration(&self) -> Option<Duration> {
+        self.reset_at.map(|reset_at| {
+            reset_at
+                .duration_since(time::now())
+                .unwrap_or(Duration::ZERO)
+        })
+    }
+}
+
+/// Parses the `Retry-After` header, in either its delay-seconds or its HTTP-date form.
+///
+/// A date in the past yields a zero delay.
+pub(crate) fn retry_after_from_headers(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get("retry-after")?.to_str().ok()?;
+    parse_retry_after(value, time::now())
+}
+
+fn parse_retry_after(value: &str, now: SystemTime) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = parse_http_date(value)?;
+    Some(date.duration_since(now).unwrap_or(Duration::ZERO))
+}
+
+/// Parses an IMF-fixdate such as `Wed, 21 Oct 2015 07:28:00 GMT`, the only date format servers
+/// are allowed to send.
+fn parse_http_date(value: &str) -> Option<SystemTime> {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    let (_weekday, rest) = value.split_once(", ")?;
+    let mut parts = rest.split(' ');
+    let day: u64 = parts.next()?.parse().ok()?;
+    let month = parts.next()?;
+    let month = MONTHS.iter().position(|m| *m == month)? as u64 + 1;
+    let year: u64 = parts.next()?.parse().ok()?;
+    let mut time = parts.next()?.split(':').map(|p| p.parse::<u64>().ok());
+    let (hour, minute, second) = (time.next()??, time.next()??, time.next()??);
+    if parts.next()? != "GMT" || parts.next().is_some() || time.next().is_some() {
+        return None;
+    }
+    if year < 1970 || !(1..=31).contains(&day) || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    // Days since the epoch, from Howard Hinnant's `days_from_civil`
+    let (y, m) = if month <= 2 {
+        (year - 1, month + 9)
+    } else {
+        (year, month - 3)
+    };
+    let era = y / 400;
+    let year_of_era = y % 400;
+    let day_of_year = (153 * m + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = (era * 146_097 + day_of_era).checked_sub(719_468)?;
+
+    Some(UNIX_EPOCH + Duration::from_secs(days * 86_400 + hour * 3_600 + minute * 60 + second))
+}
+
+/// Token bucket limiting the rate of the requests of a client and its clones, see
+/// [`FirecrawlApp::with_rate_limit`](crate::FirecrawlApp::with_rate_limit).
+#[derive(Debug)]
+pub(crate) struct TokenBucket {
+    capacity: f64,
+    /// Tokens added per second
+    refill_rate: f64,
+    state: Mutex<BucketState>,
+}
+
+#[derive(Debug)]
+struct BucketState {
+    /// Tokens left, negative when requests are waiting for tokens not refilled yet
+    tokens: f64,
+    refilled_at: time::Instant,
+}
+
+impl TokenBucket {
+    /// A full bucket of `max_requests` tokens, refilled at `max_requests` tokens per `per`.
+    pub(crate) fn new(max_requests: u32, per: Duration) -> Self {
+        let capacity = f64::from(max_requests.max(1));
+        TokenBucket {
+            capacity,
+            refill_rate: capacity / per.as_secs_f64().max(f64::EPSILON),
+            state: Mutex::new(BucketState {
+                tokens: capacity,
+                refilled_at: time::Instant::now(),
+            }),
+        }
+    }
+
+    /// Takes a token, waiting until one is refilled if the bucket is empty. Tokens are handed out
+    /// in the order they are asked for.
+    pub(crate) async fn acquire(&self) {
+        let wait = {
+            let mut state = self.refill();
+            state.tokens -= 1.0;
+            (state.tokens < 0.0).then(|| Duration::from_secs_f64(-state.tokens / self.refill_rate))
+        };
+        if let Some(wait) = wait {
+            time::delay(wait).await;
+        }
+    }
+
+    /// Takes a token if one is left, or else returns how long until one is. Fails while requests
+    /// are waiting in [`acquire`](Self::acquire), so it never gets ahead of them.
+    pub(crate) fn try_acquire(&self) -> Result<(), Duration> {
+        let mut state = self.refill();
+        if state.tokens >= 1.0 {
+            state.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - state.tokens) / self.refill_rate,
+            ))
+        }
+    }
+
+    /// Adds the tokens refilled since the last call, up to the capacity of the bucket.
+    fn refill(&self) -> std::sync::MutexGuard<'_, BucketState> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let now = time::Instant::now();
+        let refilled = now.duration_since(state.refilled_at).as_secs_f64() * self.refill_rate;
+        state.tokens = (state.tokens + refilled).min(self.capacity);
+        state.refilled_at = now;
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    #[test]
+    fn test_from_headers() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let cases = [
+            (
+                headers(&[
+                    ("x-ratelimit-limit", "10"),
+                    ("x-ratelimit-remaining", "0"),
+                    ("x-ratelimit-reset", "1700000043"),
+                ]),
+                Some(RateLimitInfo {
+                    limit: Some(10),
+                    remaining: Some(0),
+                    reset_at: Some(now + Duration::from_secs(43)),
+                }),
+            ),
+            // Seconds from now
+            (
+                headers(&[("x-ratelimit-reset", "43")]),
+                Some(RateLimitInfo {
+                    limit: None,
+                    remaining: None,
+                    reset_at: Some(now + Duration::from_secs(43)),
+                }),
+            ),
+            (
+                headers(&[("x-ratelimit-limit", "ten"), ("content-type", "text/plain")]),
+                None,
+            ),
+            (HeaderMap::new(), None),
+        ];
+
+        for (headers, expected) in cases {
+            assert_eq!(
+                RateLimitInfo::from_headers_at(&headers, now),
+                expected,
+                "{headers:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_try_acquire() {
+        let bucket = TokenBucket::new(2, Duration::from_secs(1));
+        assert_eq!(bucket.try_acquire(), Ok(()));
+        assert_eq!(bucket.try_acquire(), Ok(()));
+        let wait = bucket.try_acquire().unwrap_err();
+        assert!(wait <= Duration::from_millis(500), "{wait:?}");
+
+        // A request waiting for the next token goes first
+        let bucket = std::sync::Arc::new(TokenBucket::new(1, Duration::from_millis(200)));
+        bucket.acquire().await;
+        let waiting = tokio::spawn({
+            let bucket = bucket.clone();
+            async move { bucket.acquire().await }
+        });
+        tokio::task::yield_now().await;
+        let wait = bucket.try_acquire().unwrap_err();
+        assert!(wait > Duration::from_millis(200), "{wait:?}");
+        waiting.await.unwrap();
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_445_412_400); // Wed, 21 Oct 2015 07:26:40 GMT
+        let cases = [
+            ("120", Some(Duration::from_secs(120))),
+            (" 0 ", Some(Duration::ZERO)),
+            (
+                "Wed, 21 Oct 2015 07:28:00 GMT",
+                Some(Duration::from_secs(80)),
+            ),
+            (
+                "Thu, 29 Feb 2024 00:00:00 GMT",
+                Some(Duration::from_secs(263_752_400)),
+            ),
+            // Already passed
+            ("Tue, 20 Oct 2015 07:28:00 GMT", Some(Duration::ZERO)),
+            ("Wed, 21 Oct 2015 07:28:00 UTC", None),
+            ("21 Oct 2015 07:28:00 GMT", None),
+            ("-5", None),
+            ("soon", None),
+        ];
+
+        for (value, expected) in cases {
+            assert_eq!(parse_retry_after(value, now), expected, "{value:?}");
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::{FirecrawlApp, FirecrawlError, routes};
+
+impl FirecrawlApp {
+    /// Sends a request to an endpoint the SDK doesn't wrap yet, returning the JSON of the
+    /// response.
+    ///
+    /// A relative `path` is joined below the API version, e.g. `team/queue-status` to
+    /// `{api_url}/v1/team/queue-status`, and one starting with `/` below the API URL itself, e.g.
+    /// `/v2/scrape` for a beta. Segments are percent-encoded, so `path` can't carry a query.
+    ///
+    /// The request goes through the same headers, middlewares, hooks and error handling as the
+    /// wrapped endpoints. An empty `204` or `202` response is returned as `Value::Null`.
+    pub async fn request_raw(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<Value>,
+    ) -> Result<Value, FirecrawlError> {
+        self.request(method, path, body).await
+    }
+
+    /// Like [`request_raw`](Self::request_raw), deserializing the response as `T`.
+    pub async fn request<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<Value>,
+    ) -> Result<T, FirecrawlError> {
+        let action = format!("{method} {path}");
+        let mut request = self
+            .inner
+            .client
+            .request(method, self.endpoint(routes::custom(path)))
+            .headers(self.prepare_headers(None)?);
+        if let Some(body) = &body {
+            request = request.json(body);
+        }
+
+        let response = self
+            .send_request(request)
+            .await
+            .map_err(|e| FirecrawlError::HttpError(action.clone(), e))?;
+        self.handle_response(response, action).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+    use serde_json::json;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{body_json, header, method, path},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_request_raw_get_and_post() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/team/queue-status"))
+            .and(header("authorization", "Bearer fc-test"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "jobsInQueue": 3
+            })))
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v2/scrape"))
+            .and(body_json(json!({ "url": "https://example.com" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "data": { "markdown": "# Example" }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), Some("fc-test")).unwrap();
+        let status = app
+            .request_raw(Method::GET, "team/queue-status", None)
+            .await
+            .unwrap();
+        assert_eq!(status["jobsInQueue"], 3);
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct QueueStatus {
+            jobs_in_queue: u32,
+        }
+        let status: QueueStatus = app
+            .request(Method::GET, "team/queue-status", None)
+            .await
+            .unwrap();
+        assert_eq!(status.jobs_in_queue, 3);
+
+        let scraped = app
+            .request_raw(
+                Method::POST,
+                "/v2/scrape",
+                Some(json!({ "url": "https://example.com" })),
+            )
+            .await
+            .unwrap();
+        assert_eq!(scraped["data"]["markdown"], "# Example");
+    }
+
+    #[tokio::test]
+    async fn test_request_raw_error_mapping() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/deep-research"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+                "success": false,
+                "error": "Bad Request",
+                "details": [{ "path": ["query"], "message": "Required" }]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/team/queue-status"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "success": true })))
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let error = app
+            .request_raw(Method::POST, "deep-research", Some(json!({})))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&error, FirecrawlError::APIError(action, e)
+                if action == "POST deep-research" && e.status_code == Some(400)
+                    && e.details.is_some()),
+            "{error:?}"
+        );
+
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct QueueStatus {
+            jobs_in_queue: u32,
+        }
+        let error = app
+            .request::<QueueStatus>(Method::GET, "team/queue-status", None)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(error, FirecrawlError::ResponseParseErrorText(_)),
+            "{error:?}"
+        );
+    }
+}
@@ -0,0 +1,188 @@
+//! Paths of every API route, relative to the API URL.
+//!
+//! Endpoints build their URLs with [`FirecrawlApp::endpoint`](crate::FirecrawlApp::endpoint)
+//! from these constructors only, so a path is never spelled out twice.
+
+/// Version prefix of the versioned routes, unless changed with
+/// [`FirecrawlApp::with_api_version`](crate::FirecrawlApp::with_api_version).
+pub(crate) const API_VERSION: &str = "v1";
+
+/// A route of the API: its path segments and an optional query parameter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Route {
+    /// Whether the segments are below the API version, e.g. `/v1/scrape`.
+    pub(crate) versioned: bool,
+    pub(crate) segments: Vec<String>,
+    pub(crate) query: Option<(&'static str, String)>,
+}
+
+impl Route {
+    fn versioned(segments: &[&str]) -> Self {
+        Self {
+            versioned: true,
+            segments: segments.iter().map(|s| s.to_string()).collect(),
+            query: None,
+        }
+    }
+
+    fn unversioned(segments: &[&str]) -> Self {
+        Self {
+            versioned: false,
+            ..Self::versioned(segments)
+        }
+    }
+
+    /// The path and query of the route, as sent below the API URL with the default version.
+    /// Used by the tests.
+    #[cfg(test)]
+    fn path(&self) -> String {
+        let version = self.versioned.then_some(API_VERSION);
+        let segments: Vec<&str> = version
+            .into_iter()
+            .chain(self.segments.iter().map(String::as_str))
+            .collect();
+        let mut path = format!("/{}", segments.join("/"));
+        if let Some((name, value)) = &self.query {
+            path.push_str(&format!("?{name}={value}"));
+        }
+        path
+    }
+}
+
+#[cfg(feature = "scrape")]
+pub(crate) fn scrape() -> Route {
+    Route::versioned(&["scrape"])
+}
+
+#[cfg(feature = "batch")]
+pub(crate) fn batch_scrape() -> Route {
+    Route::versioned(&["batch", "scrape"])
+}
+
+#[cfg(feature = "batch")]
+pub(crate) fn batch_scrape_status(id: &str) -> Route {
+    Route::versioned(&["batch", "scrape", id])
+}
+
+/// Page of the results of a batch scrape starting at `cursor`.
+#[cfg(feature = "batch")]
+pub(crate) fn batch_cursor(id: &str, cursor: &str) -> Route {
+    Route {
+        query: Some(("cursor", cursor.to_string())),
+        ..batch_scrape_status(id)
+    }
+}
+
+#[cfg(feature = "crawl")]
+pub(crate) fn crawl() -> Route {
+    Route::versioned(&["crawl"])
+}
+
+#[cfg(feature = "crawl")]
+pub(crate) fn crawl_status(id: &str) -> Route {
+    Route::versioned(&["crawl", id])
+}
+
+#[cfg(feature = "map")]
+pub(crate) fn map() -> Route {
+    Route::versioned(&["map"])
+}
+
+#[cfg(feature = "search")]
+pub(crate) fn search() -> Route {
+    Route::versioned(&["search"])
+}
+
+#[cfg(feature = "extract")]
+pub(crate) fn extract() -> Route {
+    Route::versioned(&["extract"])
+}
+
+#[cfg(feature = "extract")]
+pub(crate) fn extract_status(id: &str) -> Route {
+    Route::versioned(&["extract", id])
+}
+
+#[cfg(feature = "llmstxt")]
+pub(crate) fn llmstxt() -> Route {
+    Route::versioned(&["llmstxt"])
+}
+
+#[cfg(feature = "llmstxt")]
+pub(crate) fn llmstxt_status(id: &str) -> Route {
+    Route::versioned(&["llmstxt", id])
+}
+
+pub(crate) fn credit_usage() -> Route {
+    Route::versioned(&["team", "credit-usage"])
+}
+
+pub(crate) fn token_usage() -> Route {
+    Route::versioned(&["team", "token-usage"])
+}
+
+/// Health check of self-hosted instances, which isn't versioned.
+pub(crate) fn is_production_ready() -> Route {
+    Route::unversioned(&["is-production-ready"])
+}
+
+/// Health endpoint of self-hosted instances, which isn't versioned either.
+pub(crate) fn health() -> Route {
+    Route::unversioned(&["health"])
+}
+
+/// Route of an endpoint the SDK doesn't wrap, see `FirecrawlApp::request_raw`: `path` below the
+/// API version, or below the API URL itself when it starts with `/`.
+pub(crate) fn custom(path: &str) -> Route {
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    if path.starts_with('/') {
+        Route::unversioned(&segments)
+    } else {
+        Route::versioned(&segments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_paths() {
+        let cases = [
+            #[cfg(feature = "scrape")]
+            (scrape(), "/v1/scrape"),
+            #[cfg(feature = "batch")]
+            (batch_scrape(), "/v1/batch/scrape"),
+            #[cfg(feature = "batch")]
+            (batch_scrape_status("abc"), "/v1/batch/scrape/abc"),
+            #[cfg(feature = "batch")]
+            (batch_cursor("abc", "10"), "/v1/batch/scrape/abc?cursor=10"),
+            #[cfg(feature = "crawl")]
+            (crawl(), "/v1/crawl"),
+            #[cfg(feature = "crawl")]
+            (crawl_status("abc"), "/v1/crawl/abc"),
+            #[cfg(feature = "map")]
+            (map(), "/v1/map"),
+            #[cfg(feature = "search")]
+            (search(), "/v1/search"),
+            #[cfg(feature = "extract")]
+            (extract(), "/v1/extract"),
+            #[cfg(feature = "extract")]
+            (extract_status("abc"), "/v1/extract/abc"),
+            #[cfg(feature = "llmstxt")]
+            (llmstxt(), "/v1/llmstxt"),
+            #[cfg(feature = "llmstxt")]
+            (llmstxt_status("abc"), "/v1/llmstxt/abc"),
+            (credit_usage(), "/v1/team/credit-usage"),
+            (token_usage(), "/v1/team/token-usage"),
+            (is_production_ready(), "/is-production-ready"),
+            (health(), "/health"),
+            (custom("team/queue-status"), "/v1/team/queue-status"),
+            (custom("/v2/scrape/"), "/v2/scrape"),
+        ];
+
+        for (route, expected) in cases {
+            assert_eq!(route.path(), expected);
+        }
+    }
+}
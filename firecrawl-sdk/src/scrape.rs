@@ -1,12 +1,17 @@
 use std::collections::HashMap;
 
+use futures::{StreamExt, stream};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 #[cfg(feature = "mcp-tool")]
 use schemars::JsonSchema;
 
-use crate::{API_VERSION, FirecrawlApp, FirecrawlError, document::Document};
+use crate::{
+    CostOperation, FirecrawlApp, FirecrawlError, apply_timeout,
+    document::{CacheValidators, Document},
+    routes,
+};
 
 #[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "mcp-tool", derive(JsonSchema))]
@@ -29,7 +34,8 @@ pub enum ScrapeFormats {
 
     /// Will result in a URL to a screenshot of the page.
     ///
-    /// Can not be used in conjunction with `ScrapeFormats::ScreenshotFullPage`.
+    /// Can not be used in conjunction with `ScrapeFormats::ScreenshotFullPage`. See
+    /// `ScrapeOptions.screenshot_options` for more options.
     #[serde(rename = "screenshot")]
     Screenshot,
 
@@ -76,6 +82,38 @@ pub struct JsonOptions {
     pub prompt: Option<String>,
 }
 
+impl JsonOptions {
+    /// Options extracting with `schema`, failing with a configuration error unless it is an
+    /// object with a `type` key, so a malformed schema is caught before the scrape is sent.
+    pub fn with_validated_schema(schema: Value) -> Result<Self, FirecrawlError> {
+        let Some(object) = schema.as_object() else {
+            return Err(FirecrawlError::configuration(format!(
+                "JSON schema must be an object, got {schema}"
+            )));
+        };
+        if !object.contains_key("type") {
+            return Err(FirecrawlError::configuration(format!(
+                "JSON schema has no `type` key: {schema}"
+            )));
+        }
+
+        Ok(Self {
+            schema: Some(schema),
+            ..Self::default()
+        })
+    }
+
+    /// Options extracting with the JSON Schema generated from `T`, so `Document::json` can be
+    /// deserialized back into `T`.
+    #[cfg(feature = "mcp-tool")]
+    pub fn from_schema<T: JsonSchema>() -> Self {
+        Self {
+            schema: Some(schemars::schema_for!(T).into()),
+            ..Self::default()
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "mcp-tool", derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
@@ -140,6 +178,85 @@ pub struct Action {
     pub full_page: Option<bool>,
 }
 
+impl Action {
+    fn new(action_type: ActionType) -> Self {
+        Action {
+            action_type,
+            ..Default::default()
+        }
+    }
+
+    /// Clicks the element matching `selector`.
+    pub fn click(selector: impl Into<String>) -> Self {
+        Action {
+            selector: Some(selector.into()),
+            ..Action::new(ActionType::Click)
+        }
+    }
+
+    /// Types `text` into the element matching `selector`.
+    pub fn type_text(selector: impl Into<String>, text: impl Into<String>) -> Self {
+        Action {
+            selector: Some(selector.into()),
+            text: Some(text.into()),
+            ..Action::new(ActionType::Type)
+        }
+    }
+
+    /// Waits for `milliseconds`.
+    pub fn wait_ms(milliseconds: u32) -> Self {
+        Action {
+            milliseconds: Some(milliseconds),
+            ..Action::new(ActionType::Wait)
+        }
+    }
+
+    /// Waits until an element matches `selector`.
+    pub fn wait_for(selector: impl Into<String>) -> Self {
+        Action {
+            selector: Some(selector.into()),
+            ..Action::new(ActionType::Wait)
+        }
+    }
+
+    /// Presses `key`, e.g. `Enter`.
+    pub fn press(key: impl Into<String>) -> Self {
+        Action {
+            key: Some(key.into()),
+            ..Action::new(ActionType::Press)
+        }
+    }
+
+    /// Scrolls the page `up` or `down`.
+    pub fn scroll(direction: impl Into<String>) -> Self {
+        Action {
+            direction: Some(direction.into()),
+            ..Action::new(ActionType::Scroll)
+        }
+    }
+
+    /// Takes a screenshot of the viewport, or of the whole page with `full_page`.
+    pub fn screenshot(full_page: bool) -> Self {
+        Action {
+            full_page: Some(full_page),
+            ..Action::new(ActionType::Screenshot)
+        }
+    }
+
+    /// Runs `script` in the page. Its return value is in `ActionResults.javascript_returns`.
+    pub fn execute_js(script: impl Into<String>) -> Self {
+        Action {
+            script: Some(script.into()),
+            ..Action::new(ActionType::ExecuteJavascript)
+        }
+    }
+
+    /// Scrapes the page in its current state. The result is in `ActionResults.scrapes`.
+    pub fn scrape() -> Self {
+        Action::new(ActionType::Scrape)
+    }
+}
+
 #[serde_with::skip_serializing_none]
 #[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "mcp-tool", derive(JsonSchema))]
@@ -152,8 +269,81 @@ pub struct LocationOptions {
     pub languages: Vec<String>,
 }
 
+/// Options of the screenshot format, see `ScrapeOptions.screenshot_options`.
 #[serde_with::skip_serializing_none]
 #[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenshotOptions {
+    /// Capture the full scrollable page instead of the viewport. (default: `false`)
+    pub full_page: Option<bool>,
+
+    /// JPEG quality of the screenshot, from 1 to 100.
+    pub quality: Option<u8>,
+
+    /// Size of the browser window the screenshot is taken in.
+    pub viewport: Option<Viewport>,
+}
+
+/// Size of a browser window, in CSS pixels.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Viewport {
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Entry of the `formats` array sent to the API.
+#[derive(Serialize)]
+#[serde(untagged)]
+enum FormatBody {
+    Name(ScrapeFormats),
+    Screenshot {
+        r#type: ScrapeFormats,
+        #[serde(flatten)]
+        options: ScreenshotOptions,
+    },
+}
+
+/// Turns `formats` into the `formats` array of a request, sending `screenshot` as the object
+/// of the screenshot format.
+fn format_bodies(
+    formats: Option<&Vec<ScrapeFormats>>,
+    screenshot: Option<&ScreenshotOptions>,
+) -> Option<Vec<FormatBody>> {
+    let Some(screenshot) = screenshot else {
+        return formats.map(|formats| formats.iter().copied().map(FormatBody::Name).collect());
+    };
+
+    let screenshot_body = |full_page: bool| FormatBody::Screenshot {
+        r#type: ScrapeFormats::Screenshot,
+        options: ScreenshotOptions {
+            full_page: screenshot.full_page.or(full_page.then_some(true)),
+            ..screenshot.clone()
+        },
+    };
+    let default_formats = [ScrapeFormats::Markdown];
+    let mut bodies = Vec::new();
+    let mut has_screenshot = false;
+    for format in formats.map_or(&default_formats[..], |formats| &formats[..]) {
+        match format {
+            ScrapeFormats::Screenshot | ScrapeFormats::ScreenshotFullPage => {
+                if !has_screenshot {
+                    bodies.push(screenshot_body(
+                        *format == ScrapeFormats::ScreenshotFullPage,
+                    ));
+                    has_screenshot = true;
+                }
+            }
+            format => bodies.push(FormatBody::Name(*format)),
+        }
+    }
+    if !has_screenshot {
+        bodies.push(screenshot_body(false));
+    }
+    Some(bodies)
+}
+
+// Serialized through `ScrapeOptionsBody`, to send `screenshot_options` inside `formats`
+#[derive(Deserialize, Debug, Default, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "mcp-tool", derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ScrapeOptions {
@@ -161,6 +351,15 @@ pub struct ScrapeOptions {
     #[cfg_attr(feature = "mcp-tool", schemars(skip))]
     pub formats: Option<Vec<ScrapeFormats>>,
 
+    /// Options of the screenshot, sent as the screenshot entry of `formats`.
+    ///
+    /// Adds a screenshot to `formats` if it has none, keeping the default markdown format when
+    /// `formats` is unset. The full-page setting of `ScrapeFormats::ScreenshotFullPage` applies
+    /// unless `full_page` is set.
+    #[serde(skip)]
+    #[cfg_attr(feature = "mcp-tool", schemars(skip))]
+    pub screenshot_options: Option<ScreenshotOptions>,
+
     /// Extract only the main content, filtering out navigation, footers, etc. (default: `true`)
     pub only_main_content: Option<bool>,
 
@@ -207,6 +406,259 @@ pub struct ScrapeOptions {
     /// Proxy configuration to use (values: "none", "basic", "residential") (default: `"none"`)
     #[cfg_attr(feature = "mcp-tool", schemars(skip))]
     pub proxy: Option<String>,
+
+    /// Serve a cached version of the page if it was scraped less than this many milliseconds
+    /// ago, which is faster and cheaper than scraping it again, e.g. `3600000` to accept an
+    /// hour-old copy. (default: `0`, always scrape)
+    pub max_age: Option<u32>,
+
+    /// Store the scraped page in the cache, to be served to later scrapes with `max_age`.
+    /// (default: `true`)
+    pub store_in_cache: Option<bool>,
+}
+
+option_setters!(ScrapeOptions,
+    /// Setters to build options without struct update syntax.
+    ///
+    /// ```
+    /// use firecrawl_sdk::scrape::{ScrapeFormats, ScrapeOptions};
+    ///
+    /// let options = ScrapeOptions::default()
+    ///     .with_formats([ScrapeFormats::Markdown, ScrapeFormats::Links])
+    ///     .with_only_main_content(true)
+    ///     .with_timeout(30_000);
+    /// assert_eq!(options.timeout, Some(30_000));
+    /// ```
+    {
+        with_formats => formats: impl Into<Vec<ScrapeFormats>>,
+        with_screenshot_options => screenshot_options: ScreenshotOptions,
+        with_only_main_content => only_main_content: bool,
+        with_include_tags => include_tags: impl Into<Vec<String>>,
+        with_exclude_tags => exclude_tags: impl Into<Vec<String>>,
+        with_headers => headers: impl Into<HashMap<String, String>>,
+        with_wait_for => wait_for: u32,
+        with_timeout => timeout: u32,
+        with_json_options => json_options: JsonOptions,
+        with_location => location: LocationOptions,
+        with_actions => actions: impl Into<Vec<Action>>,
+        with_mobile => mobile: bool,
+        with_skip_tls_verification => skip_tls_verification: bool,
+        with_remove_base64_images => remove_base64_images: bool,
+        with_block_ads => block_ads: bool,
+        with_proxy => proxy: impl Into<String>,
+        with_max_age => max_age: u32,
+        with_store_in_cache => store_in_cache: bool,
+    }
+);
+
+/// Credits charged for scraping a page.
+const PAGE_CREDITS: u64 = 1;
+
+/// Extra credits charged per page for `ScrapeFormats::JSON`.
+const JSON_CREDITS: u64 = 4;
+
+/// Extra credits charged per page for a `residential` or `stealth` proxy.
+const PROXY_CREDITS: u64 = 4;
+
+impl ScrapeOptions {
+    /// Options for JavaScript-heavy single-page applications: waits 3 seconds for the page to
+    /// render, within a 60 second timeout, and keeps the main content as Markdown.
+    ///
+    /// The API has no switch to block resources other than ads, so scripts, styles and images
+    /// still load as the page needs them.
+    pub fn spa_preset() -> Self {
+        Self::default()
+            .with_formats([ScrapeFormats::Markdown])
+            .with_only_main_content(true)
+            .with_wait_for(3_000)
+            .with_timeout(60_000)
+            .with_block_ads(true)
+    }
+
+    /// Options for fetching the text of static pages quickly: the main content as Markdown,
+    /// without waiting, ads or base64 images, within a 15 second timeout.
+    pub fn fast_text_preset() -> Self {
+        Self::default()
+            .with_formats([ScrapeFormats::Markdown])
+            .with_only_main_content(true)
+            .with_wait_for(0)
+            .with_timeout(15_000)
+            .with_block_ads(true)
+            .with_remove_base64_images(true)
+    }
+
+    /// Options for archiving a page as completely as possible: the whole page as Markdown,
+    /// HTML and raw HTML, its links and a full-page screenshot, after waiting 2 seconds for
+    /// dynamic content.
+    pub fn full_capture_preset() -> Self {
+        Self::default()
+            .with_formats([
+                ScrapeFormats::Markdown,
+                ScrapeFormats::HTML,
+                ScrapeFormats::RawHTML,
+                ScrapeFormats::Links,
+                ScrapeFormats::ScreenshotFullPage,
+            ])
+            .with_only_main_content(false)
+            .with_wait_for(2_000)
+            .with_timeout(90_000)
+            .with_block_ads(false)
+            .with_remove_base64_images(false)
+    }
+
+    /// Fails with `FirecrawlError::InvalidOptions` if the options can't be combined, so the
+    /// scrape isn't sent only to be rejected by the API.
+    pub(crate) fn validate(&self) -> Result<(), FirecrawlError> {
+        let formats = self.formats.as_deref().unwrap_or_default();
+        if formats.contains(&ScrapeFormats::Screenshot)
+            && formats.contains(&ScrapeFormats::ScreenshotFullPage)
+        {
+            return Err(FirecrawlError::InvalidOptions(
+                "`ScrapeFormats::Screenshot` and `ScrapeFormats::ScreenshotFullPage` can't be combined"
+                    .to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Estimates the credits charged for scraping one URL with these options.
+    ///
+    /// A page costs 1 credit, plus 4 with `ScrapeFormats::JSON` and 4 with a `residential` or
+    /// `stealth` proxy. The API may charge more than estimated, e.g. for PDFs with many pages.
+    pub fn estimated_credits_per_url(&self) -> u64 {
+        let mut credits = PAGE_CREDITS;
+        if self
+            .formats
+            .as_ref()
+            .is_some_and(|formats| formats.contains(&ScrapeFormats::JSON))
+        {
+            credits += JSON_CREDITS;
+        }
+        if matches!(self.proxy.as_deref(), Some("residential" | "stealth")) {
+            credits += PROXY_CREDITS;
+        }
+        credits
+    }
+
+    /// Scrapes the page live and keeps the result out of the cache: sets `max_age` to `0` and
+    /// `store_in_cache` to `false`.
+    pub fn bypass_cache(self) -> Self {
+        self.with_max_age(0).with_store_in_cache(false)
+    }
+
+    /// Adds `If-None-Match` and `If-Modified-Since` to `headers` from the validators of an
+    /// earlier scrape, so the page can answer `304 Not Modified` if it hasn't changed.
+    pub fn with_conditional_headers(mut self, validators: &CacheValidators) -> Self {
+        let headers = self.headers.get_or_insert_with(HashMap::new);
+        if let Some(etag) = &validators.etag {
+            headers.insert("If-None-Match".to_string(), etag.clone());
+        }
+        if let Some(last_modified) = &validators.last_modified {
+            headers.insert("If-Modified-Since".to_string(), last_modified.clone());
+        }
+        if headers.is_empty() {
+            self.headers = None;
+        }
+        self
+    }
+
+    /// Fills the options left unset with `defaults`, e.g. defaults of a server applied to the
+    /// options of its clients. Options that are set are kept as a whole, `headers` and `actions`
+    /// included.
+    pub fn merge(self, defaults: &ScrapeOptions) -> ScrapeOptions {
+        // Destructured so that new fields can't be forgotten
+        let ScrapeOptions {
+            formats,
+            screenshot_options,
+            only_main_content,
+            include_tags,
+            exclude_tags,
+            headers,
+            wait_for,
+            timeout,
+            json_options,
+            location,
+            actions,
+            mobile,
+            skip_tls_verification,
+            remove_base64_images,
+            block_ads,
+            proxy,
+            max_age,
+            store_in_cache,
+        } = self;
+
+        ScrapeOptions {
+            formats: formats.or_else(|| defaults.formats.clone()),
+            screenshot_options: screenshot_options.or_else(|| defaults.screenshot_options.clone()),
+            only_main_content: only_main_content.or(defaults.only_main_content),
+            include_tags: include_tags.or_else(|| defaults.include_tags.clone()),
+            exclude_tags: exclude_tags.or_else(|| defaults.exclude_tags.clone()),
+            headers: headers.or_else(|| defaults.headers.clone()),
+            wait_for: wait_for.or(defaults.wait_for),
+            timeout: timeout.or(defaults.timeout),
+            json_options: json_options.or_else(|| defaults.json_options.clone()),
+            location: location.or_else(|| defaults.location.clone()),
+            actions: actions.or_else(|| defaults.actions.clone()),
+            mobile: mobile.or(defaults.mobile),
+            skip_tls_verification: skip_tls_verification.or(defaults.skip_tls_verification),
+            remove_base64_images: remove_base64_images.or(defaults.remove_base64_images),
+            block_ads: block_ads.or(defaults.block_ads),
+            proxy: proxy.or_else(|| defaults.proxy.clone()),
+            max_age: max_age.or(defaults.max_age),
+            store_in_cache: store_in_cache.or(defaults.store_in_cache),
+        }
+    }
+}
+
+/// Wire form of `ScrapeOptions`.
+#[serde_with::skip_serializing_none]
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ScrapeOptionsBody<'a> {
+    formats: Option<Vec<FormatBody>>,
+    only_main_content: Option<bool>,
+    include_tags: Option<&'a Vec<String>>,
+    exclude_tags: Option<&'a Vec<String>>,
+    headers: Option<&'a HashMap<String, String>>,
+    wait_for: Option<u32>,
+    timeout: Option<u32>,
+    #[serde(rename = "jsonOptions")]
+    json_options: Option<&'a JsonOptions>,
+    location: Option<&'a LocationOptions>,
+    actions: Option<&'a Vec<Action>>,
+    mobile: Option<bool>,
+    skip_tls_verification: Option<bool>,
+    remove_base64_images: Option<bool>,
+    block_ads: Option<bool>,
+    proxy: Option<&'a String>,
+    max_age: Option<u32>,
+    store_in_cache: Option<bool>,
+}
+
+impl Serialize for ScrapeOptions {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ScrapeOptionsBody {
+            formats: format_bodies(self.formats.as_ref(), self.screenshot_options.as_ref()),
+            only_main_content: self.only_main_content,
+            include_tags: self.include_tags.as_ref(),
+            exclude_tags: self.exclude_tags.as_ref(),
+            headers: self.headers.as_ref(),
+            wait_for: self.wait_for,
+            timeout: self.timeout,
+            json_options: self.json_options.as_ref(),
+            location: self.location.as_ref(),
+            actions: self.actions.as_ref(),
+            mobile: self.mobile,
+            skip_tls_verification: self.skip_tls_verification,
+            remove_base64_images: self.remove_base64_images,
+            block_ads: self.block_ads,
+            proxy: self.proxy.as_ref(),
+            max_age: self.max_age,
+            store_in_cache: self.store_in_cache,
+        }
+        .serialize(serializer)
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
@@ -219,15 +671,20 @@ pub struct ScrapeRequestBody {
     pub options: ScrapeOptions,
 }
 
+/// Response of the scrape endpoint as sent by the API, returned by
+/// [`FirecrawlApp::scrape_url_raw`].
+#[serde_with::skip_serializing_none]
 #[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
-struct ScrapeResponse {
+pub struct ScrapeResponse {
     /// This will always be `true` due to `FirecrawlApp::handle_response`.
-    /// No need to expose.
-    success: bool,
+    pub success: bool,
 
     /// The resulting document.
-    data: Document,
+    pub data: Document,
+
+    /// Warning about the request, e.g. an option the API ignored.
+    pub warning: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
@@ -243,33 +700,346 @@ pub struct ScrapeUrlInput {
 
 impl FirecrawlApp {
     /// Scrapes a URL using the Firecrawl API.
+    ///
+    /// When `ScrapeOptions.timeout` is set, the HTTP request is given that long plus a grace
+    /// period, overriding the timeout of the client.
     pub async fn scrape_url(
         &self,
         url: impl AsRef<str>,
         options: impl Into<Option<ScrapeOptions>>,
     ) -> Result<Document, FirecrawlError> {
+        self.scrape_url_with_idempotency(url, options, None).await
+    }
+
+    /// Scrapes a URL like [`scrape_url`](Self::scrape_url), sending `idempotency_key` so a
+    /// retried request isn't charged twice.
+    pub async fn scrape_url_with_idempotency(
+        &self,
+        url: impl AsRef<str>,
+        options: impl Into<Option<ScrapeOptions>>,
+        idempotency_key: Option<String>,
+    ) -> Result<Document, FirecrawlError> {
+        let (response, request_id) = self
+            .send_scrape(url, options.into(), idempotency_key)
+            .await?;
+
+        let mut document = response.data;
+        document.request_id = request_id;
+        self.cap_html(std::slice::from_mut(&mut document));
+        Ok(document)
+    }
+
+    /// Scrapes a URL like [`scrape_url`](Self::scrape_url), returning the response of the API
+    /// as is, e.g. to forward it unchanged. `FirecrawlApp::with_max_html_size` doesn't apply.
+    pub async fn scrape_url_raw(
+        &self,
+        url: impl AsRef<str>,
+        options: impl Into<Option<ScrapeOptions>>,
+    ) -> Result<ScrapeResponse, FirecrawlError> {
+        let (response, _) = self.send_scrape(url, options.into(), None).await?;
+        Ok(response)
+    }
+
+    /// Sends a scrape request, returning the response and the id of the request.
+    async fn send_scrape(
+        &self,
+        url: impl AsRef<str>,
+        options: Option<ScrapeOptions>,
+        idempotency_key: Option<String>,
+    ) -> Result<(ScrapeResponse, Option<String>), FirecrawlError> {
         let body = ScrapeRequestBody {
             url: url.as_ref().to_string(),
-            options: options.into().unwrap_or_default(),
+            options: options.unwrap_or_default(),
         };
+        body.options.validate()?;
 
-        let headers = self.prepare_headers(None);
+        let idempotency_key = self.idempotency_key(idempotency_key);
+        let headers = self.prepare_headers(idempotency_key.as_ref())?;
 
-        let response = self
+        let request = self
+            .inner
             .client
-            .post(format!("{}/{}/scrape", self.api_url, API_VERSION))
+            .post(self.endpoint(routes::scrape()))
             .headers(headers)
-            .json(&body)
-            .send()
+            .json(&body);
+        let request = apply_timeout(request, crate::scrape_request_timeout(body.options.timeout));
+        let response = self
+            .send_request(request)
             .await
             .map_err(|e| FirecrawlError::HttpError(format!("Scraping {:?}", url.as_ref()), e))?;
 
+        #[cfg(not(feature = "strict"))]
         let response = self
-            .handle_response::<ScrapeResponse>(response, "scrape URL")
+            .handle_response_with_request_id::<ScrapeResponse>(response, "scrape URL")
             .await?;
+        #[cfg(feature = "strict")]
+        let response = {
+            let (body, request_id) = self
+                .handle_response_with_request_id::<serde_json::Value>(response, "scrape URL")
+                .await?;
+            self.warn_unknown_fields(crate::strict::unknown_fields_warning::<Document>(
+                &body["data"],
+            ));
+            let body = serde_json::from_value(body).map_err(FirecrawlError::ResponseParseError)?;
+            (body, request_id)
+        };
+
+        self.report_cost(
+            CostOperation::Scrape,
+            std::slice::from_ref(&body.url),
+            body.options.estimated_credits_per_url(),
+            true,
+            None,
+        );
+        Ok(response)
+    }
+
+    /// Scrapes the main content of a URL as plain text, e.g. for NLP pipelines.
+    ///
+    /// Requests markdown with `only_main_content` and strips the markdown syntax: headings, emphasis,
+    /// links (keeping their text), images, code fences, list markers, block quotes and tables.
+    pub async fn scrape_text(
+        &self,
+        url: impl AsRef<str>,
+        options: impl Into<Option<ScrapeOptions>>,
+    ) -> Result<String, FirecrawlError> {
+        let options = ScrapeOptions {
+            formats: Some(vec![ScrapeFormats::Markdown]),
+            only_main_content: Some(true),
+            ..options.into().unwrap_or_default()
+        };
+
+        let document = self.scrape_url(url, options).await?;
+
+        Ok(markdown_to_text(
+            document.markdown.as_deref().unwrap_or_default(),
+        ))
+    }
+
+    /// Scrapes a URL like [`scrape_url`](Self::scrape_url), returning `None` when the API served
+    /// it from its cache, i.e. the page wasn't fetched again since it was last scraped, e.g. for
+    /// pipelines only processing changed pages.
+    ///
+    /// See [`Document::is_cache_hit`].
+    pub async fn scrape_url_if_changed(
+        &self,
+        url: impl AsRef<str>,
+        options: impl Into<Option<ScrapeOptions>>,
+    ) -> Result<Option<Document>, FirecrawlError> {
+        let document = self.scrape_url(url, options).await?;
+        Ok((!document.is_cache_hit()).then_some(document))
+    }
+
+    /// Scrapes `urls` one request each, at most `max_concurrency` at a time, e.g. for a few URLs
+    /// that aren't worth polling a batch scrape job for.
+    ///
+    /// Returns one result per URL, in the order of `urls`, so a failing URL doesn't stop the
+    /// others. Fails with a configuration error if `max_concurrency` is 0.
+    pub async fn scrape_urls_concurrently(
+        &self,
+        urls: Vec<String>,
+        options: Option<ScrapeOptions>,
+        max_concurrency: usize,
+    ) -> Result<Vec<Result<Document, FirecrawlError>>, FirecrawlError> {
+        if max_concurrency == 0 {
+            return Err(FirecrawlError::configuration(
+                "max_concurrency must be at least 1",
+            ));
+        }
+
+        let mut results: Vec<_> = stream::iter(urls.iter().enumerate())
+            .map(|(i, url)| {
+                let options = options.clone();
+                async move { (i, self.scrape_url(url, options).await) }
+            })
+            .buffer_unordered(max_concurrency)
+            .collect()
+            .await;
+        results.sort_by_key(|(i, _)| *i);
+
+        Ok(results.into_iter().map(|(_, result)| result).collect())
+    }
+}
+
+/// Converts markdown to plain text, keeping one blank line between blocks.
+fn markdown_to_text(markdown: &str) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut in_code_block = false;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            lines.push(line.trim_end().to_string());
+            continue;
+        }
+        if is_thematic_break(trimmed) || is_table_separator(trimmed) {
+            continue;
+        }
+
+        let mut text = trimmed;
+        while let Some(rest) = text.strip_prefix('>') {
+            text = rest.trim_start();
+        }
+        if text.starts_with('#') {
+            text = text.trim_start_matches('#').trim_start();
+            text = text.trim_end_matches('#').trim_end();
+        }
+        text = strip_list_marker(text);
+
+        let text = if text.starts_with('|') {
+            text.trim_matches('|')
+                .split('|')
+                .map(|cell| strip_inline_markdown(cell.trim()))
+                .collect::<Vec<_>>()
+                .join("\t")
+        } else {
+            strip_inline_markdown(text)
+        };
+
+        // Collapse runs of blank lines
+        if text.trim().is_empty() && lines.last().is_none_or(|l| l.is_empty()) {
+            continue;
+        }
+        lines.push(text.trim_end().to_string());
+    }
+
+    lines.join("\n").trim().to_string()
+}
+
+/// `---`, `***` or `___`, optionally with spaces in between.
+fn is_thematic_break(line: &str) -> bool {
+    let chars: Vec<char> = line.chars().filter(|c| !c.is_whitespace()).collect();
+    chars.len() >= 3 && matches!(chars[0], '-' | '*' | '_') && chars.iter().all(|c| *c == chars[0])
+}
+
+/// Separator between the header and the body of a table, e.g. `| --- | :-: |`.
+fn is_table_separator(line: &str) -> bool {
+    line.starts_with('|')
+        && line.contains('-')
+        && line
+            .chars()
+            .all(|c| matches!(c, '|' | '-' | ':' | ' ' | '\t'))
+}
+
+fn strip_list_marker(line: &str) -> &str {
+    for marker in ["- [ ] ", "- [x] ", "- ", "* ", "+ "] {
+        if let Some(rest) = line.strip_prefix(marker) {
+            return rest;
+        }
+    }
 
-        Ok(response.data)
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 {
+        let rest = &line[digits..];
+        if let Some(rest) = rest.strip_prefix(". ").or_else(|| rest.strip_prefix(") ")) {
+            return rest;
+        }
     }
+
+    line
+}
+
+/// Removes links, images, inline code markers, emphasis and escapes from a single line.
+fn strip_inline_markdown(line: &str) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let mut output = String::with_capacity(line.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\\' if i + 1 < chars.len() && chars[i + 1].is_ascii_punctuation() => {
+                output.push(chars[i + 1]);
+                i += 2;
+            }
+            '!' if chars.get(i + 1) == Some(&'[') => match parse_inline_link(&chars, i + 1) {
+                // Images carry no text worth keeping
+                Some((_, end)) => i = end,
+                None => {
+                    output.push(c);
+                    i += 1;
+                }
+            },
+            '[' => match parse_inline_link(&chars, i) {
+                Some((text, end)) => {
+                    output.push_str(&strip_inline_markdown(&text));
+                    i = end;
+                }
+                None => {
+                    output.push(c);
+                    i += 1;
+                }
+            },
+            '`' => i += 1,
+            '*' | '_' | '~' => {
+                let run = chars[i..].iter().take_while(|x| **x == c).count();
+                let before = i.checked_sub(1).map(|j| chars[j]);
+                let after = chars.get(i + run).copied();
+                let is_word = |x: Option<char>| x.is_some_and(|x| x.is_alphanumeric());
+                // Delimiters sit at a word boundary, unlike the underscores of `snake_case`
+                let intraword = is_word(before) && is_word(after);
+                let free_standing =
+                    before.is_none_or(char::is_whitespace) && after.is_none_or(char::is_whitespace);
+                let is_delimiter = !intraword && !free_standing && (c != '~' || run >= 2);
+                if !is_delimiter {
+                    output.extend(std::iter::repeat_n(c, run));
+                }
+                i += run;
+            }
+            _ => {
+                output.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    output
+}
+
+/// Parses `[text](destination)` starting at `start`, returning the text and the index after the
+/// closing parenthesis.
+fn parse_inline_link(chars: &[char], start: usize) -> Option<(String, usize)> {
+    let mut depth = 0;
+    let mut text_end = None;
+    for (offset, c) in chars[start..].iter().enumerate() {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    text_end = Some(start + offset);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let text_end = text_end?;
+    if chars.get(text_end + 1) != Some(&'(') {
+        return None;
+    }
+
+    let mut depth = 0;
+    for (offset, c) in chars[text_end + 1..].iter().enumerate() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    let text = chars[start + 1..text_end].iter().collect();
+                    return Some((text, text_end + 1 + offset + 1));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
 }
 
 #[cfg(all(test, feature = "mcp-tool"))]
@@ -303,7 +1073,7 @@ mod schema_tests {
                         "milliseconds": {
                             "description": "Time to wait in milliseconds (for wait action)",
                             "format": "uint32",
-                            "minimum": 0.0,
+                            "minimum": 0,
                             "type": "integer"
                         },
                         "script": {
@@ -400,6 +1170,12 @@ mod schema_tests {
                 ],
                 "type": "object"
             },
+            "maxAge": {
+                "description": "Serve a cached version of the page if it was scraped less than this many milliseconds\nago, which is faster and cheaper than scraping it again, e.g. `3600000` to accept an\nhour-old copy. (default: `0`, always scrape)",
+                "format": "uint32",
+                "minimum": 0,
+                "type": "integer"
+            },
             "mobile": {
                 "description": "Use mobile viewport. (default: `false`)",
                 "type": "boolean"
@@ -416,16 +1192,20 @@ mod schema_tests {
                 "description": "Skip TLS certificate verification. (default: `false`)",
                 "type": "boolean"
             },
+            "storeInCache": {
+                "description": "Store the scraped page in the cache, to be served to later scrapes with `max_age`.\n(default: `true`)",
+                "type": "boolean"
+            },
             "timeout": {
                 "description": "Maximum time in milliseconds to wait for the page to load. (default: `60000`)",
                 "format": "uint32",
-                "minimum": 0.0,
+                "minimum": 0,
                 "type": "integer"
             },
             "waitFor": {
                 "description": "Time in milliseconds to wait for dynamic content to load. (default: `0`)",
                 "format": "uint32",
-                "minimum": 0.0,
+                "minimum": 0,
                 "type": "integer"
             }
         });
@@ -447,6 +1227,65 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn test_cache_options() {
+        let options: ScrapeOptions =
+            serde_json::from_value(json!({ "maxAge": 0, "storeInCache": false })).unwrap();
+        assert_eq!(options, ScrapeOptions::default().bypass_cache());
+        assert_eq!(options.max_age, Some(0));
+        assert_eq!(options.store_in_cache, Some(false));
+        assert_eq!(
+            serde_json::to_value(&options).unwrap(),
+            json!({ "maxAge": 0, "storeInCache": false })
+        );
+
+        // Left out unless set
+        let options: ScrapeOptions = serde_json::from_value(json!({ "timeout": 1000 })).unwrap();
+        assert_eq!((options.max_age, options.store_in_cache), (None, None));
+        assert_eq!(
+            serde_json::to_value(&options).unwrap(),
+            json!({ "timeout": 1000 })
+        );
+    }
+
+    #[test]
+    fn test_presets() {
+        let spa = ScrapeOptions::spa_preset();
+        assert_eq!(spa.formats, Some(vec![ScrapeFormats::Markdown]));
+        assert_eq!(spa.only_main_content, Some(true));
+        assert_eq!(spa.wait_for, Some(3_000));
+        assert_eq!(spa.timeout, Some(60_000));
+        assert_eq!(spa.block_ads, Some(true));
+
+        let fast = ScrapeOptions::fast_text_preset();
+        assert_eq!(fast.formats, Some(vec![ScrapeFormats::Markdown]));
+        assert_eq!(fast.only_main_content, Some(true));
+        assert_eq!(fast.wait_for, Some(0));
+        assert_eq!(fast.timeout, Some(15_000));
+        assert_eq!(fast.block_ads, Some(true));
+        assert_eq!(fast.remove_base64_images, Some(true));
+        assert_eq!(fast.estimated_credits_per_url(), 1);
+
+        let full = ScrapeOptions::full_capture_preset();
+        assert_eq!(
+            full.formats,
+            Some(vec![
+                ScrapeFormats::Markdown,
+                ScrapeFormats::HTML,
+                ScrapeFormats::RawHTML,
+                ScrapeFormats::Links,
+                ScrapeFormats::ScreenshotFullPage,
+            ])
+        );
+        assert_eq!(full.only_main_content, Some(false));
+        assert_eq!(full.wait_for, Some(2_000));
+        assert_eq!(full.block_ads, Some(false));
+        assert_eq!(
+            serde_json::to_value(&full).unwrap()["formats"][4],
+            json!("screenshot@fullPage")
+        );
+    }
+
     #[test]
     fn test_scrape_request_body_deserialization() {
         let json_data = json!({
@@ -495,7 +1334,9 @@ mod tests {
             },
             "removeBase64Images": true,
             "blockAds": true,
-            "proxy": "basic"
+            "proxy": "basic",
+            "maxAge": 3600000,
+            "storeInCache": false
         });
 
         // Deserialize the JSON to our struct
@@ -546,6 +1387,9 @@ mod tests {
                 remove_base64_images: Some(true),
                 block_ads: Some(true),
                 proxy: Some("basic".to_string()),
+                max_age: Some(3_600_000),
+                store_in_cache: Some(false),
+                screenshot_options: None,
             },
         };
 
@@ -623,6 +1467,88 @@ mod tests {
         assert_eq!(schema_actual, schema_expected);
     }
 
+    #[test]
+    fn test_json_options_with_validated_schema() {
+        let schema = json!({
+            "type": "object",
+            "properties": { "title": { "type": "string" } }
+        });
+        let json_options = JsonOptions::with_validated_schema(schema.clone()).unwrap();
+        assert_eq!(json_options.schema, Some(schema));
+        assert_eq!(json_options.prompt, None);
+
+        for (schema, message) in [
+            (json!("object"), "must be an object"),
+            (json!([{ "type": "object" }]), "must be an object"),
+            (json!({ "properties": {} }), "no `type` key"),
+        ] {
+            let error = JsonOptions::with_validated_schema(schema).unwrap_err();
+            assert!(
+                matches!(&error, FirecrawlError::APIError(kind, e)
+                    if kind == "Configuration" && e.error.contains(message)),
+                "{error:?}"
+            );
+        }
+    }
+
+    #[cfg(feature = "mcp-tool")]
+    #[tokio::test]
+    async fn test_json_options_from_schema_round_trip() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{body_partial_json, method, path},
+        };
+
+        #[derive(JsonSchema, Deserialize, Debug, PartialEq)]
+        struct Product {
+            name: String,
+            price: f64,
+            tags: Vec<String>,
+        }
+
+        let json_options = JsonOptions::from_schema::<Product>();
+        let schema = json_options.schema.clone().unwrap();
+        assert_eq!(schema["type"], "object");
+        assert_eq!(schema["properties"]["price"]["type"], "number");
+        assert_eq!(schema["required"], json!(["name", "price", "tags"]));
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/scrape"))
+            .and(body_partial_json(json!({
+                "formats": ["json"],
+                "jsonOptions": { "schema": schema }
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "data": {
+                    "json": { "name": "Widget", "price": 9.5, "tags": ["new"] },
+                    "metadata": { "sourceURL": "https://example.com", "statusCode": 200 }
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let options = ScrapeOptions::default()
+            .with_formats([ScrapeFormats::JSON])
+            .with_json_options(json_options);
+        let document = app
+            .scrape_url("https://example.com", options)
+            .await
+            .unwrap();
+        let product: Product = serde_json::from_value(document.json.unwrap()).unwrap();
+        assert_eq!(
+            product,
+            Product {
+                name: "Widget".to_string(),
+                price: 9.5,
+                tags: vec!["new".to_string()],
+            }
+        );
+    }
+
     #[test]
     fn test_action_deserialization() {
         // Test wait action
@@ -694,4 +1620,782 @@ mod tests {
 
         assert_eq!(type_action, expected_type_action);
     }
+
+    #[test]
+    fn test_action_constructors() {
+        let action = |action_type, fields: serde_json::Value| {
+            let mut action = json!({ "type": action_type });
+            action
+                .as_object_mut()
+                .unwrap()
+                .extend(fields.as_object().unwrap().clone());
+            serde_json::from_value::<Action>(action).unwrap()
+        };
+
+        let cases = [
+            (
+                Action::click("#submit"),
+                action("click", json!({ "selector": "#submit" })),
+            ),
+            (
+                Action::type_text("#search", "rust"),
+                action("type", json!({ "selector": "#search", "text": "rust" })),
+            ),
+            (
+                Action::wait_ms(500),
+                action("wait", json!({ "milliseconds": 500 })),
+            ),
+            (
+                Action::wait_for("#results"),
+                action("wait", json!({ "selector": "#results" })),
+            ),
+            (
+                Action::press("Enter"),
+                action("press", json!({ "key": "Enter" })),
+            ),
+            (
+                Action::scroll("down"),
+                action("scroll", json!({ "direction": "down" })),
+            ),
+            (
+                Action::screenshot(true),
+                action("screenshot", json!({ "fullPage": true })),
+            ),
+            (
+                Action::execute_js("document.title"),
+                action("executeJavascript", json!({ "script": "document.title" })),
+            ),
+            (Action::scrape(), action("scrape", json!({}))),
+        ];
+
+        for (action, expected) in cases {
+            assert_eq!(action, expected);
+        }
+        assert_eq!(
+            Action::wait_ms(500),
+            Action {
+                action_type: ActionType::Wait,
+                selector: None,
+                text: None,
+                milliseconds: Some(500),
+                key: None,
+                direction: None,
+                script: None,
+                full_page: None,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_observer_called_for_scrape() {
+        use std::sync::{Arc, Mutex};
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/scrape"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "data": {
+                    "markdown": "# Example",
+                    "metadata": { "sourceURL": "https://example.com", "statusCode": 200 }
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let recorder = seen.clone();
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>)
+            .unwrap()
+            .with_observer(move |info| {
+                recorder.lock().unwrap().push((
+                    info.method.to_string(),
+                    info.url.path().to_string(),
+                    info.body.map(|b| b.to_vec()),
+                    info.status,
+                    info.is_before_send(),
+                ));
+            });
+
+        app.scrape_url("https://example.com", None).await.unwrap();
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 2);
+
+        let (method, path, body, status, before_send) = &seen[0];
+        assert_eq!((method.as_str(), path.as_str()), ("POST", "/v1/scrape"));
+        let body: serde_json::Value = serde_json::from_slice(body.as_ref().unwrap()).unwrap();
+        assert_eq!(body["url"], "https://example.com");
+        assert_eq!((*status, *before_send), (None, true));
+
+        let (_, _, _, status, before_send) = &seen[1];
+        assert_eq!((*status, *before_send), (Some(200), false));
+    }
+
+    #[test]
+    fn test_markdown_to_text() {
+        let markdown = r#"# Getting *Started* #
+
+Welcome to the **Firecrawl** [docs](https://docs.firecrawl.dev "Docs").
+![logo](https://example.com/logo.png)
+
+> Note: use `scrape_url` for _single_ pages, not_this_one.
+
+
+---
+
+- First item
+* Second ~~removed~~ item
+1. Numbered \*literal\* asterisk
+- [x] Done task
+
+| Name | Value |
+| :--- | ----: |
+| a    | 1     |
+
+```rust
+let x = 2 * 3;
+```
+"#;
+
+        assert_eq!(
+            markdown_to_text(markdown),
+            "Getting Started\n\
+             \n\
+             Welcome to the Firecrawl docs.\n\
+             \n\
+             Note: use scrape_url for single pages, not_this_one.\n\
+             \n\
+             First item\n\
+             Second removed item\n\
+             Numbered *literal* asterisk\n\
+             Done task\n\
+             \n\
+             Name\tValue\n\
+             a\t1\n\
+             \n\
+             let x = 2 * 3;"
+        );
+    }
+
+    #[test]
+    fn test_markdown_to_text_keeps_plain_text() {
+        let text = "Math like 2 * 3 and a_b stays, as do [brackets] and (parens).";
+        assert_eq!(markdown_to_text(text), text);
+    }
+
+    #[tokio::test]
+    async fn test_scrape_url_if_changed() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{body_partial_json, method, path},
+        };
+
+        let server = MockServer::start().await;
+        for (url, cache_state) in [
+            ("https://example.com/fresh", "miss"),
+            ("https://example.com/cached", "hit"),
+        ] {
+            Mock::given(method("POST"))
+                .and(path("/v1/scrape"))
+                .and(body_partial_json(json!({ "url": url })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "success": true,
+                    "data": {
+                        "markdown": "# Example",
+                        "metadata": {
+                            "sourceURL": url,
+                            "statusCode": 200,
+                            "cacheState": cache_state
+                        }
+                    }
+                })))
+                .mount(&server)
+                .await;
+        }
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let fresh = app
+            .scrape_url_if_changed("https://example.com/fresh", None)
+            .await
+            .unwrap();
+        assert_eq!(fresh.unwrap().markdown.as_deref(), Some("# Example"));
+
+        let cached = app
+            .scrape_url_if_changed("https://example.com/cached", None)
+            .await
+            .unwrap();
+        assert_eq!(cached, None);
+        let document = app
+            .scrape_url("https://example.com/cached", None)
+            .await
+            .unwrap();
+        assert!(document.is_cache_hit());
+    }
+
+    #[tokio::test]
+    async fn test_scrape_url_with_idempotency() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{header, method, path},
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/scrape"))
+            .and(header("x-idempotency-key", "scrape-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "data": {
+                    "markdown": "# Example",
+                    "metadata": { "sourceURL": "https://example.com", "statusCode": 200 }
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let document = app
+            .scrape_url_with_idempotency("https://example.com", None, Some("scrape-1".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(document.markdown.as_deref(), Some("# Example"));
+    }
+
+    #[tokio::test]
+    async fn test_scrape_url_raw() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        // Serialized back byte for byte
+        let body = r##"{"success":true,"data":{"markdown":"# Example","metadata":{"sourceURL":"https://example.com","statusCode":200}},"warning":"The onlyMainContent option was ignored"}"##;
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/scrape"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, "application/json"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let response = app
+            .scrape_url_raw("https://example.com", None)
+            .await
+            .unwrap();
+        assert!(response.success);
+        assert_eq!(response.data.markdown.as_deref(), Some("# Example"));
+        assert_eq!(
+            response.warning.as_deref(),
+            Some("The onlyMainContent option was ignored")
+        );
+        assert_eq!(serde_json::to_string(&response).unwrap(), body);
+    }
+
+    #[tokio::test]
+    async fn test_scrape_warns_about_unknown_fields_in_strict_mode() {
+        use crate::observer::WarningRecorder;
+        use std::sync::Arc;
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/scrape"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "data": {
+                    "markdown": "# Example",
+                    "renderMode": "static",
+                    "metadata": { "sourceURL": "https://example.com", "statusCode": 200 }
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let warnings = Arc::new(WarningRecorder::default());
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>)
+            .unwrap()
+            .with_hook(warnings.clone());
+        for _ in 0..2 {
+            let document = app.scrape_url("https://example.com", None).await.unwrap();
+            assert_eq!(document.markdown.as_deref(), Some("# Example"));
+        }
+        // Once per client, not on every response
+        let expected = if cfg!(feature = "strict") {
+            vec!["unknown fields in Document: renderMode, the SDK may need updating"]
+        } else {
+            vec![]
+        };
+        assert_eq!(warnings.take(), expected);
+
+        let other = FirecrawlApp::new_selfhosted(server.uri(), None::<String>)
+            .unwrap()
+            .with_hook(warnings.clone());
+        other.scrape_url("https://example.com", None).await.unwrap();
+        assert_eq!(warnings.take(), expected);
+    }
+
+    #[tokio::test]
+    async fn test_screenshot_formats_rejected_before_sending() {
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(wiremock::matchers::any())
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&server)
+            .await;
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let options = ScrapeOptions::default()
+            .with_formats([ScrapeFormats::Screenshot, ScrapeFormats::ScreenshotFullPage]);
+
+        let error = app
+            .scrape_url("https://example.com", options.clone())
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&error, FirecrawlError::InvalidOptions(message) if message.contains("ScreenshotFullPage")),
+            "{error:?}"
+        );
+
+        #[cfg(feature = "batch")]
+        {
+            let error = app
+                .batch_scrape_urls_async(
+                    vec!["https://example.com".to_string()],
+                    options,
+                    None,
+                    crate::webhook::Webhook::dummy(),
+                    None,
+                )
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(error, FirecrawlError::InvalidOptions(_)),
+                "{error:?}"
+            );
+        }
+
+        // Either alone is fine
+        assert!(
+            ScrapeOptions::default()
+                .with_formats([ScrapeFormats::Markdown, ScrapeFormats::ScreenshotFullPage])
+                .validate()
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_scrape_validation_details() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let details = json!([
+            {
+                "code": "invalid_string",
+                "validation": "url",
+                "path": ["url"],
+                "message": "Invalid URL"
+            },
+            {
+                "code": "too_small",
+                "minimum": 1000,
+                "type": "number",
+                "inclusive": true,
+                "path": ["timeout"],
+                "message": "Number must be greater than or equal to 1000"
+            }
+        ]);
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/scrape"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+                "success": false,
+                "error": "Bad Request",
+                "details": details
+            })))
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let options = ScrapeOptions {
+            timeout: Some(10),
+            ..Default::default()
+        };
+        let error = app.scrape_url("not a url", options).await.unwrap_err();
+
+        assert!(
+            matches!(&error, FirecrawlError::APIError(_, e) if e.status_code == Some(400)),
+            "{error:?}"
+        );
+        assert_eq!(error.validation_details(), Some(&details));
+        assert!(!error.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_scrape_with_max_html_size() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{body_partial_json, method, path},
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/scrape"))
+            .and(body_partial_json(json!({ "formats": ["html", "rawHtml"] })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "data": {
+                    "html": "<p>Example</p>",
+                    "rawHtml": "<html><head><title>Example</title></head><body><p>Example</p></body></html>",
+                    "metadata": { "sourceURL": "https://example.com", "statusCode": 200 }
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>)
+            .unwrap()
+            .with_max_html_size(32);
+        let options =
+            ScrapeOptions::default().with_formats([ScrapeFormats::HTML, ScrapeFormats::RawHTML]);
+        let document = app
+            .scrape_url("https://example.com", options)
+            .await
+            .unwrap();
+
+        let variants = document.html_variants();
+        let cleaned = variants.cleaned.unwrap();
+        assert_eq!(cleaned.html, "<p>Example</p>");
+        assert!(!cleaned.truncated);
+        let raw = variants.raw.unwrap();
+        assert_eq!(raw.html, "<html><head><title>Example</titl");
+        assert!(raw.truncated);
+    }
+
+    #[tokio::test]
+    async fn test_scrape_urls_concurrently() {
+        use std::time::{Duration, Instant};
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{body_partial_json, method, path},
+        };
+
+        let page = |url: &str| {
+            ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "data": {
+                    "markdown": format!("# {url}"),
+                    "metadata": { "sourceURL": url, "statusCode": 200 }
+                }
+            }))
+        };
+        let server = MockServer::start().await;
+        let urls: Vec<String> = (0..6).map(|i| format!("https://example.com/{i}")).collect();
+        for (i, url) in urls.iter().enumerate() {
+            let response = match i {
+                3 => ResponseTemplate::new(500),
+                // The first page is the slowest, so it finishes last
+                0 => page(url).set_delay(Duration::from_millis(400)),
+                _ => page(url).set_delay(Duration::from_millis(200)),
+            };
+            Mock::given(method("POST"))
+                .and(path("/v1/scrape"))
+                .and(body_partial_json(json!({ "url": url })))
+                .respond_with(response)
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let start = Instant::now();
+        let results = app
+            .scrape_urls_concurrently(urls.clone(), None, 2)
+            .await
+            .unwrap();
+        let elapsed = start.elapsed();
+
+        // 0 takes one slot for 400ms while 1 and 2 take the other, then 3 fails at once and 4
+        // and 5 take both slots for another 200ms. Without the cap this takes 400ms, one at a
+        // time 1200ms.
+        assert!(elapsed >= Duration::from_millis(600), "{elapsed:?}");
+        assert!(elapsed < Duration::from_millis(1000), "{elapsed:?}");
+        assert_eq!(results.len(), 6);
+        for (i, (result, url)) in results.iter().zip(&urls).enumerate() {
+            match result {
+                Ok(document) => assert_eq!(&document.metadata.source_url, url),
+                Err(error) => {
+                    assert_eq!(i, 3, "{error:?}");
+                    assert!(matches!(
+                        error,
+                        FirecrawlError::HttpRequestFailed(_, 500, _)
+                    ));
+                }
+            }
+        }
+        assert!(results[3].is_err());
+
+        let error = app
+            .scrape_urls_concurrently(urls, None, 0)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("max_concurrency"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn test_scrape_timeout_extends_client_timeout() {
+        use std::time::Duration;
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/scrape"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({
+                        "success": true,
+                        "data": {
+                            "markdown": "# Example",
+                            "metadata": { "sourceURL": "https://example.com", "statusCode": 200 }
+                        }
+                    }))
+                    .set_delay(Duration::from_millis(500)),
+            )
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::builder()
+            .api_url(server.uri())
+            .timeout(Duration::from_millis(200))
+            .build()
+            .unwrap();
+
+        let result = app.scrape_url("https://example.com", None).await;
+        assert!(matches!(result, Err(FirecrawlError::HttpError(_, e)) if e.is_timeout()));
+
+        let options = ScrapeOptions {
+            timeout: Some(1000),
+            ..Default::default()
+        };
+        let document = app
+            .scrape_url("https://example.com", options)
+            .await
+            .unwrap();
+        assert_eq!(document.markdown.as_deref(), Some("# Example"));
+    }
+
+    #[test]
+    fn test_screenshot_options_serialization() {
+        let screenshot = ScreenshotOptions {
+            quality: Some(80),
+            viewport: Some(Viewport {
+                width: 1280,
+                height: 800,
+            }),
+            ..Default::default()
+        };
+
+        let options = ScrapeOptions::default()
+            .with_formats([ScrapeFormats::Markdown, ScrapeFormats::ScreenshotFullPage])
+            .with_screenshot_options(screenshot.clone());
+        assert_eq!(
+            serde_json::to_value(&options).unwrap(),
+            json!({
+                "formats": [
+                    "markdown",
+                    {
+                        "type": "screenshot",
+                        "fullPage": true,
+                        "quality": 80,
+                        "viewport": { "width": 1280, "height": 800 }
+                    }
+                ]
+            })
+        );
+
+        // A screenshot is added, keeping the default markdown format
+        let options = ScrapeOptions::default().with_screenshot_options(ScreenshotOptions {
+            full_page: Some(false),
+            ..screenshot
+        });
+        assert_eq!(
+            serde_json::to_value(&options).unwrap()["formats"],
+            json!([
+                "markdown",
+                {
+                    "type": "screenshot",
+                    "fullPage": false,
+                    "quality": 80,
+                    "viewport": { "width": 1280, "height": 800 }
+                }
+            ])
+        );
+
+        // Simple formats are sent as is without screenshot options
+        let options = ScrapeOptions::default().with_formats([ScrapeFormats::ScreenshotFullPage]);
+        assert_eq!(
+            serde_json::to_value(&options).unwrap(),
+            json!({ "formats": ["screenshot@fullPage"] })
+        );
+    }
+
+    #[test]
+    fn test_merge_scrape_options() {
+        let defaults = ScrapeOptions::default()
+            .with_formats([ScrapeFormats::Markdown])
+            .with_only_main_content(true)
+            .with_timeout(30_000)
+            .with_headers([("User-Agent".to_string(), "acme".to_string())]);
+        let options = ScrapeOptions::default()
+            .with_formats([ScrapeFormats::HTML])
+            .with_timeout(10_000)
+            .with_mobile(true);
+
+        assert_eq!(
+            options.merge(&defaults),
+            ScrapeOptions::default()
+                .with_formats([ScrapeFormats::HTML])
+                .with_only_main_content(true)
+                .with_timeout(10_000)
+                .with_headers([("User-Agent".to_string(), "acme".to_string())])
+                .with_mobile(true)
+        );
+        assert_eq!(ScrapeOptions::default().merge(&defaults), defaults);
+    }
+
+    #[test]
+    fn test_with_conditional_headers() {
+        let validators = CacheValidators {
+            content_type: Some("text/html".to_string()),
+            last_modified: Some("Wed, 21 Oct 2015 07:28:00 GMT".to_string()),
+            etag: Some("\"33a64df5\"".to_string()),
+        };
+        let options = ScrapeOptions {
+            headers: Some(HashMap::from([(
+                "User-Agent".to_string(),
+                "my-crawler".to_string(),
+            )])),
+            ..Default::default()
+        }
+        .with_conditional_headers(&validators);
+
+        assert_eq!(
+            options.headers,
+            Some(HashMap::from([
+                ("User-Agent".to_string(), "my-crawler".to_string()),
+                ("If-None-Match".to_string(), "\"33a64df5\"".to_string()),
+                (
+                    "If-Modified-Since".to_string(),
+                    "Wed, 21 Oct 2015 07:28:00 GMT".to_string()
+                ),
+            ]))
+        );
+
+        let options =
+            ScrapeOptions::default().with_conditional_headers(&CacheValidators::default());
+        assert_eq!(options.headers, None);
+    }
+
+    #[test]
+    fn test_estimated_credits_per_url() {
+        let cases = [
+            (ScrapeOptions::default(), 1),
+            (
+                ScrapeOptions {
+                    formats: Some(vec![ScrapeFormats::Markdown, ScrapeFormats::Links]),
+                    proxy: Some("basic".to_string()),
+                    ..Default::default()
+                },
+                1,
+            ),
+            (
+                ScrapeOptions {
+                    formats: Some(vec![ScrapeFormats::JSON]),
+                    ..Default::default()
+                },
+                5,
+            ),
+            (
+                ScrapeOptions {
+                    formats: Some(vec![ScrapeFormats::JSON]),
+                    proxy: Some("stealth".to_string()),
+                    ..Default::default()
+                },
+                9,
+            ),
+        ];
+
+        for (options, expected) in cases {
+            assert_eq!(options.estimated_credits_per_url(), expected, "{options:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scrape_url_with_auto_idempotency() {
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{method, path},
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/scrape"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "data": {
+                    "metadata": { "sourceURL": "https://example.com", "statusCode": 200 }
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>)
+            .unwrap()
+            .with_auto_idempotency(true);
+        app.scrape_url("https://example.com", None).await.unwrap();
+        app.scrape_url("https://example.com", None).await.unwrap();
+        app.scrape_url_with_idempotency("https://example.com", None, Some("scrape-1".to_string()))
+            .await
+            .unwrap();
+
+        let keys: Vec<String> = server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .map(|request| {
+                request.headers["x-idempotency-key"]
+                    .to_str()
+                    .unwrap()
+                    .to_string()
+            })
+            .collect();
+        assert_eq!(keys.len(), 3);
+        // A new key per call, and the given one wins
+        assert!(uuid::Uuid::parse_str(&keys[0]).is_ok(), "{keys:?}");
+        assert!(uuid::Uuid::parse_str(&keys[1]).is_ok(), "{keys:?}");
+        assert_ne!(keys[0], keys[1]);
+        assert_eq!(keys[2], "scrape-1");
+
+        // Off by default
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        app.scrape_url("https://example.com", None).await.unwrap();
+        let requests = server.received_requests().await.unwrap();
+        assert!(!requests[3].headers.contains_key("x-idempotency-key"));
+    }
 }
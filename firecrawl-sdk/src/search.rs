@@ -1,12 +1,17 @@
+use futures::{StreamExt, stream};
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "mcp-tool")]
 use schemars::JsonSchema;
 
 use crate::{
-    API_VERSION, FirecrawlApp, FirecrawlError, error::FirecrawlAPIError, scrape::ScrapeOptions,
+    FirecrawlApp, FirecrawlError, error::FirecrawlAPIError, routes, scrape::ScrapeOptions,
 };
 
+/// Number of queries of `FirecrawlApp::search_many` searched at the same time, to stay within
+/// the rate limits of the API.
+const SEARCH_MANY_CONCURRENCY: usize = 4;
+
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "mcp-tool", derive(JsonSchema))]
 pub struct SearchResult {
@@ -18,6 +23,183 @@ pub struct SearchResult {
     pub description: String,
 }
 
+impl SearchResult {
+    /// The description with every match of the terms of `query`, see [`extract_terms`], wrapped
+    /// in `before` and `after`, e.g. `<mark>` and `</mark>`.
+    ///
+    /// Matches are case-insensitive and cover whole words only: `rust` doesn't match in
+    /// `Rusty`. Overlapping matches are merged into the longest one. Text is compared character
+    /// by character after case folding, without Unicode normalization, so a composed `é` doesn't
+    /// match an `e` followed by a combining accent.
+    pub fn highlight(&self, query: &str, before: &str, after: &str) -> String {
+        highlight(&self.description, &extract_terms(query), before, after)
+    }
+}
+
+/// Words left out of [`extract_terms`], unless the query has nothing else.
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "how", "in", "is", "it", "of",
+    "on", "or", "that", "the", "this", "to", "was", "what", "when", "where", "who", "why", "with",
+];
+
+/// The terms of a search query, in query order, e.g. to highlight them in the results.
+///
+/// Text in double quotes is one term, an unterminated quote running to the end of the query.
+/// Other words are trimmed of punctuation, and English stop words are dropped unless the query
+/// has nothing else, so `the who` still has terms. Terms differing only by case are returned
+/// once, as first written.
+pub fn extract_terms(query: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    let mut has_content = false;
+    // Odd parts are between quotes
+    for (i, part) in query.split('"').enumerate() {
+        if i % 2 == 1 {
+            let phrase = part.split_whitespace().collect::<Vec<_>>().join(" ");
+            if !phrase.is_empty() {
+                terms.push((phrase, false));
+                has_content = true;
+            }
+            continue;
+        }
+
+        for word in part.split_whitespace() {
+            let word = word.trim_matches(|c: char| !is_word_char(c));
+            if word.is_empty() {
+                continue;
+            }
+            let stop_word = STOP_WORDS.contains(&fold(word).as_str());
+            has_content |= !stop_word;
+            terms.push((word.to_string(), stop_word));
+        }
+    }
+
+    let mut seen = Vec::new();
+    terms
+        .into_iter()
+        .filter(|(_, stop_word)| !(has_content && *stop_word))
+        .filter_map(|(term, _)| {
+            let key = fold(&term);
+            (!seen.contains(&key)).then(|| {
+                seen.push(key);
+                term
+            })
+        })
+        .collect()
+}
+
+/// `text` with every whole-word match of `terms` wrapped in `before` and `after`.
+fn highlight(text: &str, terms: &[String], before: &str, after: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    // Case-folded text, with the index in `chars` each folded character comes from
+    let mut folded = Vec::with_capacity(chars.len());
+    let mut origin = Vec::with_capacity(chars.len());
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_whitespace() {
+            // Runs of whitespace match the single spaces of phrases
+            if folded.last() != Some(&' ') {
+                folded.push(' ');
+                origin.push(i);
+            }
+            continue;
+        }
+        fold_char(c, &mut folded);
+        origin.resize(folded.len(), i);
+    }
+
+    let mut matches = Vec::new();
+    for term in terms {
+        let mut needle = Vec::new();
+        for c in term.chars() {
+            fold_char(c, &mut needle);
+        }
+        if needle.is_empty() {
+            continue;
+        }
+
+        for (start, window) in folded.windows(needle.len()).enumerate() {
+            let end = start + needle.len();
+            // Matches must not split the folding of a character, e.g. the `ss` of `ß`
+            if window != needle.as_slice()
+                || (start > 0 && origin[start - 1] == origin[start])
+                || (end < folded.len() && origin[end] == origin[end - 1])
+            {
+                continue;
+            }
+
+            let (first, last) = (origin[start], origin[end - 1]);
+            let starts_word =
+                !is_word_char(chars[first]) || first == 0 || !is_word_char(chars[first - 1]);
+            let ends_word = !is_word_char(chars[last])
+                || last + 1 == chars.len()
+                || !is_word_char(chars[last + 1]);
+            if starts_word && ends_word {
+                matches.push((first, last + 1));
+            }
+        }
+    }
+
+    // Longest match first among the ones starting at the same character
+    matches.sort_by_key(|&(start, end)| (start, std::cmp::Reverse(end)));
+    let mut highlighted = String::with_capacity(text.len());
+    let mut matches = matches.into_iter().peekable();
+    let mut open_until = None;
+    for (i, &c) in chars.iter().enumerate() {
+        if open_until == Some(i) {
+            highlighted.push_str(after);
+            open_until = None;
+        }
+        while let Some(&(start, end)) = matches.peek()
+            && start <= i
+        {
+            matches.next();
+            if start == i && open_until.is_none() {
+                highlighted.push_str(before);
+                open_until = Some(end);
+            }
+        }
+        highlighted.push(c);
+    }
+    if open_until.is_some() {
+        highlighted.push_str(after);
+    }
+    highlighted
+}
+
+/// `term` case-folded.
+fn fold(term: &str) -> String {
+    let mut folded = Vec::new();
+    for c in term.chars() {
+        fold_char(c, &mut folded);
+    }
+    folded.into_iter().collect()
+}
+
+/// Pushes the case folding of `c` to `folded`.
+///
+/// Every Turkish `i` folds to a plain `i`, so queries match Turkish text whichever casing rules
+/// produced it, e.g. `ISTANBUL` and `İstanbul`.
+fn fold_char(c: char, folded: &mut Vec<char>) {
+    match c {
+        'I' | 'İ' | 'ı' => folded.push('i'),
+        'ß' | 'ẞ' => folded.extend(['s', 's']),
+        'ς' => folded.push('σ'),
+        c => folded.extend(c.to_lowercase()),
+    }
+}
+
+/// Whether `c` is part of a word, counting the combining marks following a letter.
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric()
+        || matches!(
+            c,
+            '\u{0300}'..='\u{036F}'
+                | '\u{1AB0}'..='\u{1AFF}'
+                | '\u{1DC0}'..='\u{1DFF}'
+                | '\u{20D0}'..='\u{20FF}'
+                | '\u{FE20}'..='\u{FE2F}'
+        )
+}
+
 #[serde_with::skip_serializing_none]
 #[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "mcp-tool", derive(JsonSchema))]
@@ -49,6 +231,30 @@ pub struct SearchOptions {
     pub max_results: Option<usize>,
 }
 
+option_setters!(SearchOptions,
+    /// Setters to build options without struct update syntax.
+    ///
+    /// ```
+    /// use firecrawl_sdk::search::SearchOptions;
+    ///
+    /// let options = SearchOptions::default()
+    ///     .with_limit(5)
+    ///     .with_lang("fr")
+    ///     .with_country("fr");
+    /// assert_eq!(options.limit, Some(5));
+    /// ```
+    {
+        with_limit => limit: u32,
+        with_lang => lang: impl Into<String>,
+        with_country => country: impl Into<String>,
+        with_tbs => tbs: impl Into<String>,
+        with_filter => filter: impl Into<String>,
+        with_location => location: LocationOptions,
+        with_scrape_options => scrape_options: ScrapeOptions,
+        with_max_results => max_results: usize,
+    }
+);
+
 #[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "mcp-tool", derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
@@ -68,13 +274,23 @@ pub struct SearchRequestBody {
     pub options: SearchOptions,
 }
 
-#[derive(Deserialize, Serialize, Debug, Default)]
+/// Response of the search endpoint as sent by the API, returned by
+/// [`FirecrawlApp::search_raw`].
+#[serde_with::skip_serializing_none]
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
-struct SearchResponse {
-    success: bool,
-    data: Option<Vec<SearchResult>>,
+pub struct SearchResponse {
+    /// Whether the search succeeded
+    pub success: bool,
+
+    /// The search results
+    pub data: Option<Vec<SearchResult>>,
+
     /// Error message when success is false
-    error: Option<String>,
+    pub error: Option<String>,
+
+    /// Warning about the request, e.g. an option the API ignored.
+    pub warning: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Default, PartialEq)]
@@ -102,26 +318,19 @@ impl FirecrawlApp {
         query: impl AsRef<str>,
         options: impl Into<Option<SearchOptions>>,
     ) -> Result<Vec<SearchResult>, FirecrawlError> {
-        let body = SearchRequestBody {
-            query: query.as_ref().to_string(),
-            options: options.into().unwrap_or_default(),
-        };
-
-        let headers = self.prepare_headers(None);
-
-        let response = self
-            .client
-            .post(format!("{}/{}/search", self.api_url, API_VERSION))
-            .headers(headers)
-            .json(&body)
-            .send()
-            .await
-            .map_err(|e| {
-                FirecrawlError::HttpError(format!("Searching for {:?}", query.as_ref()), e)
-            })?;
+        self.search_with_idempotency(query, options, None).await
+    }
 
+    /// Searches like [`search`](Self::search), sending `idempotency_key` so a retried request
+    /// isn't charged twice.
+    pub async fn search_with_idempotency(
+        &self,
+        query: impl AsRef<str>,
+        options: impl Into<Option<SearchOptions>>,
+        idempotency_key: Option<String>,
+    ) -> Result<Vec<SearchResult>, FirecrawlError> {
         let response = self
-            .handle_response::<SearchResponse>(response, "search")
+            .send_search(query, options.into(), idempotency_key)
             .await?;
 
         if !response.success {
@@ -130,18 +339,179 @@ impl FirecrawlApp {
                 FirecrawlAPIError {
                     error: response.error.unwrap_or_default(),
                     details: None,
+                    status_code: None,
+                    rate_limit: None,
+                    request_id: None,
                 },
             ));
         }
 
         Ok(response.data.unwrap_or_default())
     }
+
+    /// Searches like [`search`](Self::search), returning the response of the API as is, e.g. to
+    /// forward it unchanged. A response with `success: false` is returned rather than failing.
+    pub async fn search_raw(
+        &self,
+        query: impl AsRef<str>,
+        options: impl Into<Option<SearchOptions>>,
+    ) -> Result<SearchResponse, FirecrawlError> {
+        self.send_search(query, options.into(), None).await
+    }
+
+    async fn send_search(
+        &self,
+        query: impl AsRef<str>,
+        options: Option<SearchOptions>,
+        idempotency_key: Option<String>,
+    ) -> Result<SearchResponse, FirecrawlError> {
+        let body = SearchRequestBody {
+            query: query.as_ref().to_string(),
+            options: options.unwrap_or_default(),
+        };
+
+        let idempotency_key = self.idempotency_key(idempotency_key);
+        let headers = self.prepare_headers(idempotency_key.as_ref())?;
+
+        let request = self
+            .inner
+            .client
+            .post(self.endpoint(routes::search()))
+            .headers(headers)
+            .json(&body);
+        let response = self.send_request(request).await.map_err(|e| {
+            FirecrawlError::HttpError(format!("Searching for {:?}", query.as_ref()), e)
+        })?;
+
+        self.handle_response::<SearchResponse>(response, "search")
+            .await
+    }
+
+    /// Performs several web searches concurrently using the Firecrawl API, a few at a time.
+    ///
+    /// Each query gets its own `Result`, in the same order as `queries`, so a single failing
+    /// query does not fail the whole call.
+    pub async fn search_many<I, Q>(
+        &self,
+        queries: I,
+        options: impl Into<Option<SearchOptions>>,
+    ) -> Vec<Result<Vec<SearchResult>, FirecrawlError>>
+    where
+        I: IntoIterator<Item = Q>,
+        Q: AsRef<str>,
+    {
+        let options = options.into();
+
+        stream::iter(queries)
+            .map(|query| self.search(query, options.clone()))
+            .buffered(SEARCH_MANY_CONCURRENCY)
+            .collect()
+            .await
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn search_result(description: &str) -> SearchResult {
+        SearchResult {
+            url: "https://example.com".to_string(),
+            title: "Example".to_string(),
+            description: description.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_extract_terms() {
+        let cases: [(&str, &[&str]); 6] = [
+            (
+                r#"the best "New York" pizza in town"#,
+                &["best", "New York", "pizza", "town"],
+            ),
+            ("Rust rust RUST tokio", &["Rust", "tokio"]),
+            (r#"rust "async   runtime"#, &["rust", "async runtime"]),
+            ("rust, (tokio)? -- ", &["rust", "tokio"]),
+            // Nothing but stop words
+            ("The Who", &["The", "Who"]),
+            (r#""" ""#, &[]),
+        ];
+
+        for (query, expected) in cases {
+            assert_eq!(extract_terms(query), expected, "{query}");
+        }
+    }
+
+    #[test]
+    fn test_highlight_whole_words() {
+        let result = search_result("Rust is fast. Rusty things rust, trust RUST.");
+        assert_eq!(
+            result.highlight("rust", "[", "]"),
+            "[Rust] is fast. Rusty things [rust], trust [RUST]."
+        );
+        assert_eq!(result.highlight("the", "[", "]"), result.description);
+        assert_eq!(result.highlight("", "[", "]"), result.description);
+    }
+
+    #[test]
+    fn test_highlight_phrases() {
+        let result = search_result("Pizza in New  York, new yorkers and York");
+        assert_eq!(
+            result.highlight(r#""new york" pizza"#, "<mark>", "</mark>"),
+            "<mark>Pizza</mark> in <mark>New  York</mark>, new yorkers and York"
+        );
+        // The phrase wins over the words it contains
+        assert_eq!(
+            result.highlight(r#"new york "new york""#, "[", "]"),
+            "Pizza in [New  York], [new] yorkers and [York]"
+        );
+    }
+
+    #[test]
+    fn test_highlight_turkish_i() {
+        let result = search_result("İSTANBUL'da ılık bir gün, Istanbul ve istanbul");
+        assert_eq!(
+            result.highlight("istanbul ILIK", "[", "]"),
+            "[İSTANBUL]'da [ılık] bir gün, [Istanbul] ve [istanbul]"
+        );
+        assert_eq!(
+            result.highlight("İstanbul", "[", "]"),
+            "[İSTANBUL]'da ılık bir gün, [Istanbul] ve [istanbul]"
+        );
+    }
+
+    #[test]
+    fn test_highlight_accents() {
+        let result = search_result("Café CAFÉ cafe");
+        assert_eq!(result.highlight("café", "[", "]"), "[Café] [CAFÉ] cafe");
+
+        // A combining accent belongs to the word, and isn't normalized
+        let decomposed = search_result("cafe\u{301} au lait");
+        assert_eq!(
+            decomposed.highlight("cafe", "[", "]"),
+            decomposed.description
+        );
+        assert_eq!(
+            decomposed.highlight("CAFE\u{301}", "[", "]"),
+            "[cafe\u{301}] au lait"
+        );
+    }
+
+    #[test]
+    fn test_highlight_multi_char_folding() {
+        let result = search_result("Straße STRASSE strass λόγος ΛΌΓΟΣ");
+        assert_eq!(
+            result.highlight("strasse", "[", "]"),
+            "[Straße] [STRASSE] strass λόγος ΛΌΓΟΣ"
+        );
+        // Never inside the folding of a single character
+        assert_eq!(result.highlight("stras", "[", "]"), result.description);
+        assert_eq!(
+            result.highlight("λόγος", "[", "]"),
+            "Straße STRASSE strass [λόγος] [ΛΌΓΟΣ]"
+        );
+    }
+
     #[test]
     fn test_search_request_deserialization() {
         // JSON to deserialize
@@ -182,4 +552,138 @@ mod tests {
         // Compare the deserialized struct with the expected struct directly
         assert_eq!(deserialized, expected);
     }
+
+    #[tokio::test]
+    async fn test_search_many_tolerates_failing_query() {
+        use serde_json::json;
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{body_partial_json, method, path},
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/search"))
+            .and(body_partial_json(json!({ "query": "broken" })))
+            .respond_with(ResponseTemplate::new(500).set_body_json(json!({
+                "success": false,
+                "error": "Internal server error"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/search"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "data": [{
+                    "url": "https://example.com",
+                    "title": "Example",
+                    "description": "An example page"
+                }]
+            })))
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let results = app.search_many(["first", "broken", "last"], None).await;
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap()[0].url, "https://example.com");
+        assert!(matches!(
+            &results[1],
+            Err(FirecrawlError::APIError(_, e)) if e.status_code == Some(500)
+        ));
+        assert_eq!(results[2].as_ref().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_search_many_bounds_concurrency() {
+        use crate::RequestHook;
+        use reqwest::{Method, Url};
+        use std::sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        };
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        #[derive(Default)]
+        struct InFlight {
+            current: AtomicUsize,
+            max: AtomicUsize,
+        }
+
+        impl RequestHook for InFlight {
+            fn on_request(&self, _: &Method, _: &Url, _: Option<&serde_json::Value>) {
+                let current = self.current.fetch_add(1, Ordering::SeqCst) + 1;
+                self.max.fetch_max(current, Ordering::SeqCst);
+            }
+
+            fn on_response(&self, _: Option<u16>, _: std::time::Duration) {
+                self.current.fetch_sub(1, Ordering::SeqCst);
+            }
+        }
+
+        let server = MockServer::start().await;
+        Mock::given(wiremock::matchers::method("POST"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "success": true, "data": [] }))
+                    .set_delay(std::time::Duration::from_millis(50)),
+            )
+            .mount(&server)
+            .await;
+
+        let in_flight = Arc::new(InFlight::default());
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>)
+            .unwrap()
+            .with_hook(in_flight.clone());
+        let queries: Vec<String> = (0..10).map(|i| format!("query {i}")).collect();
+        let results = app.search_many(&queries, None).await;
+
+        assert_eq!(results.len(), 10);
+        assert!(results.iter().all(Result::is_ok));
+        assert_eq!(
+            in_flight.max.load(Ordering::SeqCst),
+            SEARCH_MANY_CONCURRENCY
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_with_idempotency() {
+        use serde_json::json;
+        use wiremock::{
+            Mock, MockServer, ResponseTemplate,
+            matchers::{header, method, path},
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/search"))
+            .and(header("x-idempotency-key", "search-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "data": []
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), None::<String>).unwrap();
+        let results = app
+            .search_with_idempotency("rust", None, Some("search-1".to_string()))
+            .await
+            .unwrap();
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_search_response_round_trip() {
+        for body in [
+            r#"{"success":true,"data":[{"url":"https://firecrawl.dev","title":"Firecrawl","description":"Web data for AI"}],"warning":"Fewer results than the limit"}"#,
+            r#"{"success":false,"error":"Query is required"}"#,
+        ] {
+            let response: SearchResponse = serde_json::from_str(body).unwrap();
+            assert_eq!(serde_json::to_string(&response).unwrap(), body);
+        }
+    }
 }
@@ -0,0 +1,193 @@
+//! Warnings about fields of API responses the SDK doesn't know, with the `strict` feature.
+//!
+//! serde ignores unknown fields, so a field added by the API goes unnoticed even when it changes
+//! the meaning of the others. With `strict`, the keys of a response are compared to the fields
+//! of the struct it is deserialized into, and the unknown ones are reported as a warning, an
+//! early signal that the SDK needs updating. Only the top-level keys of `Document`, `CrawlStatus`
+//! and `BatchScrapeStatus` are checked: nested fields like `DocumentMetadata` keep their unknown
+//! fields already.
+//!
+//! Warnings go to the hooks of the client, see
+//! [`RequestHook::on_warning`](crate::RequestHook::on_warning). A client reports each set of
+//! unknown fields of a type once, not on every response.
+
+use std::sync::PoisonError;
+
+use serde::{
+    Deserializer,
+    de::{DeserializeOwned, Error, Visitor, value},
+};
+use serde_json::Value;
+
+use crate::FirecrawlApp;
+
+/// Keys of the envelope of every response, checked by `FirecrawlApp::handle_response`.
+const ENVELOPE_FIELDS: [&str; 1] = ["success"];
+
+impl FirecrawlApp {
+    /// Passes the `warnings` this client hasn't reported yet to the hooks.
+    pub(crate) fn warn_unknown_fields(&self, warnings: impl IntoIterator<Item = String>) {
+        for warning in warnings {
+            let reported = self
+                .inner
+                .reported_fields
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .insert(warning.clone());
+            if reported {
+                self.warn(&warning);
+            }
+        }
+    }
+}
+
+/// Warning about the keys of `value` that `T` doesn't know, if there are any.
+pub(crate) fn unknown_fields_warning<T: DeserializeOwned>(value: &Value) -> Option<String> {
+    let unknown = unknown_fields::<T>(value);
+    (!unknown.is_empty()).then(|| {
+        format!(
+            "unknown fields in {}: {}, the SDK may need updating",
+            short_type_name::<T>(),
+            unknown.join(", ")
+        )
+    })
+}
+
+/// Warnings about the keys of a crawl or batch scrape status `page` that `T` doesn't know, then
+/// about those of its documents.
+#[cfg(any(feature = "batch", feature = "crawl"))]
+pub(crate) fn status_page_warnings<T: DeserializeOwned>(page: &Value) -> Vec<String> {
+    let documents = page
+        .get("data")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    unknown_fields_warning::<T>(page)
+        .into_iter()
+        .chain(
+            documents
+                .iter()
+                .filter_map(unknown_fields_warning::<crate::document::Document>),
+        )
+        .collect()
+}
+
+/// Keys of `value` that aren't fields of `T`, nor aliases of them. Empty if `value` isn't an
+/// object or `T` isn't a struct with known fields, e.g. one with a `#[serde(flatten)]` field.
+fn unknown_fields<T: DeserializeOwned>(value: &Value) -> Vec<&str> {
+    let (Value::Object(object), Some(fields)) = (value, known_fields::<T>()) else {
+        return Vec::new();
+    };
+    object
+        .keys()
+        .map(String::as_str)
+        .filter(|key| !fields.contains(key) && !ENVELOPE_FIELDS.contains(key))
+        .collect()
+}
+
+/// Fields of `T` and their aliases, as listed by its derived `Deserialize` impl.
+fn known_fields<T: DeserializeOwned>() -> Option<&'static [&'static str]> {
+    let mut fields = None;
+    let _ = T::deserialize(FieldNames(&mut fields));
+    fields
+}
+
+fn short_type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// Deserializer recording the fields a struct asks for, failing everything else.
+struct FieldNames<'a>(&'a mut Option<&'static [&'static str]>);
+
+impl<'de> Deserializer<'de> for FieldNames<'_> {
+    type Error = value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(Error::custom("not a struct"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        *self.0 = Some(fields);
+        Err(Error::custom("fields recorded"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct seq tuple tuple_struct map enum identifier
+        ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::Document;
+    use serde_json::json;
+
+    #[test]
+    fn test_known_fields() {
+        let fields = known_fields::<Document>().unwrap();
+        assert!(fields.contains(&"rawHtml"));
+        // Aliases are known too, fields skipped by serde aren't
+        assert!(fields.contains(&"llm_extraction"));
+        assert!(!fields.contains(&"requestId"));
+
+        // Keeps its unknown fields
+        assert_eq!(known_fields::<crate::document::DocumentMetadata>(), None);
+        assert_eq!(known_fields::<Value>(), None);
+    }
+
+    #[cfg(feature = "crawl")]
+    #[test]
+    fn test_status_page_warnings() {
+        let page = json!({
+            "success": true,
+            "status": "completed",
+            "total": 1,
+            "completed": 1,
+            "creditsUsed": 1,
+            "expiresAt": "2025-01-02T03:04:05.000Z",
+            "crawlMode": "fast",
+            "data": [{
+                "markdown": "# Example",
+                "summary": "An example",
+                "metadata": { "sourceURL": "https://example.com", "statusCode": 200, "ogType": "website" }
+            }]
+        });
+
+        assert_eq!(
+            status_page_warnings::<crate::crawl::CrawlStatus>(&page),
+            vec![
+                "unknown fields in CrawlStatus: crawlMode, the SDK may need updating",
+                "unknown fields in Document: summary, the SDK may need updating",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown_fields_warning() {
+        let document = json!({
+            "markdown": "# Example",
+            "changeTracking": { "changeStatus": "new" },
+            "metadata": { "sourceURL": "https://example.com", "statusCode": 200 }
+        });
+        assert_eq!(
+            unknown_fields_warning::<Document>(&document).as_deref(),
+            Some("unknown fields in Document: changeTracking, the SDK may need updating")
+        );
+
+        // Known fields only
+        let document = json!({
+            "markdown": "# Example",
+            "metadata": { "sourceURL": "https://example.com", "statusCode": 200 }
+        });
+        assert_eq!(unknown_fields_warning::<Document>(&document), None);
+        assert_eq!(unknown_fields_warning::<Document>(&json!(null)), None);
+    }
+}
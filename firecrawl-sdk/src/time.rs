@@ -0,0 +1,38 @@
+//! Clocks and timers of the runtime: tokio's natively, and those of the host, e.g. a browser or
+//! a Cloudflare Worker, on `wasm32`, where the standard clocks panic.
+
+use std::time::SystemTime;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+pub(crate) use web_time::Instant;
+
+/// The current time.
+pub(crate) fn now() -> SystemTime {
+    #[cfg(not(target_arch = "wasm32"))]
+    return SystemTime::now();
+    #[cfg(target_arch = "wasm32")]
+    return web_time::web::SystemTimeExt::to_std(web_time::SystemTime::now());
+}
+
+/// Waits for `duration`.
+pub(crate) async fn delay(duration: std::time::Duration) {
+    #[cfg(not(target_arch = "wasm32"))]
+    tokio::time::sleep(duration).await;
+    #[cfg(target_arch = "wasm32")]
+    gloo_timers::future::sleep(duration).await;
+}
+
+/// Runs `future` until `deadline`, returning `None` if it didn't complete by then.
+#[cfg(all(feature = "map", feature = "scrape"))]
+pub(crate) async fn run_until<F: Future>(deadline: Instant, future: F) -> Option<F::Output> {
+    use futures::future::{Either, select};
+    use std::pin::pin;
+
+    let timer = delay(deadline.saturating_duration_since(Instant::now()));
+    match select(pin!(future), pin!(timer)).await {
+        Either::Left((output, _)) => Some(output),
+        Either::Right(_) => None,
+    }
+}
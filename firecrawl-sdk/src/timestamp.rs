@@ -0,0 +1,42 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+
+/// Parses an ISO-8601 timestamp, tolerating a missing offset (assumed UTC). `None` if `value` is
+/// empty or not a timestamp.
+pub(crate) fn parse(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        return Some(timestamp.with_timezone(&Utc));
+    }
+
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f")
+        .ok()
+        .map(|naive| naive.and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_parse_iso_8601_variants() {
+        let expected = Utc.with_ymd_and_hms(2025, 1, 2, 3, 4, 5).unwrap();
+
+        assert_eq!(parse("2025-01-02T03:04:05Z"), Some(expected));
+        assert_eq!(parse("2025-01-02T03:04:05.000Z"), Some(expected));
+        assert_eq!(parse("2025-01-02T05:04:05+02:00"), Some(expected));
+        assert_eq!(parse("2025-01-02T03:04:05"), Some(expected));
+        assert_eq!(parse(" 2025-01-02T03:04:05Z "), Some(expected));
+    }
+
+    #[test]
+    fn test_parse_missing_values() {
+        assert_eq!(parse(""), None);
+        assert_eq!(parse("  "), None);
+    }
+
+    #[test]
+    fn test_parse_invalid_value() {
+        assert_eq!(parse("next tuesday"), None);
+    }
+}
@@ -0,0 +1,276 @@
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+
+use crate::{FirecrawlApp, FirecrawlError, routes};
+
+/// Credit usage of a team. Unknown fields of the response are ignored.
+#[serde_with::skip_serializing_none]
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct CreditUsage {
+    /// Number of credits remaining for the team
+    #[serde(alias = "remainingCredits")]
+    pub remaining_credits: u64,
+
+    /// Number of credits included in the team's plan per billing period
+    #[serde(default, alias = "planCredits")]
+    pub plan_credits: Option<u64>,
+
+    /// Start of the current billing period, as an ISO 8601 timestamp
+    #[serde(default, alias = "billingPeriodStart")]
+    pub billing_period_start: Option<String>,
+
+    /// End of the current billing period, as an ISO 8601 timestamp
+    #[serde(default, alias = "billingPeriodEnd")]
+    pub billing_period_end: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct CreditUsageResponse {
+    /// This will always be `true` due to `FirecrawlApp::handle_response`.
+    success: bool,
+
+    data: CreditUsage,
+}
+
+/// Token usage of a team by LLM extraction, i.e. extract jobs and the JSON format. Unknown
+/// fields of the response are ignored.
+#[serde_with::skip_serializing_none]
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct TokenUsage {
+    /// Number of tokens remaining for the team
+    #[serde(alias = "remainingTokens")]
+    pub remaining_tokens: u64,
+
+    /// Number of tokens included in the team's plan per billing period
+    #[serde(default, alias = "planTokens")]
+    pub plan_tokens: Option<u64>,
+
+    /// Start of the current billing period, as an ISO 8601 timestamp
+    #[serde(default, alias = "billingPeriodStart")]
+    pub billing_period_start: Option<String>,
+
+    /// End of the current billing period, as an ISO 8601 timestamp
+    #[serde(default, alias = "billingPeriodEnd")]
+    pub billing_period_end: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct TokenUsageResponse {
+    /// This will always be `true` due to `FirecrawlApp::handle_response`.
+    success: bool,
+
+    data: TokenUsage,
+}
+
+impl FirecrawlApp {
+    /// Returns the remaining credits of the team owning the API key.
+    ///
+    /// Requires an API key, so this fails with a configuration error on self-hosted instances without one.
+    pub async fn get_credit_usage(&self) -> Result<CreditUsage, FirecrawlError> {
+        if self.inner.api_key.is_none() {
+            return Err(FirecrawlError::configuration(
+                "API key is required to check credit usage",
+            ));
+        }
+
+        let request = self
+            .inner
+            .client
+            .get(self.endpoint(routes::credit_usage()))
+            .headers(self.prepare_headers(None)?);
+        let response = self
+            .send_request(request)
+            .await
+            .map_err(|e| FirecrawlError::HttpError("Checking credit usage".to_string(), e))?;
+
+        let response = self
+            .handle_response::<CreditUsageResponse>(response, "check credit usage")
+            .await?;
+
+        Ok(response.data)
+    }
+
+    /// Returns the remaining LLM extraction tokens of the team owning the API key.
+    ///
+    /// Requires an API key, so this fails with a configuration error without one. Instances
+    /// without the endpoint, e.g. self-hosted ones, fail with `FirecrawlError::UnsupportedEndpoint`.
+    pub async fn get_token_usage(&self) -> Result<TokenUsage, FirecrawlError> {
+        if self.inner.api_key.is_none() {
+            return Err(FirecrawlError::configuration(
+                "API key is required to check token usage",
+            ));
+        }
+
+        let url = self.endpoint(routes::token_usage());
+        let request = self
+            .inner
+            .client
+            .get(url.clone())
+            .headers(self.prepare_headers(None)?);
+        let response = self
+            .send_request(request)
+            .await
+            .map_err(|e| FirecrawlError::HttpError("Checking token usage".to_string(), e))?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(FirecrawlError::UnsupportedEndpoint(url.path().to_string()));
+        }
+
+        let response = self
+            .handle_response::<TokenUsageResponse>(response, "check token usage")
+            .await?;
+
+        Ok(response.data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{header, method, path},
+    };
+
+    #[tokio::test]
+    async fn test_get_credit_usage() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/team/credit-usage"))
+            .and(header("Authorization", "Bearer fc-test"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "data": { "remaining_credits": 1500 }
+            })))
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), Some("fc-test")).unwrap();
+        let usage = app.get_credit_usage().await.unwrap();
+
+        assert_eq!(usage.remaining_credits, 1500);
+    }
+
+    #[test]
+    fn test_credit_usage_deserialization() {
+        let response: CreditUsageResponse = serde_json::from_value(json!({
+            "success": true,
+            "data": {
+                "remaining_credits": 97213,
+                "plan_credits": 100000,
+                "billing_period_start": "2025-05-01T00:00:00.000Z",
+                "billing_period_end": "2025-06-01T00:00:00.000Z",
+                "team_id": "0b4e9c1a-2f6d-4c3b-9a8e-5d7f1e2c3b4a"
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            response.data,
+            CreditUsage {
+                remaining_credits: 97213,
+                plan_credits: Some(100000),
+                billing_period_start: Some("2025-05-01T00:00:00.000Z".to_string()),
+                billing_period_end: Some("2025-06-01T00:00:00.000Z".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_credit_usage_deserialization_camel_case() {
+        let usage: CreditUsage = serde_json::from_value(json!({
+            "remainingCredits": 500,
+            "planCredits": 3000,
+            "billingPeriodStart": null,
+            "billingPeriodEnd": null
+        }))
+        .unwrap();
+
+        assert_eq!(usage.remaining_credits, 500);
+        assert_eq!(usage.plan_credits, Some(3000));
+        assert_eq!(usage.billing_period_start, None);
+    }
+
+    #[tokio::test]
+    async fn test_get_credit_usage_requires_api_key() {
+        let app = FirecrawlApp::new_selfhosted("http://localhost:3002", None::<String>).unwrap();
+        let result = app.get_credit_usage().await;
+
+        assert!(matches!(
+            result,
+            Err(FirecrawlError::APIError(msg, _)) if msg == "Configuration"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_token_usage() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/team/token-usage"))
+            .and(header("Authorization", "Bearer fc-test"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "success": true,
+                "data": { "remaining_tokens": 250000 }
+            })))
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), Some("fc-test")).unwrap();
+        let usage = app.get_token_usage().await.unwrap();
+
+        assert_eq!(usage.remaining_tokens, 250000);
+        assert_eq!(usage.plan_tokens, None);
+    }
+
+    #[test]
+    fn test_token_usage_deserialization() {
+        let response: TokenUsageResponse = serde_json::from_value(json!({
+            "success": true,
+            "data": {
+                "remaining_tokens": 487512,
+                "plan_tokens": 500000,
+                "billing_period_start": "2025-05-01T00:00:00.000Z",
+                "billing_period_end": "2025-06-01T00:00:00.000Z"
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            response.data,
+            TokenUsage {
+                remaining_tokens: 487512,
+                plan_tokens: Some(500000),
+                billing_period_start: Some("2025-05-01T00:00:00.000Z".to_string()),
+                billing_period_end: Some("2025-06-01T00:00:00.000Z".to_string()),
+            }
+        );
+
+        let usage: TokenUsage =
+            serde_json::from_value(json!({ "remainingTokens": 12, "planTokens": 100 })).unwrap();
+        assert_eq!(usage.remaining_tokens, 12);
+        assert_eq!(usage.plan_tokens, Some(100));
+    }
+
+    #[tokio::test]
+    async fn test_get_token_usage_unsupported() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/team/token-usage"))
+            .respond_with(
+                ResponseTemplate::new(404).set_body_string("Cannot GET /v1/team/token-usage"),
+            )
+            .mount(&server)
+            .await;
+
+        let app = FirecrawlApp::new_selfhosted(server.uri(), Some("fc-test")).unwrap();
+        let error = app.get_token_usage().await.unwrap_err();
+
+        assert!(
+            matches!(&error, FirecrawlError::UnsupportedEndpoint(path) if path == "/v1/team/token-usage"),
+            "{error:?}"
+        );
+        assert!(!error.is_retryable());
+    }
+}
@@ -0,0 +1,199 @@
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv6Addr},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use url::{Host, Url};
+
+use crate::FirecrawlError;
+
+#[cfg(feature = "mcp-tool")]
+use schemars::JsonSchema;
+
+/// Webhook notified about the progress of crawl and batch scrape jobs.
+#[serde_with::skip_serializing_none]
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "mcp-tool", derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Webhook {
+    /// Webhook URL to notify when the job is complete
+    pub url: String,
+
+    /// Custom headers to send with webhook
+    pub headers: Option<HashMap<String, String>>,
+
+    /// Custom metadata to include in webhook payload
+    pub metadata: Option<HashMap<String, Value>>,
+
+    /// Events that trigger the webhook
+    pub events: Option<Vec<String>>,
+}
+
+impl Webhook {
+    /// A placeholder webhook on a reserved domain, for jobs that don't need notifications.
+    pub fn dummy() -> Self {
+        Webhook {
+            url: "https://webhook.example.com".to_string(),
+            headers: None,
+            metadata: None,
+            events: None,
+        }
+    }
+
+    /// Checks that Firecrawl could call the webhook, before starting a job that would otherwise
+    /// never notify it.
+    ///
+    /// Only the URL is checked, nothing is sent: it must use HTTPS and must not point at
+    /// `localhost` or a loopback, private or link-local IP address.
+    pub fn validate_reachable(&self) -> Result<(), FirecrawlError> {
+        let unreachable = |reason: &str| {
+            FirecrawlError::configuration(format!(
+                "Webhook URL {} is not reachable by Firecrawl: {reason}",
+                self.url
+            ))
+        };
+
+        let url = Url::parse(&self.url).map_err(|e| unreachable(&e.to_string()))?;
+        if url.scheme() != "https" {
+            return Err(unreachable("it must use HTTPS"));
+        }
+        let is_local = match url.host() {
+            Some(Host::Domain(domain)) => {
+                let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+                domain == "localhost" || domain.ends_with(".localhost")
+            }
+            Some(Host::Ipv4(ip)) => is_local_ip(IpAddr::V4(ip)),
+            Some(Host::Ipv6(ip)) => is_local_ip(IpAddr::V6(ip)),
+            None => return Err(unreachable("it has no host")),
+        };
+        if is_local {
+            return Err(unreachable("it points at a local or private address"));
+        }
+        Ok(())
+    }
+}
+
+/// Whether `ip` can only be reached from the local machine or network.
+fn is_local_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_local_ip(IpAddr::V4(ip)),
+            None => ip.is_loopback() || ip.is_unspecified() || is_local_ipv6(ip),
+        },
+    }
+}
+
+/// Unique local (`fc00::/7`) and link-local (`fe80::/10`) IPv6 addresses.
+fn is_local_ipv6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    first & 0xfe00 == 0xfc00 || first & 0xffc0 == 0xfe80
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_dummy_serializes_to_placeholder_url() {
+        assert_eq!(
+            serde_json::to_value(Webhook::dummy()).unwrap(),
+            json!({ "url": "https://webhook.example.com" })
+        );
+    }
+
+    #[test]
+    fn test_webhook_serialization() {
+        let webhook = Webhook {
+            url: "https://hooks.example.org/firecrawl".to_string(),
+            headers: Some(HashMap::from([(
+                "Authorization".to_string(),
+                "Bearer token".to_string(),
+            )])),
+            metadata: Some(HashMap::from([("run".to_string(), json!(7))])),
+            events: Some(vec!["completed".to_string(), "failed".to_string()]),
+        };
+
+        let value = serde_json::to_value(&webhook).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "url": "https://hooks.example.org/firecrawl",
+                "headers": { "Authorization": "Bearer token" },
+                "metadata": { "run": 7 },
+                "events": ["completed", "failed"]
+            })
+        );
+        assert_eq!(serde_json::from_value::<Webhook>(value).unwrap(), webhook);
+    }
+
+    fn webhook(url: &str) -> Webhook {
+        Webhook {
+            url: url.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_validate_reachable_rejects_localhost() {
+        for url in [
+            "https://localhost:3000/hook",
+            "https://LOCALHOST./hook",
+            "https://app.localhost/hook",
+            "https://127.0.0.1/hook",
+            "https://[::1]/hook",
+        ] {
+            let error = webhook(url).validate_reachable().unwrap_err();
+            assert!(
+                error.to_string().contains("local or private"),
+                "{url}: {error}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_reachable_rejects_private_ips() {
+        for url in [
+            "https://10.0.0.5/hook",
+            "https://192.168.1.20:8443/hook",
+            "https://172.16.0.1/hook",
+            "https://169.254.169.254/hook",
+            "https://0.0.0.0/hook",
+            "https://[fd12::1]/hook",
+            "https://[fe80::1]/hook",
+            "https://[::ffff:192.168.1.1]/hook",
+        ] {
+            assert!(webhook(url).validate_reachable().is_err(), "{url}");
+        }
+    }
+
+    #[test]
+    fn test_validate_reachable_rejects_plain_http_and_invalid_urls() {
+        let error = webhook("http://hooks.example.org/firecrawl")
+            .validate_reachable()
+            .unwrap_err();
+        assert!(error.to_string().contains("HTTPS"), "{error}");
+        assert!(webhook("not a url").validate_reachable().is_err());
+    }
+
+    #[test]
+    fn test_validate_reachable_accepts_public_https_urls() {
+        for url in [
+            "https://hooks.example.org/firecrawl",
+            "https://203.0.113.7/hook",
+            "https://[2001:db8::1]/hook",
+        ] {
+            webhook(url).validate_reachable().unwrap();
+        }
+        Webhook::dummy().validate_reachable().unwrap();
+    }
+}
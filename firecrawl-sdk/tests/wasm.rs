@@ -0,0 +1,50 @@
+//! Runs on `wasm32-unknown-unknown`, with `wasm-bindgen-test-runner` as the test runner:
+//!
+//! ```sh
+//! CARGO_TARGET_WASM32_UNKNOWN_UNKNOWN_RUNNER=wasm-bindgen-test-runner \
+//!     cargo test -p firecrawl-sdk --target wasm32-unknown-unknown --no-default-features \
+//!     --features wasm,scrape --test wasm
+//! ```
+#![cfg(target_arch = "wasm32")]
+
+use std::sync::{Arc, Mutex};
+
+use firecrawl_sdk::FirecrawlApp;
+use firecrawl_sdk::scrape::{ScrapeFormats, ScrapeOptions};
+use serde_json::{Value, json};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+async fn test_scrape_url_request() {
+    let sent = Arc::new(Mutex::new(None));
+    let recorded = sent.clone();
+    // Nothing listens there, so the request fails once built and handed to fetch
+    let app = FirecrawlApp::new_selfhosted("http://127.0.0.1:9", Some("fc-test"))
+        .unwrap()
+        .with_observer(move |info| {
+            if info.is_before_send() {
+                *recorded.lock().unwrap() = Some((
+                    info.method.to_string(),
+                    info.url.to_string(),
+                    info.body
+                        .map(|body| serde_json::from_slice::<Value>(body).unwrap()),
+                ));
+            }
+        });
+
+    let result = app
+        .scrape_url(
+            "https://example.com",
+            ScrapeOptions::default().with_formats([ScrapeFormats::Markdown]),
+        )
+        .await;
+
+    assert!(result.is_err());
+    let (method, url, body) = sent.lock().unwrap().take().unwrap();
+    assert_eq!(method, "POST");
+    assert_eq!(url, "http://127.0.0.1:9/v1/scrape");
+    assert_eq!(
+        body,
+        Some(json!({ "url": "https://example.com", "formats": ["markdown"] }))
+    );
+}